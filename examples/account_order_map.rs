@@ -60,14 +60,10 @@ async fn main() -> Result<()> {
                 .get_order_normalized(pool, order_id)
                 .await?
             {
-                let remaining_quantity = order.quantity.parse::<f64>().unwrap()
-                    - order.filled_quantity.parse::<f64>().unwrap();
-                let order_price = order.normalized_price.parse::<f64>().unwrap();
-
                 if order.is_bid {
-                    bid_orders.push((order_price, remaining_quantity));
+                    bid_orders.push((order.normalized_price, order.remaining_quantity));
                 } else {
-                    ask_orders.push((order_price, remaining_quantity));
+                    ask_orders.push((order.normalized_price, order.remaining_quantity));
                 }
             }
         }
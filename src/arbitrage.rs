@@ -0,0 +1,507 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{Argument, Command, ProgrammableTransaction};
+use sui_sdk::types::{Identifier, TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
+use tokio::sync::mpsc;
+
+use crate::client::{DeepBookClient, PoolDeepPrice};
+use crate::transactions::flashloan::FlashLoanAsset;
+use crate::utils::config::{PoolMap, DEEP_SCALAR};
+use crate::DataReader;
+
+/// Direction of a swap through a pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwapDirection {
+    BaseToQuote,
+    QuoteToBase,
+}
+
+/// A single swap in an arbitrage cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageLeg {
+    pub pool_key: String,
+    pub direction: SwapDirection,
+}
+
+/// Borrow an asset from `loan_pool_key`, swap it through `legs` back into the same
+/// asset, and repay the loan
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageCycle {
+    pub loan_pool_key: String,
+    pub loan_asset: FlashLoanAsset,
+    pub loan_coin_key: String,
+    pub legs: Vec<ArbitrageLeg>,
+}
+
+/// Simulated result of a single leg
+#[derive(Debug, Clone)]
+pub struct LegQuote {
+    pub pool_key: String,
+    pub direction: SwapDirection,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    pub deep_required: f64,
+}
+
+/// Simulated result of a full cycle, denominated in the borrowed asset
+#[derive(Debug, Clone)]
+pub struct CycleQuote {
+    pub cycle: ArbitrageCycle,
+    pub borrow_amount: f64,
+    pub legs: Vec<LegQuote>,
+    pub amount_out: f64,
+    pub deep_required: f64,
+    pub deep_cost: f64,
+    pub profit: f64,
+}
+
+impl CycleQuote {
+    /// Price a cycle from its simulated legs, valuing DEEP fees in the borrowed asset
+    ///
+    /// Each leg's DEEP fee is valued in the leg's input asset at its pool's DEEP price,
+    /// then carried back to the loan asset at the rate realized along the path so far.
+    ///
+    /// @param cycle - The quoted cycle
+    /// @param borrow_amount - The amount of the loan asset borrowed
+    /// @param legs - Each leg's quote, with its pool's DEEP price if the leg pays DEEP
+    pub fn new(
+        cycle: &ArbitrageCycle,
+        borrow_amount: f64,
+        legs: Vec<(LegQuote, Option<PoolDeepPrice>)>,
+    ) -> anyhow::Result<Self> {
+        let mut quotes = Vec::with_capacity(legs.len());
+        let mut amount_out = borrow_amount;
+        let mut deep_required = 0.0;
+        let mut deep_cost = 0.0;
+
+        for (leg, deep_price) in legs {
+            if leg.deep_required > 0.0 {
+                let input_is_base = leg.direction == SwapDirection::BaseToQuote;
+                let (deep_per_base, deep_per_quote) = deep_price.map_or((None, None), |price| {
+                    (price.deep_per_base, price.deep_per_quote)
+                });
+                let cost_in_input = match (deep_per_base, deep_per_quote) {
+                    (Some(deep_per_base), _) if deep_per_base > 0.0 => {
+                        let cost_in_base = leg.deep_required / deep_per_base;
+                        if input_is_base {
+                            cost_in_base
+                        } else {
+                            cost_in_base * leg.amount_in / leg.amount_out.max(f64::EPSILON)
+                        }
+                    }
+                    (_, Some(deep_per_quote)) if deep_per_quote > 0.0 => {
+                        let cost_in_quote = leg.deep_required / deep_per_quote;
+                        if input_is_base {
+                            cost_in_quote * leg.amount_in / leg.amount_out.max(f64::EPSILON)
+                        } else {
+                            cost_in_quote
+                        }
+                    }
+                    _ => return Err(anyhow::anyhow!("No DEEP price for pool {}", leg.pool_key)),
+                };
+                deep_cost += cost_in_input * borrow_amount / leg.amount_in;
+                deep_required += leg.deep_required;
+            }
+            amount_out = leg.amount_out;
+            quotes.push(leg);
+        }
+
+        Ok(Self {
+            cycle: cycle.clone(),
+            borrow_amount,
+            legs: quotes,
+            amount_out,
+            deep_required,
+            deep_cost,
+            profit: amount_out - borrow_amount - deep_cost,
+        })
+    }
+
+    /// Profit relative to the borrowed amount
+    pub fn profit_ratio(&self) -> f64 {
+        if self.borrow_amount == 0.0 {
+            return 0.0;
+        }
+        self.profit / self.borrow_amount
+    }
+}
+
+/// A profitable cycle together with a ready-to-sign transaction
+#[derive(Debug, Clone)]
+pub struct ArbitrageOpportunity {
+    pub quote: CycleQuote,
+    pub transaction: ProgrammableTransaction,
+}
+
+/// Outcome of one scan over every configured cycle
+#[derive(Debug, Clone, Default)]
+pub struct ArbitrageScan {
+    pub opportunities: Vec<ArbitrageOpportunity>,
+    /// Cycles that could not be quoted or built, with the error
+    pub failed: Vec<(ArbitrageCycle, String)>,
+}
+
+/// Settings for the arbitrage planner
+#[derive(Debug, Clone)]
+pub struct ArbitrageConfig {
+    /// Amount to borrow per loan asset, keyed by coin key. Assets without an entry are skipped
+    pub borrow_amounts: HashMap<String, f64>,
+    /// Minimum profit, relative to the borrowed amount, required to emit a transaction
+    pub min_profit_ratio: f64,
+    /// Slippage tolerance applied to each leg's quoted output when setting `min_out`
+    pub slippage: f64,
+    /// Maximum number of swaps in a cycle
+    pub max_legs: usize,
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            borrow_amounts: HashMap::new(),
+            min_profit_ratio: 0.001,
+            slippage: 0.001,
+            max_legs: 2,
+        }
+    }
+}
+
+/// Enumerate flash-loan arbitrage cycles over a set of pools
+///
+/// Every pool can lend either of its assets. A cycle swaps the borrowed asset through
+/// up to `max_legs` other distinct pools and ends back in the borrowed asset.
+///
+/// @param pools - The pools to search
+/// @param max_legs - Maximum number of swaps in a cycle
+pub fn find_cycles(pools: &PoolMap, max_legs: usize) -> Vec<ArbitrageCycle> {
+    let mut pool_keys: Vec<&str> = pools.keys().copied().collect();
+    pool_keys.sort_unstable();
+
+    let mut cycles = Vec::new();
+    for loan_pool_key in &pool_keys {
+        let loan_pool = &pools[loan_pool_key];
+        for (loan_asset, loan_coin) in [
+            (FlashLoanAsset::Base, &loan_pool.base_coin),
+            (FlashLoanAsset::Quote, &loan_pool.quote_coin),
+        ] {
+            let mut path = Vec::new();
+            extend_cycles(
+                pools,
+                &pool_keys,
+                loan_pool_key,
+                loan_coin,
+                loan_coin,
+                max_legs,
+                &mut path,
+                &mut |legs| {
+                    cycles.push(ArbitrageCycle {
+                        loan_pool_key: loan_pool_key.to_string(),
+                        loan_asset,
+                        loan_coin_key: loan_coin.clone(),
+                        legs,
+                    })
+                },
+            );
+        }
+    }
+    cycles
+}
+
+#[allow(clippy::too_many_arguments)]
+fn extend_cycles(
+    pools: &PoolMap,
+    pool_keys: &[&'static str],
+    loan_pool_key: &str,
+    loan_coin: &str,
+    current_coin: &str,
+    max_legs: usize,
+    path: &mut Vec<ArbitrageLeg>,
+    emit: &mut dyn FnMut(Vec<ArbitrageLeg>),
+) {
+    if path.len() == max_legs {
+        return;
+    }
+
+    for pool_key in pool_keys {
+        if *pool_key == loan_pool_key || path.iter().any(|leg| leg.pool_key == *pool_key) {
+            continue;
+        }
+
+        let pool = &pools[pool_key];
+        let (direction, next_coin) = if pool.base_coin == current_coin {
+            (SwapDirection::BaseToQuote, pool.quote_coin.as_str())
+        } else if pool.quote_coin == current_coin {
+            (SwapDirection::QuoteToBase, pool.base_coin.as_str())
+        } else {
+            continue;
+        };
+
+        path.push(ArbitrageLeg {
+            pool_key: pool_key.to_string(),
+            direction,
+        });
+        if next_coin == loan_coin {
+            if path.len() >= 2 {
+                emit(path.clone());
+            }
+        } else {
+            extend_cycles(
+                pools,
+                pool_keys,
+                loan_pool_key,
+                loan_coin,
+                next_coin,
+                max_legs,
+                path,
+                emit,
+            );
+        }
+        path.pop();
+    }
+}
+
+/// Quotes flash-loan arbitrage cycles via dev-inspect and builds transactions for profitable ones
+pub struct ArbitragePlanner<'a> {
+    client: &'a DeepBookClient,
+    config: ArbitrageConfig,
+}
+
+impl<'a> ArbitragePlanner<'a> {
+    /// Creates a new ArbitragePlanner
+    ///
+    /// @param client - The DeepBookClient used for quoting and building
+    /// @param config - The ArbitrageConfig settings
+    pub fn new(client: &'a DeepBookClient, config: ArbitrageConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// All cycles over the client's configured pools
+    pub fn cycles(&self) -> Vec<ArbitrageCycle> {
+        find_cycles(self.client.config().pools(), self.config.max_legs)
+    }
+
+    /// Quote every leg of a cycle, including DEEP fees valued in the borrowed asset
+    ///
+    /// @param cycle - The cycle to quote
+    /// @param borrow_amount - The amount of the loan asset to borrow
+    pub async fn quote_cycle(
+        &self,
+        cycle: &ArbitrageCycle,
+        borrow_amount: f64,
+    ) -> anyhow::Result<CycleQuote> {
+        let mut legs = Vec::with_capacity(cycle.legs.len());
+        let mut amount_in = borrow_amount;
+        for leg in &cycle.legs {
+            let pool = self.client.config().pool_ref(&leg.pool_key)?;
+            let (amount_out, deep_required) = match leg.direction {
+                SwapDirection::BaseToQuote => {
+                    let out = self
+                        .client
+                        .get_quote_quantity_out_by_ref(&pool, amount_in)
+                        .await?;
+                    (out.quote_out, out.deep_required)
+                }
+                SwapDirection::QuoteToBase => {
+                    let out = self
+                        .client
+                        .get_base_quantity_out_by_ref(&pool, amount_in)
+                        .await?;
+                    (out.base_out, out.deep_required)
+                }
+            };
+            let deep_price = if deep_required > 0.0 {
+                Some(self.client.get_pool_deep_price_by_ref(&pool).await?)
+            } else {
+                None
+            };
+
+            legs.push((
+                LegQuote {
+                    pool_key: leg.pool_key.clone(),
+                    direction: leg.direction,
+                    amount_in,
+                    amount_out,
+                    deep_required,
+                },
+                deep_price,
+            ));
+            amount_in = amount_out;
+        }
+
+        CycleQuote::new(cycle, borrow_amount, legs)
+    }
+
+    /// Build a PTB that borrows, runs every leg, repays the loan and sends the profit to `sender`
+    ///
+    /// @param quote - The quoted cycle to execute
+    /// @param sender - The address signing the transaction and paying DEEP fees
+    pub async fn build_transaction(
+        &self,
+        quote: &CycleQuote,
+        sender: SuiAddress,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let deep_coin = self.deep_coin(&mut ptb, quote, sender).await?;
+
+        let mut pools = Vec::with_capacity(quote.legs.len());
+        for leg in &quote.legs {
+            pools.push(self.client.config().pool_ref(&leg.pool_key)?);
+        }
+
+        let slippage = self.config.slippage;
+        let deep_book = &self.client.deep_book;
+        let session = self.client.flash_loans.session(
+            &quote.cycle.loan_pool_key,
+            quote.cycle.loan_asset,
+            quote.borrow_amount,
+        )?;
+
+        let profit = session
+            .run(&mut ptb, async |ptb, borrowed| {
+                let mut coin = borrowed;
+                let mut deep_coin = deep_coin;
+                for (leg, pool) in quote.legs.iter().zip(&pools) {
+                    let min_out = leg.amount_out * (1.0 - slippage);
+                    let (coin_out, leftover, deep_left) = match leg.direction {
+                        SwapDirection::BaseToQuote => {
+                            let (base_left, quote_out, deep_left) = deep_book
+                                .swap_exact_base_for_quote_with_coins(
+                                    ptb, pool, coin, deep_coin, min_out,
+                                )
+                                .await?;
+                            (quote_out, base_left, deep_left)
+                        }
+                        SwapDirection::QuoteToBase => {
+                            let (base_out, quote_left, deep_left) = deep_book
+                                .swap_exact_quote_for_base_with_coins(
+                                    ptb, pool, coin, deep_coin, min_out,
+                                )
+                                .await?;
+                            (base_out, quote_left, deep_left)
+                        }
+                    };
+                    ptb.transfer_arg(sender, leftover);
+                    coin = coin_out;
+                    deep_coin = deep_left;
+                }
+                ptb.transfer_arg(sender, deep_coin);
+                Ok(coin)
+            })
+            .await?;
+
+        ptb.transfer_arg(sender, profit);
+        Ok(ptb.finish())
+    }
+
+    /// Add the DEEP coin paying the cycle's fees to a PTB
+    ///
+    /// A zero-value coin is created when no leg pays DEEP. Otherwise the sender's DEEP
+    /// coins are merged until they cover `deep_required`.
+    async fn deep_coin(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        quote: &CycleQuote,
+        sender: SuiAddress,
+    ) -> anyhow::Result<Argument> {
+        let deep_coin = self.client.config().get_coin("DEEP")?;
+        let deep_amount = (quote.deep_required * DEEP_SCALAR as f64).ceil() as u64;
+        if deep_amount == 0 {
+            return Ok(ptb.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("coin")?,
+                Identifier::new("zero")?,
+                vec![TypeTag::from_str(&deep_coin.type_name)?],
+                vec![],
+            ));
+        }
+
+        let sui_client = self.client.sui_client();
+        let coins = sui_client
+            .get_coin_objects(sender, deep_coin.type_name.clone(), deep_amount)
+            .await?;
+        let mut arguments = Vec::with_capacity(coins.len());
+        for coin in coins {
+            arguments.push(ptb.obj(sui_client.coin_object(coin).await?)?);
+        }
+        let (first, rest) = arguments
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("No DEEP coins owned by {}", sender))?;
+        if !rest.is_empty() {
+            ptb.command(Command::MergeCoins(*first, rest.to_vec()));
+        }
+        Ok(*first)
+    }
+
+    /// Quote every configured cycle once and return the profitable ones with transactions
+    ///
+    /// Cycles that fail to quote, for example because a book is empty, or fail to build,
+    /// for example because the sender lacks DEEP, are reported in `failed`.
+    ///
+    /// @param sender - The address signing the transactions
+    pub async fn scan(&self, sender: SuiAddress) -> ArbitrageScan {
+        let mut scan = ArbitrageScan::default();
+        for cycle in self.cycles() {
+            let borrow_amount = match self.config.borrow_amounts.get(&cycle.loan_coin_key) {
+                Some(amount) => *amount,
+                None => continue,
+            };
+            let quote = match self.quote_cycle(&cycle, borrow_amount).await {
+                Ok(quote) => quote,
+                Err(e) => {
+                    scan.failed.push((cycle, e.to_string()));
+                    continue;
+                }
+            };
+            if quote.profit_ratio() < self.config.min_profit_ratio {
+                continue;
+            }
+
+            match self.build_transaction(&quote, sender).await {
+                Ok(transaction) => scan
+                    .opportunities
+                    .push(ArbitrageOpportunity { quote, transaction }),
+                Err(e) => scan.failed.push((cycle, e.to_string())),
+            }
+        }
+        scan
+    }
+
+    /// Scan continuously, sending profitable opportunities to `sink` until it is closed
+    ///
+    /// Failed cycles do not stop the loop; each is passed to `on_error`.
+    ///
+    /// @param sender - The address signing the transactions
+    /// @param interval - Time between scans
+    /// @param sink - Channel receiving opportunities
+    /// @param on_error - Called with every cycle that failed to quote or build, and its error
+    pub async fn scan_forever<F>(
+        &self,
+        sender: SuiAddress,
+        interval: Duration,
+        sink: mpsc::Sender<ArbitrageOpportunity>,
+        mut on_error: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut(&ArbitrageCycle, &str),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let scan = self.scan(sender).await;
+            for (cycle, error) in &scan.failed {
+                on_error(cycle, error);
+            }
+            for opportunity in scan.opportunities {
+                if sink.send(opportunity).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
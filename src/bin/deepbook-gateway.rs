@@ -0,0 +1,341 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use sui_deepbookv3::cache::TtlCache;
+use sui_deepbookv3::client::DeepBookClient;
+use sui_deepbookv3::utils::file_config::FileConfig;
+use sui_deepbookv3::utils::types::OrderId;
+
+/// Serve DeepBook reads over HTTP and WebSocket
+#[derive(Parser)]
+#[command(name = "deepbook-gateway", version)]
+struct Args {
+    /// Path of the JSON config file
+    #[arg(long, default_value = "deepbook.json")]
+    config: PathBuf,
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    /// How long read results are reused, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    cache_ttl_ms: u64,
+    /// Time between book updates pushed over WebSocket, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    book_interval_ms: u64,
+}
+
+/// Ticks on each side of the mid price when a book request does not set them
+const DEFAULT_TICKS: u64 = 10;
+/// Most ticks a book request may ask for
+const MAX_TICKS: u64 = 100;
+
+struct Gateway {
+    client: DeepBookClient,
+    cache: TtlCache<Value>,
+    book_interval: Duration,
+}
+
+/// An error returned to the caller as `{"error": ...}`
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(message: impl ToString) -> Self {
+        Self(StatusCode::NOT_FOUND, message.to_string())
+    }
+
+    fn bad_request(message: impl ToString) -> Self {
+        Self(StatusCode::BAD_REQUEST, message.to_string())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self(StatusCode::BAD_GATEWAY, e.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult = Result<Json<Value>, ApiError>;
+
+#[derive(Deserialize)]
+struct BookQuery {
+    ticks: Option<u64>,
+}
+
+impl BookQuery {
+    fn ticks(&self) -> Result<u64, ApiError> {
+        match self.ticks.unwrap_or(DEFAULT_TICKS) {
+            ticks @ 1..=MAX_TICKS => Ok(ticks),
+            _ => Err(ApiError::bad_request(format!(
+                "ticks must be between 1 and {}",
+                MAX_TICKS
+            ))),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BalancesQuery {
+    /// Comma separated coin keys, every configured coin when missing
+    coins: Option<String>,
+}
+
+impl Gateway {
+    fn check_pool(&self, pool_key: &str) -> Result<(), ApiError> {
+        self.client
+            .config()
+            .get_pool(pool_key)
+            .map(|_| ())
+            .map_err(ApiError::not_found)
+    }
+
+    fn check_manager(&self, manager_key: &str) -> Result<(), ApiError> {
+        self.client
+            .config()
+            .get_balance_manager(manager_key)
+            .map(|_| ())
+            .map_err(ApiError::not_found)
+    }
+
+    /// Serve a read from the cache, reading it on a miss
+    async fn cached<T, F>(&self, key: String, read: F) -> Result<Value, ApiError>
+    where
+        T: Serialize,
+        F: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        Ok(self
+            .cache
+            .get_or_try_insert_with(&key, || async move {
+                Ok::<_, anyhow::Error>(serde_json::to_value(read.await?)?)
+            })
+            .await?)
+    }
+
+    async fn book(&self, pool_key: &str, ticks: u64) -> Result<Value, ApiError> {
+        self.cached(
+            format!("book/{}/{}", pool_key, ticks),
+            self.client.get_level2_ticks_from_mid(pool_key, ticks),
+        )
+        .await
+    }
+}
+
+async fn pools(State(gateway): State<Arc<Gateway>>) -> ApiResult {
+    let mut pools: Vec<_> = gateway.client.config().pools().iter().collect();
+    pools.sort_unstable_by_key(|(key, _)| **key);
+    Ok(Json(Value::Array(
+        pools
+            .iter()
+            .map(|(key, pool)| {
+                json!({
+                    "key": key,
+                    "address": pool.address,
+                    "base_coin": pool.base_coin,
+                    "quote_coin": pool.quote_coin,
+                })
+            })
+            .collect(),
+    )))
+}
+
+async fn mid(State(gateway): State<Arc<Gateway>>, Path(pool_key): Path<String>) -> ApiResult {
+    gateway.check_pool(&pool_key)?;
+    let mid_price = gateway
+        .cached(
+            format!("mid/{}", pool_key),
+            gateway.client.mid_price(&pool_key),
+        )
+        .await?;
+    Ok(Json(json!({ "pool": pool_key, "mid_price": mid_price })))
+}
+
+async fn book(
+    State(gateway): State<Arc<Gateway>>,
+    Path(pool_key): Path<String>,
+    Query(query): Query<BookQuery>,
+) -> ApiResult {
+    gateway.check_pool(&pool_key)?;
+    Ok(Json(gateway.book(&pool_key, query.ticks()?).await?))
+}
+
+async fn vault(State(gateway): State<Arc<Gateway>>, Path(pool_key): Path<String>) -> ApiResult {
+    gateway.check_pool(&pool_key)?;
+    Ok(Json(
+        gateway
+            .cached(
+                format!("vault/{}", pool_key),
+                gateway.client.vault_balances(&pool_key),
+            )
+            .await?,
+    ))
+}
+
+async fn params(State(gateway): State<Arc<Gateway>>, Path(pool_key): Path<String>) -> ApiResult {
+    gateway.check_pool(&pool_key)?;
+    let client = &gateway.client;
+    Ok(Json(
+        gateway
+            .cached(format!("params/{}", pool_key), async {
+                Ok::<_, anyhow::Error>(json!({
+                    "book": client.pool_book_params(&pool_key).await?,
+                    "trade": client.pool_trade_params(&pool_key).await?,
+                }))
+            })
+            .await?,
+    ))
+}
+
+async fn order(
+    State(gateway): State<Arc<Gateway>>,
+    Path((pool_key, order_id)): Path<(String, String)>,
+) -> ApiResult {
+    gateway.check_pool(&pool_key)?;
+    let order_id: OrderId = order_id.parse().map_err(ApiError::bad_request)?;
+    let order = gateway
+        .cached(
+            format!("order/{}/{}", pool_key, order_id),
+            gateway.client.get_order_normalized(&pool_key, order_id),
+        )
+        .await?;
+    if order.is_null() {
+        return Err(ApiError::not_found(format!("Order {} not found", order_id)));
+    }
+    Ok(Json(order))
+}
+
+async fn balances(
+    State(gateway): State<Arc<Gateway>>,
+    Path(manager_key): Path<String>,
+    Query(query): Query<BalancesQuery>,
+) -> ApiResult {
+    gateway.check_manager(&manager_key)?;
+    let coins: Vec<String> = match &query.coins {
+        Some(coins) => coins
+            .split(',')
+            .map(|coin| coin.trim().to_string())
+            .collect(),
+        None => {
+            let mut coins: Vec<String> = gateway
+                .client
+                .config()
+                .coins()
+                .keys()
+                .map(|coin| coin.to_string())
+                .collect();
+            coins.sort_unstable();
+            coins
+        }
+    };
+    for coin in &coins {
+        gateway
+            .client
+            .config()
+            .get_coin(coin)
+            .map_err(ApiError::not_found)?;
+    }
+
+    let mut balances = serde_json::Map::new();
+    for coin in coins {
+        let balance = gateway
+            .cached(format!("balance/{}/{}", manager_key, coin), async {
+                let (_, balance) = gateway
+                    .client
+                    .check_manager_balance(&manager_key, &coin)
+                    .await?;
+                Ok::<_, anyhow::Error>(balance)
+            })
+            .await?;
+        balances.insert(coin, balance);
+    }
+    Ok(Json(
+        json!({ "manager": manager_key, "balances": balances }),
+    ))
+}
+
+async fn book_updates(
+    State(gateway): State<Arc<Gateway>>,
+    Path(pool_key): Path<String>,
+    Query(query): Query<BookQuery>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    gateway.check_pool(&pool_key)?;
+    let ticks = query.ticks()?;
+    Ok(upgrade.on_upgrade(move |socket| push_book(socket, gateway, pool_key, ticks)))
+}
+
+/// Send the book whenever it changes until the client goes away
+async fn push_book(mut socket: WebSocket, gateway: Arc<Gateway>, pool_key: String, ticks: u64) {
+    let mut interval = tokio::time::interval(gateway.book_interval);
+    let mut last: Option<Value> = None;
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let update = match gateway.book(&pool_key, ticks).await {
+                    Ok(book) if last.as_ref() == Some(&book) => continue,
+                    Ok(book) => {
+                        last = Some(book.clone());
+                        json!({ "pool": pool_key, "book": book })
+                    }
+                    Err(ApiError(_, error)) => json!({ "pool": pool_key, "error": error }),
+                };
+                if socket.send(Message::Text(update.to_string().into())).await.is_err() {
+                    return;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn router(gateway: Arc<Gateway>) -> Router {
+    Router::new()
+        .route("/pools", get(pools))
+        .route("/pools/{key}/mid", get(mid))
+        .route("/pools/{key}/book", get(book))
+        .route("/pools/{key}/book/ws", get(book_updates))
+        .route("/pools/{key}/vault", get(vault))
+        .route("/pools/{key}/params", get(params))
+        .route("/pools/{key}/orders/{id}", get(order))
+        .route("/managers/{key}/balances", get(balances))
+        .with_state(gateway)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = FileConfig::load(&args.config)?;
+    let gateway = Arc::new(Gateway {
+        client: config.client(None).await?,
+        cache: TtlCache::new(Duration::from_millis(args.cache_ttl_ms)),
+        book_interval: Duration::from_millis(args.book_interval_ms.max(1)),
+    });
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    println!("Listening on {}", args.listen);
+    axum::serve(listener, router(gateway)).await?;
+    Ok(())
+}
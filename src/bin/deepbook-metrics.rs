@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use clap::Parser;
+use tokio::sync::RwLock;
+
+use sui_deepbookv3::client::DeepBookClient;
+use sui_deepbookv3::metrics::{render, MetricsSampler};
+use sui_deepbookv3::utils::file_config::FileConfig;
+
+/// Export DeepBook pool and balance manager state as Prometheus metrics
+#[derive(Parser)]
+#[command(name = "deepbook-metrics", version)]
+struct Args {
+    /// Path of the JSON config file
+    #[arg(long, default_value = "deepbook.json")]
+    config: PathBuf,
+    /// Address to serve /metrics on
+    #[arg(long, default_value = "127.0.0.1:9184")]
+    listen: SocketAddr,
+    /// Seconds between sampling rounds
+    #[arg(long, default_value_t = 30)]
+    interval_secs: u64,
+    /// Pools to sample, every configured pool when missing
+    #[arg(long, value_delimiter = ',')]
+    pools: Vec<String>,
+    /// Balance managers to sample, every manager of the config file when missing
+    #[arg(long, value_delimiter = ',')]
+    managers: Vec<String>,
+}
+
+/// Sample on every interval tick, replacing the rendered metrics
+async fn sample_loop(
+    client: DeepBookClient,
+    pool_keys: Vec<String>,
+    manager_keys: Vec<String>,
+    interval: Duration,
+    metrics: Arc<RwLock<String>>,
+) -> Result<()> {
+    let sampler = MetricsSampler::new(&client, pool_keys, manager_keys)?;
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let sample = sampler.sample().await;
+        for (key, error) in sample.failed_pools.iter().chain(&sample.failed_managers) {
+            eprintln!("Failed to sample {}: {}", key, error);
+        }
+        *metrics.write().await = render(&sample);
+    }
+}
+
+async fn serve_metrics(State(metrics): State<Arc<RwLock<String>>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.read().await.clone(),
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = FileConfig::load(&args.config)?;
+    let client = config.client(None).await?;
+
+    let mut pool_keys = args.pools;
+    if pool_keys.is_empty() {
+        pool_keys = client
+            .config()
+            .pools()
+            .keys()
+            .map(|key| key.to_string())
+            .collect();
+        pool_keys.sort_unstable();
+    }
+    let mut manager_keys = args.managers;
+    if manager_keys.is_empty() {
+        manager_keys = config.balance_managers.keys().cloned().collect();
+        manager_keys.sort_unstable();
+    }
+    // Fail on unknown keys before serving
+    MetricsSampler::new(&client, pool_keys.clone(), manager_keys.clone())?;
+
+    let metrics = Arc::new(RwLock::new(String::new()));
+    let sampler = tokio::spawn(sample_loop(
+        client,
+        pool_keys,
+        manager_keys,
+        Duration::from_secs(args.interval_secs.max(1)),
+        metrics.clone(),
+    ));
+
+    let router = Router::new()
+        .route("/metrics", get(serve_metrics))
+        .with_state(metrics);
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    println!("Serving metrics on http://{}/metrics", args.listen);
+    tokio::select! {
+        result = axum::serve(listener, router) => result?,
+        result = sampler => result??,
+    }
+    Ok(())
+}
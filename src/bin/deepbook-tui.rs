@@ -0,0 +1,642 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Clear, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use tokio::sync::mpsc;
+
+use sui_deepbookv3::client::{DeepBookClient, Level2TicksFromMid, NormalizedOrder, PoolBookParams};
+use sui_deepbookv3::executor::TransactionExecutor;
+use sui_deepbookv3::history::{Trade, TradeHistory};
+use sui_deepbookv3::ladder::{build_ladder, spread, LadderRow};
+use sui_deepbookv3::utils::client_order_id::{ClientOrderIdGenerator, TimePrefixedIdGenerator};
+use sui_deepbookv3::utils::file_config::FileConfig;
+use sui_deepbookv3::utils::types::PlaceLimitOrderParams;
+
+/// Live order book and account monitor for one pool and balance manager
+#[derive(Parser)]
+#[command(name = "deepbook-tui", version)]
+struct Args {
+    /// Key of the pool
+    pool: String,
+    /// Key of the balance manager
+    manager: String,
+    /// Path of the JSON config file
+    #[arg(long, default_value = "deepbook.json")]
+    config: PathBuf,
+    /// Number of ticks shown on each side of the mid price
+    #[arg(long, default_value_t = 15)]
+    ticks: u64,
+    /// Seconds between refreshes
+    #[arg(long, default_value_t = 2)]
+    refresh_secs: u64,
+    /// Quantity of orders placed from the ladder, the pool's minimum size by default
+    #[arg(long)]
+    size: Option<f64>,
+}
+
+/// Number of recent trades shown
+const TRADES_SHOWN: usize = 20;
+/// Maximum number of events scanned for recent trades
+const TRADE_EVENTS_SCANNED: usize = 200;
+
+struct Snapshot {
+    book: Level2TicksFromMid,
+    ladder: Vec<LadderRow>,
+    mid_price: Option<f64>,
+    orders: Vec<NormalizedOrder>,
+    trades: Vec<Trade>,
+    /// Why recent trades could not be read, if they could not
+    trades_error: Option<String>,
+    balances: Vec<(String, f64)>,
+}
+
+/// A transaction waiting for confirmation
+enum Action {
+    Place {
+        is_bid: bool,
+        price: f64,
+        quantity: f64,
+    },
+    Cancel(NormalizedOrder),
+}
+
+impl Action {
+    fn describe(&self) -> String {
+        match self {
+            Action::Place {
+                is_bid,
+                price,
+                quantity,
+            } => format!(
+                "Place {} {} at {}",
+                if *is_bid { "bid" } else { "ask" },
+                quantity,
+                price
+            ),
+            Action::Cancel(order) => format!(
+                "Cancel {} {} at {} (order {})",
+                if order.is_bid { "bid" } else { "ask" },
+                order.remaining_quantity,
+                order.normalized_price,
+                order.order_id
+            ),
+        }
+    }
+}
+
+/// Work handed to the background task
+enum Request {
+    Refresh,
+    Execute(Action),
+}
+
+/// Results sent back to the UI by the background task
+enum Response {
+    Snapshot(Result<Snapshot>),
+    Executed(Action, Result<String>),
+}
+
+/// Owns the client and performs all network calls, so the UI never blocks on them
+struct Worker {
+    pool: String,
+    manager: String,
+    ticks: u64,
+    tick_size: f64,
+    config: FileConfig,
+    client: DeepBookClient,
+    ids: TimePrefixedIdGenerator,
+}
+
+impl Worker {
+    /// Serve requests until the UI hangs up
+    ///
+    /// @param requests - Work sent by the UI
+    /// @param responses - Where results are sent
+    async fn run(
+        self,
+        mut requests: mpsc::UnboundedReceiver<Request>,
+        responses: mpsc::UnboundedSender<Response>,
+    ) {
+        while let Some(request) = requests.recv().await {
+            let response = match request {
+                Request::Refresh => Response::Snapshot(self.read_snapshot().await),
+                Request::Execute(action) => {
+                    let result = self.execute(&action).await;
+                    Response::Executed(action, result)
+                }
+            };
+            if responses.send(response).is_err() {
+                return;
+            }
+        }
+    }
+
+    async fn read_snapshot(&self) -> Result<Snapshot> {
+        let client = &self.client;
+        let pool_key = &self.pool;
+        let pool = client.config().pool_ref(pool_key)?;
+        let pool_config = client.config().get_pool(pool_key)?;
+
+        let book = client
+            .get_level2_ticks_from_mid(pool_key, self.ticks)
+            .await?;
+        let order_ids = client.account_open_orders(pool_key, &self.manager).await?;
+        let mut orders: Vec<NormalizedOrder> = if order_ids.is_empty() {
+            Vec::new()
+        } else {
+            client
+                .get_orders(pool_key, order_ids)
+                .await?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|order| NormalizedOrder::from_raw(order, &pool))
+                .collect()
+        };
+        orders.sort_by_key(|order| order.order_id);
+
+        let mut coins = vec![
+            pool_config.base_coin.clone(),
+            pool_config.quote_coin.clone(),
+        ];
+        if !coins.iter().any(|coin| coin == "DEEP") {
+            coins.push("DEEP".to_string());
+        }
+        let mut balances = Vec::with_capacity(coins.len());
+        for coin in coins {
+            let (_, balance) = client.check_manager_balance(&self.manager, &coin).await?;
+            balances.push((coin, balance));
+        }
+
+        let (trades, trades_error) = match TradeHistory::new(client)
+            .recent_trades(pool_key, TRADES_SHOWN, TRADE_EVENTS_SCANNED)
+            .await
+        {
+            Ok(trades) => (trades, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+
+        Ok(Snapshot {
+            ladder: build_ladder(&book, &orders, self.tick_size),
+            book,
+            mid_price: client.mid_price(pool_key).await.ok(),
+            orders,
+            trades,
+            trades_error,
+            balances,
+        })
+    }
+
+    /// Sign and submit a confirmed action
+    async fn execute(&self, action: &Action) -> Result<String> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        match action {
+            Action::Place {
+                is_bid,
+                price,
+                quantity,
+            } => {
+                let params = PlaceLimitOrderParams {
+                    pool_key: self.pool.clone(),
+                    balance_manager_key: self.manager.clone(),
+                    client_order_id: self.ids.next_id()?,
+                    price: *price,
+                    quantity: *quantity,
+                    is_bid: *is_bid,
+                    expiration: None,
+                    order_type: None,
+                    self_matching_option: None,
+                    pay_with_deep: None,
+                };
+                self.client
+                    .deep_book
+                    .place_limit_order(&mut ptb, params)
+                    .await?;
+            }
+            Action::Cancel(order) => {
+                self.client
+                    .deep_book
+                    .cancel_order(&mut ptb, &self.pool, &self.manager, order.order_id)
+                    .await?;
+            }
+        }
+
+        let response = self.config.executor()?.execute(ptb.finish()).await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Failure { error }) => Err(anyhow::anyhow!("{}", error)),
+            _ => Ok(format!("{}: {}", action.describe(), response.digest)),
+        }
+    }
+}
+
+struct App {
+    args: Args,
+    book_params: PoolBookParams,
+    requests: mpsc::UnboundedSender<Request>,
+    snapshot: Option<Snapshot>,
+    ladder_state: TableState,
+    selected_order: usize,
+    size: f64,
+    pending: Option<Action>,
+    status: String,
+    refresh_now: bool,
+    refreshing: bool,
+    quit: bool,
+}
+
+impl App {
+    fn send(&self, request: Request) -> Result<()> {
+        self.requests
+            .send(request)
+            .map_err(|_| anyhow::anyhow!("Background task stopped"))
+    }
+
+    fn on_response(&mut self, response: Response) {
+        match response {
+            Response::Snapshot(Ok(snapshot)) => {
+                self.refreshing = false;
+                let rows = snapshot.ladder.len();
+                if self
+                    .ladder_state
+                    .selected()
+                    .is_none_or(|cursor| cursor >= rows)
+                {
+                    // Start at the row closest to the mid price
+                    let cursor = snapshot
+                        .ladder
+                        .iter()
+                        .position(|row| row.bid_quantity > 0.0)
+                        .unwrap_or(rows / 2);
+                    self.ladder_state.select((rows > 0).then_some(cursor));
+                }
+                self.selected_order = self
+                    .selected_order
+                    .min(snapshot.orders.len().saturating_sub(1));
+                if let Some(e) = &snapshot.trades_error {
+                    self.status = format!("Trades unavailable: {}", e);
+                }
+                self.snapshot = Some(snapshot);
+            }
+            Response::Snapshot(Err(e)) => {
+                self.refreshing = false;
+                self.status = format!("Refresh failed: {}", e);
+            }
+            Response::Executed(action, result) => {
+                self.status = match result {
+                    Ok(status) => status,
+                    Err(e) => format!("{} failed: {}", action.describe(), e),
+                };
+                self.refresh_now = true;
+            }
+        }
+    }
+
+    fn on_key(&mut self, code: KeyCode) -> Result<()> {
+        if self.pending.is_some() {
+            match code {
+                KeyCode::Char('y') => {
+                    let action = self.pending.take().unwrap();
+                    self.status = format!("Submitting: {}", action.describe());
+                    self.send(Request::Execute(action))?;
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.pending = None;
+                    self.status = "Canceled".to_string();
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        let rows = self.snapshot.as_ref().map_or(0, |s| s.ladder.len());
+        let orders = self.snapshot.as_ref().map_or(0, |s| s.orders.len());
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => {
+                let cursor = self.ladder_state.selected().unwrap_or(0);
+                self.ladder_state.select(Some(cursor.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+                let cursor = self.ladder_state.selected().unwrap_or(0);
+                self.ladder_state.select(Some((cursor + 1).min(rows - 1)));
+            }
+            KeyCode::Tab if orders > 0 => {
+                self.selected_order = (self.selected_order + 1) % orders;
+            }
+            KeyCode::BackTab if orders > 0 => {
+                self.selected_order = (self.selected_order + orders - 1) % orders;
+            }
+            KeyCode::Char('+') => self.size += self.book_params.lot_size,
+            KeyCode::Char('-') => {
+                self.size = (self.size - self.book_params.lot_size).max(self.book_params.min_size)
+            }
+            KeyCode::Char('b') | KeyCode::Char('s') => {
+                if let Some(price) = self.cursor_price() {
+                    self.pending = Some(Action::Place {
+                        is_bid: code == KeyCode::Char('b'),
+                        price,
+                        quantity: (self.size * 1e9).round() / 1e9,
+                    });
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(order) = self
+                    .snapshot
+                    .as_ref()
+                    .and_then(|s| s.orders.get(self.selected_order))
+                {
+                    self.pending = Some(Action::Cancel(order.clone()));
+                }
+            }
+            KeyCode::Char('r') => self.refresh_now = true,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn cursor_price(&self) -> Option<f64> {
+        let snapshot = self.snapshot.as_ref()?;
+        Some(snapshot.ladder.get(self.ladder_state.selected()?)?.price)
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [ladder_area, side] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+    let [orders_area, trades_area, balances_area] = Layout::vertical([
+        Constraint::Percentage(40),
+        Constraint::Percentage(40),
+        Constraint::Percentage(20),
+    ])
+    .areas(side);
+
+    let summary = match &app.snapshot {
+        Some(snapshot) => {
+            let mid = snapshot
+                .mid_price
+                .map_or("-".to_string(), |mid| format!("{:.9}", mid));
+            let spread = spread(&snapshot.book).map_or("-".to_string(), |(bid, ask, spread)| {
+                format!("{} / {} ({})", bid, ask, spread)
+            });
+            format!(
+                "{}  mid {}  bid/ask {}  size {}",
+                app.args.pool, mid, spread, app.size
+            )
+        }
+        None => format!("{}  loading...", app.args.pool),
+    };
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(summary), Line::from(app.status.as_str())])
+            .block(Block::bordered().title(format!(" {} ", app.args.manager))),
+        header,
+    );
+
+    let Some(snapshot) = &app.snapshot else {
+        return;
+    };
+
+    let selected_price = snapshot
+        .orders
+        .get(app.selected_order)
+        .map(|order| order.normalized_price);
+    let ladder_rows = snapshot.ladder.iter().map(|row| {
+        let mark = if selected_price
+            .is_some_and(|price| (price - row.price).abs() < app.book_params.tick_size / 2.0)
+        {
+            ">"
+        } else {
+            " "
+        };
+        let own = row.own_bid_quantity + row.own_ask_quantity;
+        let style = if row.has_own_order() {
+            Style::new().yellow().bold()
+        } else {
+            Style::new()
+        };
+        Row::new(vec![
+            Cell::from(mark),
+            Cell::from(quantity(row.bid_quantity)).green(),
+            Cell::from(format!("{:.9}", row.price)),
+            Cell::from(quantity(row.ask_quantity)).red(),
+            Cell::from(quantity(own)),
+        ])
+        .style(style)
+    });
+    let ladder = Table::new(
+        ladder_rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(vec!["", "bid", "price", "ask", "mine"]).bold())
+    .row_highlight_style(Style::new().reversed())
+    .block(Block::bordered().title(" Depth "));
+    frame.render_stateful_widget(ladder, ladder_area, &mut app.ladder_state);
+
+    let order_rows = snapshot.orders.iter().enumerate().map(|(index, order)| {
+        let row = Row::new(vec![
+            Cell::from(if order.is_bid { "bid" } else { "ask" }),
+            Cell::from(format!("{:.9}", order.normalized_price)),
+            Cell::from(format!("{}/{}", order.remaining_quantity, order.quantity)),
+            Cell::from(format!("{:?}", order.status)),
+        ]);
+        if index == app.selected_order {
+            row.reversed()
+        } else {
+            row
+        }
+    });
+    frame.render_widget(
+        Table::new(
+            order_rows,
+            [
+                Constraint::Length(4),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(vec!["side", "price", "remaining", "status"]).bold())
+        .block(Block::bordered().title(" Open orders ")),
+        orders_area,
+    );
+
+    let trade_rows = snapshot.trades.iter().map(|trade| {
+        let time = chrono::DateTime::from_timestamp_millis(trade.timestamp_ms as i64)
+            .map_or("-".to_string(), |time| time.format("%H:%M:%S").to_string());
+        let row = Row::new(vec![
+            time,
+            format!("{:.9}", trade.price),
+            quantity(trade.base_quantity),
+        ]);
+        if trade.taker_is_bid {
+            row.green()
+        } else {
+            row.red()
+        }
+    });
+    frame.render_widget(
+        Table::new(
+            trade_rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(vec!["time", "price", "quantity"]).bold())
+        .block(Block::bordered().title(" Trades ")),
+        trades_area,
+    );
+
+    let balances = snapshot
+        .balances
+        .iter()
+        .map(|(coin, balance)| Line::from(format!("{:<8} {}", coin, balance)))
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Paragraph::new(balances).block(Block::bordered().title(" Balances ")),
+        balances_area,
+    );
+
+    frame.render_widget(
+        Line::from(
+            "↑/↓ move  b bid  s ask  +/- size  tab select order  c cancel  r refresh  q quit",
+        )
+        .dim(),
+        footer,
+    );
+
+    if let Some(action) = &app.pending {
+        let area = popup(frame.area(), 60, 5);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(action.describe()),
+                Line::from(""),
+                Line::from("Sign and submit? [y/n]").bold(),
+            ])
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(" Confirm ")),
+            area,
+        );
+    }
+}
+
+fn quantity(value: f64) -> String {
+    if value > 0.0 {
+        value.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn popup(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    mut responses: mpsc::UnboundedReceiver<Response>,
+) -> Result<()> {
+    let refresh = Duration::from_secs(app.args.refresh_secs.max(1));
+    let mut last_refresh: Option<Instant> = None;
+    loop {
+        while let Ok(response) = responses.try_recv() {
+            app.on_response(response);
+        }
+
+        // At most one refresh is queued at a time, so a slow node cannot build a backlog
+        if !app.refreshing
+            && (app.refresh_now || last_refresh.is_none_or(|at| at.elapsed() >= refresh))
+        {
+            app.send(Request::Refresh)?;
+            app.refreshing = true;
+            app.refresh_now = false;
+            last_refresh = Some(Instant::now());
+        }
+
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if event::poll(Duration::from_millis(200))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.on_key(key.code)?;
+                }
+            }
+        }
+        if app.quit {
+            return Ok(());
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = FileConfig::load(&args.config)?;
+    let client = config.client(None).await?;
+    let book_params = client.pool_book_params(&args.pool).await?;
+    client.config().balance_manager_ref(&args.manager)?;
+
+    let (requests, worker_requests) = mpsc::unbounded_channel();
+    let (worker_responses, responses) = mpsc::unbounded_channel();
+    let worker = Worker {
+        pool: args.pool.clone(),
+        manager: args.manager.clone(),
+        ticks: args.ticks,
+        tick_size: book_params.tick_size,
+        config,
+        client,
+        ids: TimePrefixedIdGenerator::new(),
+    };
+    tokio::spawn(worker.run(worker_requests, worker_responses));
+
+    let mut app = App {
+        size: args.size.unwrap_or(book_params.min_size),
+        args,
+        book_params,
+        requests,
+        snapshot: None,
+        ladder_state: TableState::default(),
+        selected_order: 0,
+        pending: None,
+        status: String::new(),
+        refresh_now: false,
+        refreshing: false,
+        quit: false,
+    };
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, responses).await;
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,538 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+use sui_sdk::rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::TransactionKind;
+
+use sui_deepbookv3::client::{DeepBookClient, NormalizedOrder};
+use sui_deepbookv3::executor::TransactionExecutor;
+use sui_deepbookv3::fees::FeePlanner;
+use sui_deepbookv3::utils::client_order_id::{ClientOrderIdGenerator, TimePrefixedIdGenerator};
+use sui_deepbookv3::utils::file_config::FileConfig;
+use sui_deepbookv3::utils::types::{
+    ClientOrderId, OrderId, OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams,
+    SelfMatchingOptions, SwapParams,
+};
+
+/// Query and trade on DeepBook
+#[derive(Parser)]
+#[command(name = "deepbook", version)]
+struct Cli {
+    /// Path of the JSON config file
+    #[arg(long, global = true, default_value = "deepbook.json")]
+    config: PathBuf,
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+    /// Simulate write commands instead of submitting them
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the configured pools
+    Pools,
+    /// Show the order book around the mid price
+    Book {
+        pool: String,
+        /// Number of ticks on each side
+        #[arg(long, default_value_t = 10)]
+        ticks: u64,
+    },
+    /// Show the mid price
+    Mid { pool: String },
+    /// Show a balance manager's balance of a coin
+    Balance { manager: String, coin: String },
+    /// List a balance manager's open orders in a pool
+    Orders { pool: String, manager: String },
+    /// Show a balance manager's account in a pool
+    Account { pool: String, manager: String },
+    /// Place a limit order, or a market order without --price
+    Place {
+        pool: String,
+        manager: String,
+        #[arg(long, value_enum)]
+        side: Side,
+        #[arg(long)]
+        quantity: f64,
+        #[arg(long)]
+        price: Option<f64>,
+        #[arg(long, value_enum, default_value_t = CliOrderType::NoRestriction)]
+        order_type: CliOrderType,
+        #[arg(long, value_enum, default_value_t = CliSelfMatching::Allowed)]
+        self_matching: CliSelfMatching,
+        /// Expire the order after this many seconds of on-chain time
+        #[arg(long)]
+        good_til_secs: Option<u64>,
+        /// Pay fees in the input asset instead of DEEP
+        #[arg(long)]
+        pay_with_input: bool,
+        #[arg(long)]
+        client_order_id: Option<u64>,
+    },
+    /// Cancel an order
+    Cancel {
+        pool: String,
+        manager: String,
+        order_id: OrderId,
+    },
+    /// Cancel every open order of a balance manager in a pool
+    CancelAll { pool: String, manager: String },
+    /// Deposit into a balance manager
+    Deposit {
+        manager: String,
+        coin: String,
+        amount: f64,
+    },
+    /// Withdraw from a balance manager to the wallet
+    Withdraw {
+        manager: String,
+        coin: String,
+        amount: f64,
+    },
+    /// Swap from the wallet without a balance manager
+    Swap {
+        pool: String,
+        /// Amount of the asset sold
+        amount: f64,
+        /// The asset sold
+        #[arg(long, value_enum)]
+        sell: Asset,
+        /// Minimum amount received
+        #[arg(long, default_value_t = 0.0)]
+        min_out: f64,
+        /// DEEP paid for fees; planned from the pool's fees when missing
+        #[arg(long)]
+        deep_amount: Option<f64>,
+    },
+    /// Stake DEEP in a pool
+    Stake {
+        pool: String,
+        manager: String,
+        amount: f64,
+    },
+    /// Vote for a governance proposal
+    Vote {
+        pool: String,
+        manager: String,
+        proposal_id: String,
+    },
+    /// Create and share a new balance manager
+    CreateManager,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Asset {
+    Base,
+    Quote,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliOrderType {
+    NoRestriction,
+    ImmediateOrCancel,
+    FillOrKill,
+    PostOnly,
+}
+
+impl From<CliOrderType> for OrderType {
+    fn from(value: CliOrderType) -> Self {
+        match value {
+            CliOrderType::NoRestriction => OrderType::NoRestriction,
+            CliOrderType::ImmediateOrCancel => OrderType::ImmediateOrCancel,
+            CliOrderType::FillOrKill => OrderType::FillOrKill,
+            CliOrderType::PostOnly => OrderType::PostOnly,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliSelfMatching {
+    Allowed,
+    CancelTaker,
+    CancelMaker,
+}
+
+impl From<CliSelfMatching> for SelfMatchingOptions {
+    fn from(value: CliSelfMatching) -> Self {
+        match value {
+            CliSelfMatching::Allowed => SelfMatchingOptions::SelfMatchingAllowed,
+            CliSelfMatching::CancelTaker => SelfMatchingOptions::CancelTaker,
+            CliSelfMatching::CancelMaker => SelfMatchingOptions::CancelMaker,
+        }
+    }
+}
+
+impl Command {
+    fn is_write(&self) -> bool {
+        !matches!(
+            self,
+            Command::Pools
+                | Command::Book { .. }
+                | Command::Mid { .. }
+                | Command::Balance { .. }
+                | Command::Orders { .. }
+                | Command::Account { .. }
+        )
+    }
+}
+
+struct App {
+    config: FileConfig,
+    client: DeepBookClient,
+    json: bool,
+    dry_run: bool,
+}
+
+impl App {
+    fn print<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) -> Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        } else {
+            println!("{}", text());
+        }
+        Ok(())
+    }
+
+    /// Submit a transaction, or simulate it with --dry-run
+    async fn submit(&self, ptb: ProgrammableTransactionBuilder) -> Result<()> {
+        let transaction = ptb.finish();
+        if self.dry_run {
+            let results = self
+                .client
+                .sui_client()
+                .read_api()
+                .dev_inspect_transaction_block(
+                    self.client.address(),
+                    TransactionKind::ProgrammableTransaction(transaction),
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            return self.print(&results, || match &results.error {
+                Some(error) => format!("Dry run failed: {}", error),
+                None => format!("Dry run: {:?}", results.effects.status()),
+            });
+        }
+
+        let response = self.config.executor()?.execute(transaction).await?;
+        self.print(&response, || {
+            let status = response
+                .effects
+                .as_ref()
+                .map(|effects| format!("{:?}", effects.status()))
+                .unwrap_or_else(|| "unknown".to_string());
+            format!("{} {}", response.digest, status)
+        })
+    }
+
+    async fn run(&self, command: Command) -> Result<()> {
+        let client = &self.client;
+        match command {
+            Command::Pools => {
+                let mut pools: Vec<_> = client.config().pools().iter().collect();
+                pools.sort_unstable_by_key(|(key, _)| **key);
+                let value: Vec<_> = pools
+                    .iter()
+                    .map(|(key, pool)| {
+                        json!({
+                            "key": key,
+                            "address": pool.address,
+                            "base_coin": pool.base_coin,
+                            "quote_coin": pool.quote_coin,
+                        })
+                    })
+                    .collect();
+                self.print(&value, || {
+                    pools
+                        .iter()
+                        .map(|(key, pool)| {
+                            format!(
+                                "{:<16} {}/{} {}",
+                                key, pool.base_coin, pool.quote_coin, pool.address
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+            }
+            Command::Book { pool, ticks } => {
+                let book = client.get_level2_ticks_from_mid(&pool, ticks).await?;
+                self.print(&book, || {
+                    let mut lines = Vec::new();
+                    for (price, quantity) in book.ask_prices.iter().zip(&book.ask_quantities).rev()
+                    {
+                        lines.push(format!("ask {:>16.9} {:>16.9}", price, quantity));
+                    }
+                    for (price, quantity) in book.bid_prices.iter().zip(&book.bid_quantities) {
+                        lines.push(format!("bid {:>16.9} {:>16.9}", price, quantity));
+                    }
+                    lines.join("\n")
+                })
+            }
+            Command::Mid { pool } => {
+                let mid = client.mid_price(&pool).await?;
+                self.print(&json!({ "pool": pool, "mid_price": mid }), || {
+                    mid.to_string()
+                })
+            }
+            Command::Balance { manager, coin } => {
+                let (coin_type, balance) = client.check_manager_balance(&manager, &coin).await?;
+                self.print(
+                    &json!({ "coin_type": coin_type, "balance": balance }),
+                    || format!("{} {}", balance, coin),
+                )
+            }
+            Command::Orders { pool, manager } => {
+                let pool_ref = client.config().pool_ref(&pool)?;
+                let order_ids = client.account_open_orders(&pool, &manager).await?;
+                let mut orders: Vec<NormalizedOrder> = if order_ids.is_empty() {
+                    Vec::new()
+                } else {
+                    client
+                        .get_orders(&pool, order_ids)
+                        .await?
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|order| NormalizedOrder::from_raw(order, &pool_ref))
+                        .collect()
+                };
+                orders.sort_by_key(|order| order.order_id);
+                self.print(&orders, || {
+                    orders
+                        .iter()
+                        .map(|order| {
+                            format!(
+                                "{} {:>16.9} {:>16.9}/{:<16.9} {:?} client={} id={}",
+                                if order.is_bid { "bid" } else { "ask" },
+                                order.normalized_price,
+                                order.remaining_quantity,
+                                order.quantity,
+                                order.status,
+                                order.client_order_id.value(),
+                                order.order_id,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+            }
+            Command::Account { pool, manager } => {
+                let account = client.account(&pool, &manager).await?;
+                self.print(&account, || format!("{:#?}", account))
+            }
+            Command::Place {
+                pool,
+                manager,
+                side,
+                quantity,
+                price,
+                order_type,
+                self_matching,
+                good_til_secs,
+                pay_with_input,
+                client_order_id,
+            } => {
+                let client_order_id = match client_order_id {
+                    Some(id) => ClientOrderId(id),
+                    None => TimePrefixedIdGenerator::new().next_id()?,
+                };
+                let is_bid = matches!(side, Side::Bid);
+                let pay_with_deep = Some(!pay_with_input);
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                match price {
+                    Some(price) => {
+                        let mut params = PlaceLimitOrderParams {
+                            pool_key: pool,
+                            balance_manager_key: manager,
+                            client_order_id,
+                            price,
+                            quantity,
+                            is_bid,
+                            expiration: None,
+                            order_type: Some(order_type.into()),
+                            self_matching_option: Some(self_matching.into()),
+                            pay_with_deep,
+                        };
+                        if let Some(secs) = good_til_secs {
+                            let time_in_force = client.good_til(Duration::from_secs(secs)).await?;
+                            params = params.with_time_in_force(time_in_force);
+                        }
+                        client.deep_book.place_limit_order(&mut ptb, params).await?;
+                    }
+                    None => {
+                        let params = PlaceMarketOrderParams {
+                            pool_key: pool,
+                            balance_manager_key: manager,
+                            client_order_id,
+                            quantity,
+                            is_bid,
+                            self_matching_option: Some(self_matching.into()),
+                            pay_with_deep,
+                        };
+                        client
+                            .deep_book
+                            .place_market_order(&mut ptb, params)
+                            .await?;
+                    }
+                }
+                self.submit(ptb).await
+            }
+            Command::Cancel {
+                pool,
+                manager,
+                order_id,
+            } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .deep_book
+                    .cancel_order(&mut ptb, &pool, &manager, order_id)
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::CancelAll { pool, manager } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .deep_book
+                    .cancel_all_orders(&mut ptb, &pool, &manager)
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::Deposit {
+                manager,
+                coin,
+                amount,
+            } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .balance_manager
+                    .deposit_into_manager(&mut ptb, client.address(), &manager, &coin, amount)
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::Withdraw {
+                manager,
+                coin,
+                amount,
+            } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .balance_manager
+                    .withdraw_from_manager(&mut ptb, &manager, &coin, amount, client.address())
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::Swap {
+                pool,
+                amount,
+                sell,
+                min_out,
+                deep_amount,
+            } => {
+                let sell_base = matches!(sell, Asset::Base);
+                let mut params = SwapParams {
+                    sender: client.address(),
+                    pool_key: pool.clone(),
+                    amount,
+                    deep_amount: deep_amount.unwrap_or(0.0),
+                    min_out,
+                    deep_coin: None,
+                    base_coin: None,
+                    quote_coin: None,
+                };
+                if deep_amount.is_none() {
+                    let plan = FeePlanner::new(client)
+                        .plan_swap(&pool, amount, sell_base)
+                        .await?;
+                    params = plan.apply_to_swap(params);
+                }
+
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                if sell_base {
+                    client
+                        .deep_book
+                        .swap_exact_base_for_quote(&mut ptb, params)
+                        .await?;
+                } else {
+                    client
+                        .deep_book
+                        .swap_exact_quote_for_base(&mut ptb, params)
+                        .await?;
+                }
+                self.submit(ptb).await
+            }
+            Command::Stake {
+                pool,
+                manager,
+                amount,
+            } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .governance
+                    .stake(&mut ptb, &pool, &manager, amount)
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::Vote {
+                pool,
+                manager,
+                proposal_id,
+            } => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .governance
+                    .vote(&mut ptb, &pool, &manager, &proposal_id)
+                    .await?;
+                self.submit(ptb).await
+            }
+            Command::CreateManager => {
+                let mut ptb = ProgrammableTransactionBuilder::new();
+                client
+                    .balance_manager
+                    .create_and_share_balance_manager(&mut ptb)?;
+                self.submit(ptb).await
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = FileConfig::load(&cli.config)?;
+
+    // Writes are sent from the wallet's active address; a dry run may use the configured one
+    let address = if cli.command.is_write() && !(cli.dry_run && config.address.is_some()) {
+        let mut wallet = config.wallet()?;
+        Some(wallet.active_address()?)
+    } else {
+        None
+    };
+    let client = config.client(address).await?;
+
+    let app = App {
+        config,
+        client,
+        json: cli.json,
+        dry_run: cli.dry_run,
+    };
+    app.run(cli.command).await
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A map whose entries expire a fixed time after they are inserted
+///
+/// Shared read results are cached here so repeated requests within `ttl` reuse one chain
+/// read. The lock is never held across a read, so concurrent misses may read twice.
+pub struct TtlCache<V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    /// Creates a new TtlCache
+    ///
+    /// @param ttl - How long an entry stays fresh
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The fresh value for a key, if any
+    ///
+    /// @param key - The cache key
+    pub fn get(&self, key: &str) -> Option<V> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)
+            .filter(|(inserted, _)| inserted.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Store a value, dropping expired entries
+    ///
+    /// @param key - The cache key
+    /// @param value - The value to store
+    pub fn insert(&self, key: impl Into<String>, value: V) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (inserted, _)| inserted.elapsed() < self.ttl);
+        entries.insert(key.into(), (Instant::now(), value));
+    }
+
+    /// The fresh value for a key, reading and storing it on a miss
+    ///
+    /// Failed reads are not cached.
+    ///
+    /// @param key - The cache key
+    /// @param read - Reads the value on a miss
+    pub async fn get_or_try_insert_with<F, Fut>(&self, key: &str, read: F) -> anyhow::Result<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<V>>,
+    {
+        if let Some(value) = self.get(key) {
+            return Ok(value);
+        }
+        let value = read().await?;
+        self.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Number of stored entries, fresh or not
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
-use sui_sdk::types::base_types::SuiAddress;
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::dynamic_field::DynamicFieldName;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::{TypeTag, SUI_CLOCK_OBJECT_ID};
 use sui_sdk::SuiClient;
 
 use crate::transactions::balance_manager::BalanceManagerContract;
+use crate::transactions::bulk::BulkOrderContract;
 use crate::transactions::deepbook::DeepBookContract;
 use crate::transactions::deepbook_admin::DeepBookAdminContract;
 use crate::transactions::flashloan::FlashLoanContract;
@@ -14,6 +19,12 @@ use crate::transactions::governance::GovernanceContract;
 use crate::utils::config::{
     BalanceManagerMap, CoinMap, DeepBookConfig, Environment, PoolMap, DEEP_SCALAR, FLOAT_SCALAR,
 };
+use crate::utils::json::{
+    json_bool, json_field, json_object_id, json_option, json_u64, object_json,
+};
+use crate::utils::types::{
+    BalanceManagerRef, ClientOrderId, OrderId, OrderStatus, PoolRef, TimeInForce,
+};
 use crate::DataReader;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,51 +44,94 @@ pub struct QuantityOut {
     pub deep_required: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ID {
     pub bytes: SuiAddress,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderDeepPrice {
     pub asset_is_base: bool,
     pub deep_per_asset: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     pub balance_manager_id: ID,
-    pub order_id: u128,
-    pub client_order_id: u64,
+    pub order_id: OrderId,
+    pub client_order_id: ClientOrderId,
     pub quantity: u64,
     pub filled_quantity: u64,
     pub fee_is_deep: bool,
     pub order_deep_price: OrderDeepPrice,
     pub epoch: u64,
-    pub status: u8,
+    pub status: OrderStatus,
     pub expire_timestamp: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NormalizedOrder {
     pub balance_manager_id: ID,
-    pub order_id: u128,
-    pub client_order_id: u64,
-    pub quantity: String,
-    pub filled_quantity: String,
+    pub order_id: OrderId,
+    pub client_order_id: ClientOrderId,
+    pub quantity: f64,
+    pub filled_quantity: f64,
+    /// Quantity still resting in the book
+    pub remaining_quantity: f64,
     pub fee_is_deep: bool,
     pub order_deep_price: NormalizedOrderDeepPrice,
     pub epoch: u64,
-    pub status: u8,
+    pub status: OrderStatus,
     pub expire_timestamp: u64,
     pub is_bid: bool,
-    pub normalized_price: String,
+    pub normalized_price: f64,
+    /// Quote value of the remaining quantity at the order's price
+    pub notional: f64,
+    /// The order as read from the pool
+    pub raw: Order,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl NormalizedOrder {
+    /// Scale an order read from a pool
+    ///
+    /// @param order - The order as read from the pool
+    /// @param pool - The PoolRef holding the order
+    pub fn from_raw(order: Order, pool: &PoolRef) -> Self {
+        let quantity = round(pool.base_from_raw(order.quantity));
+        let filled_quantity = round(pool.base_from_raw(order.filled_quantity));
+        let remaining_quantity =
+            round(pool.base_from_raw(order.quantity.saturating_sub(order.filled_quantity)));
+        let normalized_price = order.order_id.normalized_price(pool);
+
+        Self {
+            balance_manager_id: order.balance_manager_id,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            quantity,
+            filled_quantity,
+            remaining_quantity,
+            fee_is_deep: order.fee_is_deep,
+            order_deep_price: NormalizedOrderDeepPrice {
+                asset_is_base: order.order_deep_price.asset_is_base,
+                deep_per_asset: round(
+                    order.order_deep_price.deep_per_asset as f64 / DEEP_SCALAR as f64,
+                ),
+            },
+            epoch: order.epoch,
+            status: order.status,
+            expire_timestamp: order.expire_timestamp,
+            is_bid: order.order_id.is_bid(),
+            normalized_price,
+            notional: round(remaining_quantity * normalized_price),
+            raw: order,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NormalizedOrderDeepPrice {
     pub asset_is_base: bool,
-    pub deep_per_asset: String,
+    pub deep_per_asset: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -86,7 +140,7 @@ pub struct Level2Range {
     pub quantities: Vec<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Level2TicksFromMid {
     pub bid_prices: Vec<f64>,
     pub bid_quantities: Vec<f64>,
@@ -101,37 +155,76 @@ pub struct VaultBalances {
     pub deep: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolTradeParams {
     pub taker_fee: f64,
     pub maker_fee: f64,
     pub stake_required: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolBookParams {
     pub tick_size: f64,
     pub lot_size: f64,
     pub min_size: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Balances {
     pub base: f64,
     pub quote: f64,
     pub deep: f64,
 }
 
+impl Balances {
+    /// Scale on-chain balances of a pool
+    ///
+    /// @param raw - The balances in their on-chain representation
+    /// @param pool - The PoolRef the balances belong to
+    pub fn from_raw(raw: &RawBalances, pool: &PoolRef) -> Self {
+        Self {
+            base: round(pool.base_from_raw(raw.base)),
+            quote: round(pool.quote_from_raw(raw.quote)),
+            deep: round(raw.deep as f64 / DEEP_SCALAR as f64),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct RawOrderDeepPrice {
     asset_is_base: bool,
     deep_per_asset: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Balances in their on-chain representation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawBalances {
+    pub base: u64,
+    pub quote: u64,
+    pub deep: u64,
+}
+
+/// An account as returned by `pool::account`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawAccount {
+    pub epoch: u64,
+    pub open_orders: Vec<OrderId>,
+    pub taker_volume: u128,
+    pub maker_volume: u128,
+    pub active_stake: u64,
+    pub inactive_stake: u64,
+    pub created_proposal: bool,
+    pub voted_proposal: Option<ID>,
+    pub unclaimed_rebates: RawBalances,
+    pub settled_balances: RawBalances,
+    pub owed_balances: RawBalances,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub epoch: u64,
-    pub open_orders: Vec<u128>,
+    pub open_orders: Vec<OrderId>,
+    /// Volumes are in the base asset
     pub taker_volume: f64,
     pub maker_volume: f64,
     pub active_stake: f64,
@@ -141,21 +234,144 @@ pub struct Account {
     pub unclaimed_rebates: Balances,
     pub settled_balances: Balances,
     pub owed_balances: Balances,
+    /// The account as returned by the pool
+    pub raw: RawAccount,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Account {
+    /// Scale an account returned by a pool
+    ///
+    /// @param raw - The account as returned by the pool
+    /// @param pool - The PoolRef the account belongs to
+    pub fn from_raw(raw: RawAccount, pool: &PoolRef) -> Self {
+        Self {
+            epoch: raw.epoch,
+            open_orders: raw.open_orders.clone(),
+            taker_volume: round(raw.taker_volume as f64 / pool.base_scalar as f64),
+            maker_volume: round(raw.maker_volume as f64 / pool.base_scalar as f64),
+            active_stake: round(raw.active_stake as f64 / DEEP_SCALAR as f64),
+            inactive_stake: round(raw.inactive_stake as f64 / DEEP_SCALAR as f64),
+            created_proposal: raw.created_proposal,
+            voted_proposal: raw.voted_proposal,
+            unclaimed_rebates: Balances::from_raw(&raw.unclaimed_rebates, pool),
+            settled_balances: Balances::from_raw(&raw.settled_balances, pool),
+            owed_balances: Balances::from_raw(&raw.owed_balances, pool),
+            raw,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolDeepPrice {
     pub asset_is_base: bool,
     pub deep_per_base: Option<f64>,
     pub deep_per_quote: Option<f64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceProposal {
+    pub proposal_id: ObjectID,
+    pub taker_fee: f64,
+    pub maker_fee: f64,
+    pub stake_required: f64,
+    pub votes: f64,
+    pub quorum: f64,
+    pub quorum_reached: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceState {
+    pub epoch: u64,
+    pub whitelisted: bool,
+    pub stable: bool,
+    pub voting_power: f64,
+    pub quorum: f64,
+    pub trade_params: PoolTradeParams,
+    pub next_trade_params: PoolTradeParams,
+    pub proposals: Vec<GovernanceProposal>,
+}
+
+impl GovernanceState {
+    /// Decode the governance state from the JSON of a pool's inner state
+    ///
+    /// The state is returned as stored, see `roll_forward` to bring it to the chain epoch.
+    ///
+    /// @param inner - The `PoolInner` object as rendered by the JSON-RPC API
+    pub fn from_pool_inner(inner: &serde_json::Value) -> anyhow::Result<Self> {
+        let governance = json_field(inner, &["state", "governance"])?;
+        let quorum = json_u64(json_field(governance, &["quorum"])?)?;
+
+        let proposals = json_field(governance, &["proposals", "contents"])?
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse proposals"))?
+            .iter()
+            .map(|entry| {
+                let proposal = json_field(entry, &["value"])?;
+                let votes = json_u64(json_field(proposal, &["votes"])?)?;
+                Ok(GovernanceProposal {
+                    proposal_id: json_object_id(json_field(entry, &["key"])?)?,
+                    taker_fee: fee_from_raw(json_u64(json_field(proposal, &["taker_fee"])?)?),
+                    maker_fee: fee_from_raw(json_u64(json_field(proposal, &["maker_fee"])?)?),
+                    stake_required: deep_from_raw(json_u64(json_field(
+                        proposal,
+                        &["stake_required"],
+                    )?)?),
+                    votes: deep_from_raw(votes),
+                    quorum: deep_from_raw(quorum),
+                    quorum_reached: votes >= quorum,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            epoch: json_u64(json_field(governance, &["epoch"])?)?,
+            whitelisted: json_bool(json_field(governance, &["whitelisted"])?)?,
+            stable: json_bool(json_field(governance, &["stable"])?)?,
+            voting_power: deep_from_raw(json_u64(json_field(governance, &["voting_power"])?)?),
+            quorum: deep_from_raw(quorum),
+            trade_params: trade_params_from_json(json_field(governance, &["trade_params"])?)?,
+            next_trade_params: trade_params_from_json(json_field(
+                governance,
+                &["next_trade_params"],
+            )?)?,
+            proposals,
+        })
+    }
+
+    /// Roll the state forward to the chain epoch, as the pool does on its next interaction
+    ///
+    /// A new epoch clears the proposals, applies `next_trade_params` and sets the quorum
+    /// to half the voting power. Nothing changes if `epoch` is already current.
+    ///
+    /// @param current_epoch - The chain's current epoch
+    pub fn roll_forward(&mut self, current_epoch: u64) {
+        if self.epoch >= current_epoch {
+            return;
+        }
+        let voting_power = (self.voting_power * DEEP_SCALAR as f64).round() as u64;
+        self.epoch = current_epoch;
+        self.proposals.clear();
+        self.trade_params = self.next_trade_params.clone();
+        self.quorum = deep_from_raw(voting_power / 2);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountStake {
+    pub epoch: u64,
+    pub active_stake: f64,
+    pub inactive_stake: f64,
+    pub created_proposal: bool,
+    pub voted_proposal: Option<ObjectID>,
+}
+
 /// DeepBookClient struct for managing DeepBook operations.
 pub struct DeepBookClient {
     client: SuiClient,
     config: DeepBookConfig,
     address: SuiAddress,
     pub balance_manager: BalanceManagerContract,
+    pub bulk_orders: BulkOrderContract,
     pub deep_book: DeepBookContract,
     pub deep_book_admin: DeepBookAdminContract,
     pub flash_loans: FlashLoanContract,
@@ -188,6 +404,11 @@ impl DeepBookClient {
             address,
             config: config.clone(),
             balance_manager: balance_manager.clone(),
+            bulk_orders: BulkOrderContract::new(
+                client.clone(),
+                config.clone(),
+                balance_manager.clone(),
+            ),
             deep_book: DeepBookContract::new(
                 client.clone(),
                 config.clone(),
@@ -203,6 +424,21 @@ impl DeepBookClient {
         }
     }
 
+    /// The configuration used by this client
+    pub fn config(&self) -> &DeepBookConfig {
+        &self.config
+    }
+
+    /// The underlying SuiClient
+    pub fn sui_client(&self) -> &SuiClient {
+        &self.client
+    }
+
+    /// The address used as sender for read-only calls
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+
     /// Check the balance of a balance manager for a specific coin
     ///
     /// @param manager_key - The key of the balance manager
@@ -212,11 +448,32 @@ impl DeepBookClient {
         manager_key: &str,
         coin_key: &str,
     ) -> anyhow::Result<(String, f64)> {
-        let mut ptb = ProgrammableTransactionBuilder::new();
+        let manager = self.config.balance_manager_ref(manager_key)?;
         let coin = self.config.get_coin(coin_key)?;
+        let coin_type = TypeTag::from_str(&coin.type_name)?;
+
+        let (_, balance) = self
+            .check_manager_balance_by_ref(&manager, coin_type, coin.scalar)
+            .await?;
+        Ok((coin.type_name.clone(), balance))
+    }
+
+    /// Check the balance of a balance manager addressed by object ID for a specific coin type
+    ///
+    /// @param manager - The BalanceManagerRef to check
+    /// @param coin_type - The type tag of the coin
+    /// @param coin_scalar - The scalar of the coin
+    pub async fn check_manager_balance_by_ref(
+        &self,
+        manager: &BalanceManagerRef,
+        coin_type: TypeTag,
+        coin_scalar: u64,
+    ) -> anyhow::Result<(String, f64)> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let type_name = coin_type.to_canonical_string(true);
 
         self.balance_manager
-            .check_manager_balance(&mut ptb, manager_key, coin_key)
+            .check_manager_balance_by_ref(&mut ptb, manager, coin_type)
             .await?;
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(res) => {
@@ -224,12 +481,9 @@ impl DeepBookClient {
                     .first()
                     .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
                 let balance = bcs::from_bytes::<u64>(&res.0)?;
-                let adjusted_balance = balance as f64 / coin.scalar as f64;
+                let adjusted_balance = balance as f64 / coin_scalar as f64;
 
-                Ok((
-                    coin.type_name.clone(),
-                    (adjusted_balance * 1e9).round() / 1e9,
-                ))
+                Ok((type_name, (adjusted_balance * 1e9).round() / 1e9))
             }
             Err(e) => Err(e),
         }
@@ -239,8 +493,16 @@ impl DeepBookClient {
     ///
     /// @param pool_key - The key of the pool
     pub async fn whitelisted(&self, pool_key: &str) -> anyhow::Result<bool> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.whitelisted_by_ref(&pool).await
+    }
+
+    /// Check if a pool addressed by object ID is whitelisted
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn whitelisted_by_ref(&self, pool: &PoolRef) -> anyhow::Result<bool> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        self.deep_book.whitelisted(&mut ptb, pool_key).await?;
+        self.deep_book.whitelisted_by_ref(&mut ptb, pool).await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(res) => {
@@ -262,13 +524,27 @@ impl DeepBookClient {
         &self,
         pool_key: &str,
         base_quantity: f64,
+    ) -> anyhow::Result<QuoteQuantityOut> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_quote_quantity_out_by_ref(&pool, base_quantity)
+            .await
+    }
+
+    /// Get the quote quantity out for a given base quantity, on a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param base_quantity - The base quantity to convert
+    pub async fn get_quote_quantity_out_by_ref(
+        &self,
+        pool: &PoolRef,
+        base_quantity: f64,
     ) -> anyhow::Result<QuoteQuantityOut> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .get_quote_quantity_out(&mut ptb, pool_key, base_quantity)
+            .get_quote_quantity_out_by_ref(&mut ptb, pool, base_quantity)
             .await?;
 
-        self.get_quote_quantity_out_inner(ptb, pool_key, base_quantity)
+        self.get_quote_quantity_out_inner(ptb, pool, base_quantity)
             .await
     }
 
@@ -280,12 +556,26 @@ impl DeepBookClient {
         &self,
         pool_key: &str,
         quote_quantity: f64,
+    ) -> anyhow::Result<QuoteQuantityOut> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_base_quantity_out_by_ref(&pool, quote_quantity)
+            .await
+    }
+
+    /// Get the base quantity out for a given quote quantity, on a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param quote_quantity - The quote quantity to convert
+    pub async fn get_base_quantity_out_by_ref(
+        &self,
+        pool: &PoolRef,
+        quote_quantity: f64,
     ) -> anyhow::Result<QuoteQuantityOut> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .get_base_quantity_out(&mut ptb, pool_key, quote_quantity)
+            .get_base_quantity_out_by_ref(&mut ptb, pool, quote_quantity)
             .await?;
-        self.get_quote_quantity_out_inner(ptb, pool_key, quote_quantity)
+        self.get_quote_quantity_out_inner(ptb, pool, quote_quantity)
             .await
     }
 
@@ -299,13 +589,29 @@ impl DeepBookClient {
         pool_key: &str,
         base_quantity: f64,
         quote_quantity: f64,
+    ) -> anyhow::Result<QuantityOut> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_quantity_out_by_ref(&pool, base_quantity, quote_quantity)
+            .await
+    }
+
+    /// Get the output quantities for given base and quote quantities, on a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param base_quantity - Base quantity to convert
+    /// @param quote_quantity - Quote quantity to convert
+    pub async fn get_quantity_out_by_ref(
+        &self,
+        pool: &PoolRef,
+        base_quantity: f64,
+        quote_quantity: f64,
     ) -> anyhow::Result<QuantityOut> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .get_quantity_out(&mut ptb, pool_key, base_quantity, quote_quantity)
+            .get_quantity_out_by_ref(&mut ptb, pool, base_quantity, quote_quantity)
             .await?;
         let result = self
-            .get_quote_quantity_out_inner(ptb, pool_key, quote_quantity)
+            .get_quote_quantity_out_inner(ptb, pool, quote_quantity)
             .await?;
         Ok(QuantityOut {
             base_quantity,
@@ -324,10 +630,24 @@ impl DeepBookClient {
         &self,
         pool_key: &str,
         manager_key: &str,
-    ) -> anyhow::Result<Vec<u128>> {
+    ) -> anyhow::Result<Vec<OrderId>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.account_open_orders_by_ref(&pool, &manager).await
+    }
+
+    /// Get open orders for a balance manager in a pool, both addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef owning the orders
+    pub async fn account_open_orders_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Vec<OrderId>> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .account_open_orders(&mut ptb, pool_key, manager_key)
+            .account_open_orders_by_ref(&mut ptb, pool, manager)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -335,7 +655,7 @@ impl DeepBookClient {
                 let res = res
                     .first()
                     .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
-                let order_ids = bcs::from_bytes::<Vec<u128>>(&res.0)?;
+                let order_ids = bcs::from_bytes::<Vec<OrderId>>(&res.0)?;
                 Ok(order_ids)
             }
             Err(e) => Err(e),
@@ -346,22 +666,46 @@ impl DeepBookClient {
     ///
     /// @param pool_key - The key of the pool
     /// @param order_id - The order ID
-    pub async fn get_order(&self, pool_key: &str, order_id: u128) -> anyhow::Result<Option<Order>> {
+    pub async fn get_order(
+        &self,
+        pool_key: &str,
+        order_id: OrderId,
+    ) -> anyhow::Result<Option<Order>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_order_by_ref(&pool, order_id).await
+    }
+
+    /// Get the order information for a specific order in a pool addressed by object ID
+    ///
+    /// The book aborts in its `big_vector` lookup when no order has this ID, which is
+    /// reported as `None` rather than an error.
+    ///
+    /// @param pool - The PoolRef holding the order
+    /// @param order_id - The order ID
+    pub async fn get_order_by_ref(
+        &self,
+        pool: &PoolRef,
+        order_id: OrderId,
+    ) -> anyhow::Result<Option<Order>> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .get_order(&mut ptb, pool_key, order_id)
+            .get_order_by_ref(&mut ptb, pool, order_id)
             .await?;
 
-        match self.client.dev_inspect_transaction(self.address, ptb).await {
-            Ok(res) => {
-                let res = res
-                    .first()
-                    .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
-                let order = bcs::from_bytes::<Order>(&res.0)?;
-                Ok(Some(order))
-            }
-            Err(e) => Err(e),
-        }
+        let results = match self
+            .client
+            .dev_inspect_transaction_results(self.address, ptb)
+            .await
+        {
+            Ok(results) => results,
+            Err(e) if is_missing_order_abort(&e.to_string()) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let res = results
+            .first()
+            .and_then(|values| values.first())
+            .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
+        Ok(Some(bcs::from_bytes::<Order>(&res.0)?))
     }
 
     /// Get the order information for a specific order in a pool, with normalized price
@@ -371,44 +715,95 @@ impl DeepBookClient {
     pub async fn get_order_normalized(
         &self,
         pool_key: &str,
-        order_id: u128,
+        order_id: OrderId,
+    ) -> anyhow::Result<Option<NormalizedOrder>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_order_normalized_by_ref(&pool, order_id).await
+    }
+
+    /// Get the order information for a specific order in a pool addressed by object ID, with normalized price
+    ///
+    /// @param pool - The PoolRef holding the order
+    /// @param order_id - The order ID
+    pub async fn get_order_normalized_by_ref(
+        &self,
+        pool: &PoolRef,
+        order_id: OrderId,
     ) -> anyhow::Result<Option<NormalizedOrder>> {
-        let order = match self.get_order(pool_key, order_id).await? {
+        let order = match self.get_order_by_ref(pool, order_id).await? {
             Some(order) => order,
             None => return Ok(None),
         };
 
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        Ok(Some(NormalizedOrder::from_raw(order, pool)))
+    }
 
-        let (is_bid, raw_price, _order_id) = self.decode_order_id(order.order_id)?;
-        let normalized_price = (raw_price as f64 * base_coin.scalar as f64)
-            / (quote_coin.scalar as f64 * FLOAT_SCALAR as f64);
+    /// Find an open order of a balance manager by its client order ID
+    ///
+    /// @param pool_key - The key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    /// @param client_order_id - The client order ID
+    pub async fn get_order_by_client_order_id(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+        client_order_id: ClientOrderId,
+    ) -> anyhow::Result<Option<Order>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.get_order_by_client_order_id_by_ref(&pool, &manager, client_order_id)
+            .await
+    }
 
-        Ok(Some(NormalizedOrder {
-            balance_manager_id: order.balance_manager_id,
-            order_id: order.order_id,
-            client_order_id: order.client_order_id,
-            quantity: format!("{:.9}", order.quantity as f64 / base_coin.scalar as f64),
-            filled_quantity: format!(
-                "{:.9}",
-                order.filled_quantity as f64 / base_coin.scalar as f64
-            ),
-            fee_is_deep: order.fee_is_deep,
-            order_deep_price: NormalizedOrderDeepPrice {
-                asset_is_base: order.order_deep_price.asset_is_base,
-                deep_per_asset: format!(
-                    "{:.9}",
-                    order.order_deep_price.deep_per_asset as f64 / DEEP_SCALAR as f64
-                ),
-            },
-            epoch: order.epoch,
-            status: order.status,
-            expire_timestamp: order.expire_timestamp,
-            is_bid,
-            normalized_price: format!("{:.9}", normalized_price),
-        }))
+    /// Find an open order of a balance manager by its client order ID, with the pool and
+    /// balance manager addressed by object ID
+    ///
+    /// @param pool - The PoolRef holding the order
+    /// @param manager - The BalanceManagerRef owning the order
+    /// @param client_order_id - The client order ID
+    pub async fn get_order_by_client_order_id_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+        client_order_id: ClientOrderId,
+    ) -> anyhow::Result<Option<Order>> {
+        let order_ids = self.account_open_orders_by_ref(pool, manager).await?;
+        if order_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let orders = self
+            .get_orders_by_ref(pool, order_ids)
+            .await?
+            .unwrap_or_default();
+        Ok(orders
+            .into_iter()
+            .find(|order| order.client_order_id == client_order_id))
+    }
+
+    /// Find an open order of a balance manager by its client order ID, normalized
+    ///
+    /// @param pool_key - The key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    /// @param client_order_id - The client order ID
+    pub async fn get_order_normalized_by_client_order_id(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+        client_order_id: ClientOrderId,
+    ) -> anyhow::Result<Option<NormalizedOrder>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        match self
+            .get_order_by_client_order_id_by_ref(&pool, &manager, client_order_id)
+            .await?
+        {
+            Some(order) => {
+                self.get_order_normalized_by_ref(&pool, order.order_id)
+                    .await
+            }
+            None => Ok(None),
+        }
     }
 
     /// Get multiple orders from a pool
@@ -418,11 +813,24 @@ impl DeepBookClient {
     pub async fn get_orders(
         &self,
         pool_key: &str,
-        order_ids: Vec<String>,
+        order_ids: Vec<OrderId>,
+    ) -> anyhow::Result<Option<Vec<Order>>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_orders_by_ref(&pool, order_ids).await
+    }
+
+    /// Get multiple orders from a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef holding the orders
+    /// @param order_ids - List of order IDs to retrieve
+    pub async fn get_orders_by_ref(
+        &self,
+        pool: &PoolRef,
+        order_ids: Vec<OrderId>,
     ) -> anyhow::Result<Option<Vec<Order>>> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         self.deep_book
-            .get_orders(&mut ptb, pool_key, order_ids)
+            .get_orders_by_ref(&mut ptb, pool, order_ids)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -449,14 +857,29 @@ impl DeepBookClient {
         price_low: f64,
         price_high: f64,
         is_bid: bool,
+    ) -> anyhow::Result<Level2Range> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_level2_range_by_ref(&pool, price_low, price_high, is_bid)
+            .await
+    }
+
+    /// Get level 2 order book specifying range of price, on a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param price_low - Lower bound of the price range
+    /// @param price_high - Upper bound of the price range
+    /// @param is_bid - Whether to get bid or ask orders
+    pub async fn get_level2_range_by_ref(
+        &self,
+        pool: &PoolRef,
+        price_low: f64,
+        price_high: f64,
+        is_bid: bool,
     ) -> anyhow::Result<Level2Range> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
 
         self.deep_book
-            .get_level2_range(&mut ptb, pool_key, price_low, price_high, is_bid)
+            .get_level2_range_by_ref(&mut ptb, pool, price_low, price_high, is_bid)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -474,17 +897,11 @@ impl DeepBookClient {
                 Ok(Level2Range {
                     prices: prices
                         .into_iter()
-                        .map(|price| {
-                            ((price as f64 * base_coin.scalar as f64)
-                                / (FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-                                * 1e9)
-                                .round()
-                                / 1e9
-                        })
+                        .map(|price| (pool.price_from_raw(price) * 1e9).round() / 1e9)
                         .collect(),
                     quantities: quantities
                         .into_iter()
-                        .map(|qty| ((qty as f64 / base_coin.scalar as f64) * 1e9).round() / 1e9)
+                        .map(|qty| (pool.base_from_raw(qty) * 1e9).round() / 1e9)
                         .collect(),
                 })
             }
@@ -500,14 +917,24 @@ impl DeepBookClient {
         &self,
         pool_key: &str,
         ticks: u64,
+    ) -> anyhow::Result<Level2TicksFromMid> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_level2_ticks_from_mid_by_ref(&pool, ticks).await
+    }
+
+    /// Get level 2 order book ticks from mid-price for a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param ticks - Number of ticks from mid-price
+    pub async fn get_level2_ticks_from_mid_by_ref(
+        &self,
+        pool: &PoolRef,
+        ticks: u64,
     ) -> anyhow::Result<Level2TicksFromMid> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
 
         self.deep_book
-            .get_level2_ticks_from_mid(&mut ptb, pool_key, ticks)
+            .get_level2_ticks_from_mid_by_ref(&mut ptb, pool, ticks)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -535,31 +962,19 @@ impl DeepBookClient {
                 Ok(Level2TicksFromMid {
                     bid_prices: bid_prices
                         .into_iter()
-                        .map(|price| {
-                            ((price as f64 * base_coin.scalar as f64)
-                                / (FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-                                * 1e9)
-                                .round()
-                                / 1e9
-                        })
+                        .map(|price| (pool.price_from_raw(price) * 1e9).round() / 1e9)
                         .collect(),
                     bid_quantities: bid_quantities
                         .into_iter()
-                        .map(|qty| ((qty as f64 / base_coin.scalar as f64) * 1e9).round() / 1e9)
+                        .map(|qty| (pool.base_from_raw(qty) * 1e9).round() / 1e9)
                         .collect(),
                     ask_prices: ask_prices
                         .into_iter()
-                        .map(|price| {
-                            ((price as f64 * base_coin.scalar as f64)
-                                / (FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-                                * 1e9)
-                                .round()
-                                / 1e9
-                        })
+                        .map(|price| (pool.price_from_raw(price) * 1e9).round() / 1e9)
                         .collect(),
                     ask_quantities: ask_quantities
                         .into_iter()
-                        .map(|qty| ((qty as f64 / base_coin.scalar as f64) * 1e9).round() / 1e9)
+                        .map(|qty| (pool.base_from_raw(qty) * 1e9).round() / 1e9)
                         .collect(),
                 })
             }
@@ -571,12 +986,17 @@ impl DeepBookClient {
     ///
     /// @param pool_key - Key of the pool
     pub async fn vault_balances(&self, pool_key: &str) -> anyhow::Result<VaultBalances> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.vault_balances_by_ref(&pool).await
+    }
+
+    /// Get the vault balances for a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn vault_balances_by_ref(&self, pool: &PoolRef) -> anyhow::Result<VaultBalances> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_scalar = self.config.get_coin(&pool.base_coin)?.scalar;
-        let quote_scalar = self.config.get_coin(&pool.quote_coin)?.scalar;
 
-        self.deep_book.vault_balances(&mut ptb, pool_key).await?;
+        self.deep_book.vault_balances_by_ref(&mut ptb, pool).await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(mut res) => {
@@ -596,8 +1016,8 @@ impl DeepBookClient {
                 let deep_in_vault = bcs::from_bytes::<u64>(&deep_in_vault.0)?;
 
                 Ok(VaultBalances {
-                    base: ((base_in_vault as f64 / base_scalar as f64) * 1e9).round() / 1e9,
-                    quote: ((quote_in_vault as f64 / quote_scalar as f64) * 1e9).round() / 1e9,
+                    base: (pool.base_from_raw(base_in_vault) * 1e9).round() / 1e9,
+                    quote: (pool.quote_from_raw(quote_in_vault) * 1e9).round() / 1e9,
                     deep: ((deep_in_vault as f64 / DEEP_SCALAR as f64) * 1e9).round() / 1e9,
                 })
             }
@@ -635,12 +1055,17 @@ impl DeepBookClient {
     ///
     /// @param pool_key - Key of the pool
     pub async fn mid_price(&self, pool_key: &str) -> anyhow::Result<f64> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.mid_price_by_ref(&pool).await
+    }
+
+    /// Get the mid price for a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn mid_price_by_ref(&self, pool: &PoolRef) -> anyhow::Result<f64> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
 
-        self.deep_book.mid_price(&mut ptb, pool_key).await?;
+        self.deep_book.mid_price_by_ref(&mut ptb, pool).await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(res) => {
@@ -649,8 +1074,7 @@ impl DeepBookClient {
                     .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
 
                 let mid_price = bcs::from_bytes::<u64>(&res.0)?;
-                let adjusted_mid_price = (mid_price as f64 * base_coin.scalar as f64)
-                    / (quote_coin.scalar as f64 * FLOAT_SCALAR as f64);
+                let adjusted_mid_price = pool.price_from_raw(mid_price);
 
                 Ok((adjusted_mid_price * 1e9).round() / 1e9)
             }
@@ -662,8 +1086,21 @@ impl DeepBookClient {
     ///
     /// @param pool_key - Key of the pool
     pub async fn pool_trade_params(&self, pool_key: &str) -> anyhow::Result<PoolTradeParams> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.pool_trade_params_by_ref(&pool).await
+    }
+
+    /// Get the trade parameters for a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn pool_trade_params_by_ref(
+        &self,
+        pool: &PoolRef,
+    ) -> anyhow::Result<PoolTradeParams> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        self.deep_book.pool_trade_params(&mut ptb, pool_key).await?;
+        self.deep_book
+            .pool_trade_params_by_ref(&mut ptb, pool)
+            .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(mut res) => {
@@ -697,12 +1134,19 @@ impl DeepBookClient {
     ///
     /// @param pool_key - Key of the pool
     pub async fn pool_book_params(&self, pool_key: &str) -> anyhow::Result<PoolBookParams> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.pool_book_params_by_ref(&pool).await
+    }
+
+    /// Get the book parameters for a pool addressed by object ID, including tick size, lot size, and min size
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn pool_book_params_by_ref(&self, pool: &PoolRef) -> anyhow::Result<PoolBookParams> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_scalar = self.config.get_coin(&pool.base_coin)?.scalar;
-        let quote_scalar = self.config.get_coin(&pool.quote_coin)?.scalar;
 
-        self.deep_book.pool_book_params(&mut ptb, pool_key).await?;
+        self.deep_book
+            .pool_book_params_by_ref(&mut ptb, pool)
+            .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(mut res) => {
@@ -722,10 +1166,9 @@ impl DeepBookClient {
                 let min_size = bcs::from_bytes::<u64>(&min_size.0)?;
 
                 Ok(PoolBookParams {
-                    tick_size: (tick_size as f64 * base_scalar as f64)
-                        / (quote_scalar as f64 * FLOAT_SCALAR as f64),
-                    lot_size: lot_size as f64 / base_scalar as f64,
-                    min_size: min_size as f64 / base_scalar as f64,
+                    tick_size: pool.price_from_raw(tick_size),
+                    lot_size: pool.base_from_raw(lot_size),
+                    min_size: pool.base_from_raw(min_size),
                 })
             }
             Err(e) => Err(e),
@@ -737,13 +1180,24 @@ impl DeepBookClient {
     /// @param pool_key - Key of the pool
     /// @param manager_key - The key of the BalanceManager
     pub async fn account(&self, pool_key: &str, manager_key: &str) -> anyhow::Result<Account> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.account_by_ref(&pool, &manager).await
+    }
+
+    /// Get the account information for a pool and balance manager addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    pub async fn account_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Account> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_scalar = self.config.get_coin(&pool.base_coin)?.scalar;
-        let quote_scalar = self.config.get_coin(&pool.quote_coin)?.scalar;
 
         self.deep_book
-            .account(&mut ptb, pool_key, manager_key)
+            .account_by_ref(&mut ptb, pool, manager)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -751,32 +1205,42 @@ impl DeepBookClient {
                 let res = res
                     .first()
                     .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
-                let raw_account = bcs::from_bytes::<Account>(&res.0)?;
-                Ok(Account {
-                    epoch: raw_account.epoch,
-                    open_orders: raw_account.open_orders,
-                    taker_volume: raw_account.taker_volume as f64 / base_scalar as f64,
-                    maker_volume: raw_account.maker_volume as f64 / base_scalar as f64,
-                    active_stake: raw_account.active_stake as f64 / DEEP_SCALAR as f64,
-                    inactive_stake: raw_account.inactive_stake as f64 / DEEP_SCALAR as f64,
-                    created_proposal: raw_account.created_proposal,
-                    voted_proposal: raw_account.voted_proposal,
-                    unclaimed_rebates: Balances {
-                        base: raw_account.unclaimed_rebates.base as f64 / base_scalar as f64,
-                        quote: raw_account.unclaimed_rebates.quote as f64 / quote_scalar as f64,
-                        deep: raw_account.unclaimed_rebates.deep as f64 / DEEP_SCALAR as f64,
-                    },
-                    settled_balances: Balances {
-                        base: raw_account.settled_balances.base as f64 / base_scalar as f64,
-                        quote: raw_account.settled_balances.quote as f64 / quote_scalar as f64,
-                        deep: raw_account.settled_balances.deep as f64 / DEEP_SCALAR as f64,
-                    },
-                    owed_balances: Balances {
-                        base: raw_account.owed_balances.base as f64 / base_scalar as f64,
-                        quote: raw_account.owed_balances.quote as f64 / quote_scalar as f64,
-                        deep: raw_account.owed_balances.deep as f64 / DEEP_SCALAR as f64,
-                    },
-                })
+                account_from_bcs(&res.0, pool)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check whether a balance manager has an account in a pool
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn account_exists(&self, pool_key: &str, manager_key: &str) -> anyhow::Result<bool> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.account_exists_by_ref(&pool, &manager).await
+    }
+
+    /// Check whether a balance manager has an account in a pool, both addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    pub async fn account_exists_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<bool> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        self.deep_book
+            .account_exists_by_ref(&mut ptb, pool, manager)
+            .await?;
+
+        match self.client.dev_inspect_transaction(self.address, ptb).await {
+            Ok(res) => {
+                let res = res
+                    .first()
+                    .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
+                Ok(bcs::from_bytes::<bool>(&res.0)?)
             }
             Err(e) => Err(e),
         }
@@ -790,14 +1254,25 @@ impl DeepBookClient {
         &self,
         pool_key: &str,
         balance_manager_key: &str,
+    ) -> anyhow::Result<Balances> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(balance_manager_key)?;
+        self.locked_balance_by_ref(&pool, &manager).await
+    }
+
+    /// Get the locked balances for a pool and balance manager addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    pub async fn locked_balance_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
     ) -> anyhow::Result<Balances> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_scalar = self.config.get_coin(&pool.base_coin)?.scalar;
-        let quote_scalar = self.config.get_coin(&pool.quote_coin)?.scalar;
 
         self.deep_book
-            .locked_balance(&mut ptb, pool_key, balance_manager_key)
+            .locked_balance_by_ref(&mut ptb, pool, manager)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -817,11 +1292,14 @@ impl DeepBookClient {
                     .ok_or_else(|| anyhow::anyhow!("Failed to get first result"))?;
                 let deep_locked = bcs::from_bytes::<u64>(&deep_locked.0)?;
 
-                Ok(Balances {
-                    base: ((base_locked as f64 / base_scalar as f64) * 1e9).round() / 1e9,
-                    quote: ((quote_locked as f64 / quote_scalar as f64) * 1e9).round() / 1e9,
-                    deep: ((deep_locked as f64 / DEEP_SCALAR as f64) * 1e9).round() / 1e9,
-                })
+                Ok(Balances::from_raw(
+                    &RawBalances {
+                        base: base_locked,
+                        quote: quote_locked,
+                        deep: deep_locked,
+                    },
+                    pool,
+                ))
             }
             Err(e) => Err(e),
         }
@@ -831,14 +1309,21 @@ impl DeepBookClient {
     ///
     /// @param pool_key - Key of the pool
     pub async fn get_pool_deep_price(&self, pool_key: &str) -> anyhow::Result<PoolDeepPrice> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_pool_deep_price_by_ref(&pool).await
+    }
+
+    /// Get the DEEP price conversion for a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn get_pool_deep_price_by_ref(
+        &self,
+        pool: &PoolRef,
+    ) -> anyhow::Result<PoolDeepPrice> {
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-        let deep_coin = self.config.get_coin("DEEP")?;
 
         self.deep_book
-            .get_pool_deep_price(&mut ptb, pool_key)
+            .get_pool_deep_price_by_ref(&mut ptb, pool)
             .await?;
 
         match self.client.dev_inspect_transaction(self.address, ptb).await {
@@ -854,8 +1339,7 @@ impl DeepBookClient {
                     asset_is_base: pool_deep_price.asset_is_base,
                     deep_per_base: if pool_deep_price.asset_is_base {
                         Some(
-                            (deep_per_asset * base_coin.scalar as f64 / deep_coin.scalar as f64)
-                                .round()
+                            (deep_per_asset * pool.base_scalar as f64 / DEEP_SCALAR as f64).round()
                                 / 1e9,
                         )
                     } else {
@@ -863,7 +1347,7 @@ impl DeepBookClient {
                     },
                     deep_per_quote: if !pool_deep_price.asset_is_base {
                         Some(
-                            (deep_per_asset * quote_coin.scalar as f64 / deep_coin.scalar as f64)
+                            (deep_per_asset * pool.quote_scalar as f64 / DEEP_SCALAR as f64)
                                 .round()
                                 / 1e9,
                         )
@@ -876,16 +1360,168 @@ impl DeepBookClient {
         }
     }
 
+    /// Get the governance state of a pool, including proposals and quorum
+    ///
+    /// State stored in an earlier epoch is rolled forward to the chain epoch, matching what
+    /// the pool applies on its next interaction.
+    ///
+    /// @param pool_key - Key of the pool
+    pub async fn governance_state(&self, pool_key: &str) -> anyhow::Result<GovernanceState> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.governance_state_by_ref(&pool).await
+    }
+
+    /// Get the governance state of a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    pub async fn governance_state_by_ref(&self, pool: &PoolRef) -> anyhow::Result<GovernanceState> {
+        let inner = self.pool_inner(pool).await?;
+        let current_epoch = self
+            .client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?
+            .epoch;
+        let mut state = GovernanceState::from_pool_inner(&inner)?;
+        state.roll_forward(current_epoch);
+        Ok(state)
+    }
+
+    /// Get the stake and vote of a balance manager in a pool
+    ///
+    /// Stake that was inactive before the current chain epoch is reported as active,
+    /// matching how the pool rolls the account forward on its next interaction. Returns None
+    /// if the balance manager has never traded or staked in the pool.
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn account_stake(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+    ) -> anyhow::Result<Option<AccountStake>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.account_stake_by_ref(&pool, &manager).await
+    }
+
+    /// Get the stake and vote of a balance manager in a pool, both addressed by object ID
+    ///
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    pub async fn account_stake_by_ref(
+        &self,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Option<AccountStake>> {
+        let inner = self.pool_inner(pool).await?;
+        let current_epoch = self
+            .client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?
+            .epoch;
+        let accounts_id = json_object_id(json_field(&inner, &["state", "accounts", "id"])?)?;
+
+        let name = DynamicFieldName {
+            type_: TypeTag::from_str("0x2::object::ID")?,
+            value: serde_json::Value::String(manager.id.to_hex_literal()),
+        };
+        let account = match self
+            .client
+            .get_dynamic_field_object(accounts_id, name)
+            .await?
+        {
+            Some(object) => object_json(object)?,
+            None => return Ok(None),
+        };
+        let account = json_field(&account, &["value"])?;
+
+        let epoch = json_u64(json_field(account, &["epoch"])?)?;
+        let mut active_stake = json_u64(json_field(account, &["active_stake"])?)?;
+        let mut inactive_stake = json_u64(json_field(account, &["inactive_stake"])?)?;
+        let mut created_proposal = json_bool(json_field(account, &["created_proposal"])?)?;
+        let mut voted_proposal = match json_field(account, &["voted_proposal"])? {
+            serde_json::Value::Null => None,
+            value => json_option(value).map(json_object_id).transpose()?,
+        };
+
+        if epoch < current_epoch {
+            active_stake += inactive_stake;
+            inactive_stake = 0;
+            created_proposal = false;
+            voted_proposal = None;
+        }
+
+        Ok(Some(AccountStake {
+            epoch: epoch.max(current_epoch),
+            active_stake: deep_from_raw(active_stake),
+            inactive_stake: deep_from_raw(inactive_stake),
+            created_proposal,
+            voted_proposal,
+        }))
+    }
+
+    /// Get the timestamp of the latest DEEP price point of a pool, in milliseconds
+    ///
+    /// @param pool_key - Key of the pool
+    /// @returns None if the pool has no price points
+    pub async fn last_deep_price_point_ms(&self, pool_key: &str) -> anyhow::Result<Option<u64>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.last_deep_price_point_ms_by_ref(&pool).await
+    }
+
+    /// Get the timestamp of the latest DEEP price point of a pool addressed by object ID,
+    /// in milliseconds
+    ///
+    /// @param pool - The PoolRef to query
+    /// @returns None if the pool has no price points
+    pub async fn last_deep_price_point_ms_by_ref(
+        &self,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Option<u64>> {
+        let inner = self.pool_inner(pool).await?;
+        let deep_price = json_field(&inner, &["deep_price"])?;
+
+        let mut latest = None;
+        for prices in ["base_prices", "quote_prices"] {
+            let prices = json_field(deep_price, &[prices])?
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("Failed to parse {} as an array", prices))?;
+            if let Some(price) = prices.last() {
+                let timestamp = json_u64(json_field(price, &["timestamp"])?)?;
+                latest = latest.max(Some(timestamp));
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Read the versioned inner state of a pool as JSON
+    async fn pool_inner(&self, pool: &PoolRef) -> anyhow::Result<serde_json::Value> {
+        let pool_object = object_json(self.client.get_object(pool.pool_id).await?)?;
+        let versioned = json_field(&pool_object, &["inner"])?;
+        let versioned_id = json_object_id(json_field(versioned, &["id"])?)?;
+        let version = json_u64(json_field(versioned, &["version"])?)?;
+
+        let name = DynamicFieldName {
+            type_: TypeTag::U64,
+            value: serde_json::Value::String(version.to_string()),
+        };
+        let inner = self
+            .client
+            .get_dynamic_field_object(versioned_id, name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Pool {} inner state not found", pool.pool_id))?;
+        let inner = object_json(inner)?;
+        Ok(json_field(&inner, &["value"])?.clone())
+    }
+
     async fn get_quote_quantity_out_inner(
         &self,
         ptb: ProgrammableTransactionBuilder,
-        pool_key: &str,
+        pool: &PoolRef,
         base_quantity: f64,
     ) -> anyhow::Result<QuoteQuantityOut> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_scalar = self.config.get_coin(&pool.base_coin)?.scalar;
-        let quote_scalar = self.config.get_coin(&pool.quote_coin)?.scalar;
-
         match self.client.dev_inspect_transaction(self.address, ptb).await {
             Ok(mut res) => {
                 let base_out = res
@@ -905,8 +1541,8 @@ impl DeepBookClient {
 
                 Ok(QuoteQuantityOut {
                     base_quantity,
-                    base_out: (base_out as f64 / base_scalar as f64 * 1e9).round() / 1e9,
-                    quote_out: (quote_out as f64 / quote_scalar as f64 * 1e9).round() / 1e9,
+                    base_out: (pool.base_from_raw(base_out) * 1e9).round() / 1e9,
+                    quote_out: (pool.quote_from_raw(quote_out) * 1e9).round() / 1e9,
                     deep_required: (deep_required as f64 / DEEP_SCALAR as f64 * 1e9).round() / 1e9,
                 })
             }
@@ -914,14 +1550,66 @@ impl DeepBookClient {
         }
     }
 
+    /// Get the current time of the on-chain `Clock`, in milliseconds
+    pub async fn clock_timestamp_ms(&self) -> anyhow::Result<u64> {
+        let clock = object_json(self.client.get_object(SUI_CLOCK_OBJECT_ID).await?)?;
+        json_u64(json_field(&clock, &["timestamp_ms"])?)
+    }
+
+    /// Good-til-time expiring a duration from the current on-chain time
+    ///
+    /// @param duration - How long the order rests on the book
+    pub async fn good_til(&self, duration: Duration) -> anyhow::Result<TimeInForce> {
+        let now = self.clock_timestamp_ms().await?;
+        Ok(TimeInForce::good_til_duration(now, duration))
+    }
+
     /// Decode the order ID to get bid/ask status, price, and order ID
     ///
+    /// Prefer the accessors of OrderId.
+    ///
     /// @param encoded_order_id - Encoded order ID
-    pub fn decode_order_id(&self, encoded_order_id: u128) -> anyhow::Result<(bool, u64, u64)> {
-        let is_bid = (encoded_order_id >> 127) == 0;
-        let price = ((encoded_order_id >> 64) & ((1 << 63) - 1)) as u64;
-        let order_id = (encoded_order_id & ((1 << 64) - 1)) as u64;
-
-        Ok((is_bid, price, order_id))
+    pub fn decode_order_id(encoded_order_id: OrderId) -> (bool, u64, u64) {
+        (
+            encoded_order_id.is_bid(),
+            encoded_order_id.price(),
+            encoded_order_id.sequence(),
+        )
     }
 }
+
+fn fee_from_raw(raw: u64) -> f64 {
+    (raw as f64 / FLOAT_SCALAR as f64 * 1e9).round() / 1e9
+}
+
+fn deep_from_raw(raw: u64) -> f64 {
+    (raw as f64 / DEEP_SCALAR as f64 * 1e9).round() / 1e9
+}
+
+fn trade_params_from_json(value: &serde_json::Value) -> anyhow::Result<PoolTradeParams> {
+    Ok(PoolTradeParams {
+        taker_fee: fee_from_raw(json_u64(json_field(value, &["taker_fee"])?)?),
+        maker_fee: fee_from_raw(json_u64(json_field(value, &["maker_fee"])?)?),
+        stake_required: deep_from_raw(json_u64(json_field(value, &["stake_required"])?)?),
+    })
+}
+
+/// Decode a BCS-encoded `Account` returned by `pool::account`
+///
+/// @param bytes - The BCS bytes of the account
+/// @param pool - The PoolRef the account belongs to, used for scaling
+pub(crate) fn account_from_bcs(bytes: &[u8], pool: &PoolRef) -> anyhow::Result<Account> {
+    let raw_account = bcs::from_bytes::<RawAccount>(bytes)?;
+    Ok(Account::from_raw(raw_account, pool))
+}
+
+/// Whether a dev-inspect error is the abort raised by `pool::get_order` for an unknown order ID
+///
+/// @param error - The dev-inspect error message
+fn is_missing_order_abort(error: &str) -> bool {
+    error.contains("MoveAbort") && error.contains("Identifier(\"big_vector\")")
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
@@ -0,0 +1,545 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use crate::client::DeepBookClient;
+use crate::executor::TransactionExecutor;
+use crate::utils::backoff::Backoff;
+use crate::utils::types::{
+    ClientOrderId, OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams,
+};
+
+/// The price a trigger is compared against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriceSource {
+    Mid,
+    BestBid,
+    BestAsk,
+}
+
+/// Distance of a trailing stop from the best price seen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrailingDistance {
+    Absolute(f64),
+    /// Fraction of the best price seen, e.g. 0.05 for 5%
+    Percent(f64),
+}
+
+impl TrailingDistance {
+    fn from(&self, price: f64) -> f64 {
+        match self {
+            TrailingDistance::Absolute(distance) => *distance,
+            TrailingDistance::Percent(fraction) => price * fraction,
+        }
+    }
+}
+
+/// When a conditional order fires
+///
+/// Directions follow the side of the order fired: a sell stop-loss fires when the price
+/// falls to the trigger, a buy stop-loss when it rises to it, and take-profits the reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    StopLoss {
+        trigger_price: f64,
+    },
+    TakeProfit {
+        trigger_price: f64,
+    },
+    /// Follows the highest price for a sell, or the lowest for a buy, at a distance
+    TrailingStop {
+        distance: TrailingDistance,
+        /// Best price seen so far, None before the first observation
+        extreme: Option<f64>,
+    },
+}
+
+impl Trigger {
+    /// Feed a price to the trigger, returning whether it fires
+    ///
+    /// @param is_bid - Side of the order fired by the trigger
+    /// @param price - The observed price
+    pub fn observe(&mut self, is_bid: bool, price: f64) -> bool {
+        match self {
+            Trigger::StopLoss { trigger_price } => {
+                if is_bid {
+                    price >= *trigger_price
+                } else {
+                    price <= *trigger_price
+                }
+            }
+            Trigger::TakeProfit { trigger_price } => {
+                if is_bid {
+                    price <= *trigger_price
+                } else {
+                    price >= *trigger_price
+                }
+            }
+            Trigger::TrailingStop { distance, extreme } => {
+                let best = match *extreme {
+                    Some(best) if is_bid => best.min(price),
+                    Some(best) => best.max(price),
+                    None => price,
+                };
+                *extreme = Some(best);
+                if is_bid {
+                    price >= best + distance.from(best)
+                } else {
+                    price <= best - distance.from(best)
+                }
+            }
+        }
+    }
+}
+
+/// The order placed when a trigger fires
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OrderAction {
+    Market,
+    Limit { price: f64 },
+}
+
+/// Lifecycle of a conditional order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConditionalStatus {
+    Pending,
+    /// The trigger fired and the order was handed to the executor
+    Triggered,
+    /// The trigger fired but submitting the order failed; its siblings stay pending
+    Failed(String),
+    Cancelled,
+    Expired,
+}
+
+/// A stop or take-profit order held off-chain until its trigger fires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalOrder {
+    /// Local ID assigned by the TriggerEngine
+    pub id: u64,
+    pub pool_key: String,
+    pub balance_manager_key: String,
+    pub client_order_id: ClientOrderId,
+    pub is_bid: bool,
+    pub quantity: f64,
+    pub trigger: Trigger,
+    pub source: PriceSource,
+    pub action: OrderAction,
+    pub pay_with_deep: Option<bool>,
+    /// Orders sharing a group are one-cancels-other: the first to be submitted cancels
+    /// the rest
+    pub oco_group: Option<u64>,
+    /// Time after which the order expires, in milliseconds since the Unix epoch
+    pub expires_at_ms: Option<u64>,
+    pub status: ConditionalStatus,
+}
+
+/// Prices observed for a pool
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PriceQuote {
+    pub mid: Option<f64>,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+}
+
+impl PriceQuote {
+    fn get(&self, source: PriceSource) -> Option<f64> {
+        match source {
+            PriceSource::Mid => self.mid,
+            PriceSource::BestBid => self.best_bid,
+            PriceSource::BestAsk => self.best_ask,
+        }
+    }
+}
+
+/// Pending conditional orders and the logic deciding when they fire
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriggerEngine {
+    next_id: u64,
+    orders: BTreeMap<u64, ConditionalOrder>,
+}
+
+impl TriggerEngine {
+    /// Creates a new TriggerEngine with no orders
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restore an engine saved by `save`, or an empty one if the file does not exist
+    ///
+    /// @param path - The JSON state file
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        match std::fs::read(path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save every order as JSON, replacing the file atomically
+    ///
+    /// @param path - The JSON state file
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(temp, path)?;
+        Ok(())
+    }
+
+    /// Add an order, assigning it an ID and marking it pending
+    ///
+    /// @param order - The order to add. Its `id` and `status` are overwritten
+    /// @returns The assigned ID
+    pub fn add(&mut self, mut order: ConditionalOrder) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        order.id = id;
+        order.status = ConditionalStatus::Pending;
+        self.orders.insert(id, order);
+        id
+    }
+
+    /// Cancel a pending order
+    ///
+    /// @param id - The order ID
+    pub fn cancel(&mut self, id: u64) -> anyhow::Result<()> {
+        let order = self
+            .orders
+            .get_mut(&id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown conditional order {}", id))?;
+        if order.status != ConditionalStatus::Pending {
+            return Err(anyhow::anyhow!("Conditional order {} is not pending", id));
+        }
+        order.status = ConditionalStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Get an order
+    ///
+    /// @param id - The order ID
+    pub fn order(&self, id: u64) -> Option<&ConditionalOrder> {
+        self.orders.get(&id)
+    }
+
+    /// All orders, in ID order
+    pub fn orders(&self) -> impl Iterator<Item = &ConditionalOrder> {
+        self.orders.values()
+    }
+
+    /// Orders still waiting for their trigger
+    pub fn pending(&self) -> impl Iterator<Item = &ConditionalOrder> {
+        self.orders
+            .values()
+            .filter(|order| order.status == ConditionalStatus::Pending)
+    }
+
+    /// Keys of the pools with pending orders
+    pub fn pending_pools(&self) -> Vec<String> {
+        let mut pools: Vec<String> = self.pending().map(|order| order.pool_key.clone()).collect();
+        pools.sort_unstable();
+        pools.dedup();
+        pools
+    }
+
+    /// Expire old orders, then feed prices to the pending orders of a pool
+    ///
+    /// Fired orders are marked triggered. Their one-cancels-other siblings stay pending
+    /// but cannot fire until the order is marked submitted, which cancels them, or failed,
+    /// which lets them fire again.
+    ///
+    /// @param pool_key - Key of the pool the prices belong to
+    /// @param quote - The observed prices
+    /// @param now_ms - Current time in milliseconds since the Unix epoch
+    /// @returns The orders that fired
+    pub fn evaluate(
+        &mut self,
+        pool_key: &str,
+        quote: &PriceQuote,
+        now_ms: u64,
+    ) -> Vec<ConditionalOrder> {
+        let mut fired = Vec::new();
+        let ids: Vec<u64> = self.orders.keys().copied().collect();
+        for id in ids {
+            let order = match self.orders.get_mut(&id) {
+                Some(order) if order.status == ConditionalStatus::Pending => order,
+                _ => continue,
+            };
+            if order
+                .expires_at_ms
+                .is_some_and(|expires_at| now_ms >= expires_at)
+            {
+                order.status = ConditionalStatus::Expired;
+                continue;
+            }
+            if order.pool_key != pool_key {
+                continue;
+            }
+            let price = match quote.get(order.source) {
+                Some(price) => price,
+                None => continue,
+            };
+            if !order.trigger.observe(order.is_bid, price) {
+                continue;
+            }
+            let group = order.oco_group;
+            if group.is_some_and(|group| self.group_in_flight(group)) {
+                continue;
+            }
+
+            let order = self.orders.get_mut(&id).expect("order exists");
+            order.status = ConditionalStatus::Triggered;
+            fired.push(order.clone());
+        }
+        fired
+    }
+
+    /// Record that a fired order was submitted, cancelling its one-cancels-other siblings
+    ///
+    /// @param id - The order ID
+    pub fn mark_submitted(&mut self, id: u64) {
+        let group = match self.orders.get(&id) {
+            Some(order) if order.status == ConditionalStatus::Triggered => order.oco_group,
+            _ => return,
+        };
+        if let Some(group) = group {
+            for sibling in self.orders.values_mut() {
+                if sibling.oco_group == Some(group) && sibling.status == ConditionalStatus::Pending
+                {
+                    sibling.status = ConditionalStatus::Cancelled;
+                }
+            }
+        }
+    }
+
+    /// Record that submitting a fired order failed
+    ///
+    /// Its one-cancels-other siblings stay pending and may fire again.
+    ///
+    /// @param id - The order ID
+    /// @param reason - The error returned by the executor
+    pub fn mark_failed(&mut self, id: u64, reason: String) {
+        if let Some(order) = self.orders.get_mut(&id) {
+            order.status = ConditionalStatus::Failed(reason);
+        }
+    }
+
+    /// Whether an order of the group fired and has not been marked submitted or failed
+    fn group_in_flight(&self, group: u64) -> bool {
+        self.orders.values().any(|order| {
+            order.oco_group == Some(group) && order.status == ConditionalStatus::Triggered
+        })
+    }
+}
+
+/// Outcome of one ConditionalOrderWatcher poll
+#[derive(Debug, Clone, Default)]
+pub struct PollReport {
+    /// The orders that fired; failed submissions have a `Failed` status
+    pub fired: Vec<ConditionalOrder>,
+    /// Keys of the pools whose prices were read
+    pub checked_pools: Vec<String>,
+    /// Keys of the pools whose prices could not be read, with the error
+    pub failed_pools: Vec<(String, String)>,
+}
+
+/// Watches pool prices and fires conditional orders through `DeepBookContract`
+///
+/// The engine is saved to its state file after every change. A fired order is saved as
+/// triggered before it is submitted, so a crash never submits it twice; its siblings then
+/// stay pending but blocked until cancelled.
+pub struct ConditionalOrderWatcher<'a> {
+    client: &'a DeepBookClient,
+    engine: TriggerEngine,
+    path: PathBuf,
+}
+
+impl<'a> ConditionalOrderWatcher<'a> {
+    /// Open the watcher, restoring pending orders from the state file
+    ///
+    /// @param client - The DeepBookClient used to read prices and build orders
+    /// @param path - The JSON state file, created on the first save
+    pub fn open(client: &'a DeepBookClient, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        Ok(Self {
+            client,
+            engine: TriggerEngine::load(&path)?,
+            path,
+        })
+    }
+
+    pub fn engine(&self) -> &TriggerEngine {
+        &self.engine
+    }
+
+    /// Add an order and save the state file
+    ///
+    /// @param order - The order to add
+    /// @returns The assigned ID
+    pub fn add(&mut self, order: ConditionalOrder) -> anyhow::Result<u64> {
+        self.client.config().pool_ref(&order.pool_key)?;
+        self.client
+            .config()
+            .balance_manager_ref(&order.balance_manager_key)?;
+        let id = self.engine.add(order);
+        self.engine.save(&self.path)?;
+        Ok(id)
+    }
+
+    /// Cancel a pending order and save the state file
+    ///
+    /// @param id - The order ID
+    pub fn cancel(&mut self, id: u64) -> anyhow::Result<()> {
+        self.engine.cancel(id)?;
+        self.engine.save(&self.path)
+    }
+
+    /// Read the mid price and the best bid and ask of a pool
+    ///
+    /// @param pool_key - Key of the pool
+    pub async fn price_quote(&self, pool_key: &str) -> anyhow::Result<PriceQuote> {
+        let book = self.client.get_level2_ticks_from_mid(pool_key, 1).await?;
+        Ok(PriceQuote {
+            mid: self.client.mid_price(pool_key).await.ok(),
+            best_bid: book.bid_prices.iter().copied().reduce(f64::max),
+            best_ask: book.ask_prices.iter().copied().reduce(f64::min),
+        })
+    }
+
+    /// Check every pool with pending orders once and submit the orders that fire
+    ///
+    /// Price read errors and submission errors are recorded in the report and on the
+    /// order rather than returned, so one failing pool does not hold up the others.
+    ///
+    /// @param executor - The TransactionExecutor submitting fired orders
+    /// @returns The PollReport, or the error writing the state file
+    pub async fn poll(&mut self, executor: &dyn TransactionExecutor) -> anyhow::Result<PollReport> {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+
+        let mut report = PollReport::default();
+        for pool_key in self.engine.pending_pools() {
+            match self.price_quote(&pool_key).await {
+                Ok(quote) => {
+                    report
+                        .fired
+                        .extend(self.engine.evaluate(&pool_key, &quote, now_ms));
+                    report.checked_pools.push(pool_key);
+                }
+                Err(e) => report.failed_pools.push((pool_key, e.to_string())),
+            }
+        }
+        // Persists expiries and trailing stop extremes too.
+        self.engine.save(&self.path)?;
+
+        for order in &mut report.fired {
+            match self.submit(order, executor).await {
+                Ok(()) => self.engine.mark_submitted(order.id),
+                Err(e) => self.engine.mark_failed(order.id, e.to_string()),
+            }
+            self.engine.save(&self.path)?;
+            if let Some(updated) = self.engine.order(order.id) {
+                order.status = updated.status.clone();
+            }
+        }
+        Ok(report)
+    }
+
+    /// Poll repeatedly, passing the result of every poll to `on_poll`
+    ///
+    /// Errors do not stop the loop. While polls fail, or no pool's prices can be read,
+    /// the delay doubles from `interval` up to `max_backoff`.
+    ///
+    /// @param interval - Time between successful polls
+    /// @param max_backoff - Longest delay after failed polls
+    /// @param executor - The TransactionExecutor submitting fired orders
+    /// @param on_poll - Called with the result of each poll
+    pub async fn run<F>(
+        &mut self,
+        interval: Duration,
+        max_backoff: Duration,
+        executor: &dyn TransactionExecutor,
+        mut on_poll: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut(&anyhow::Result<PollReport>),
+    {
+        let mut backoff = Backoff::new(interval, max_backoff);
+        loop {
+            let result = self.poll(executor).await;
+            let failed = match &result {
+                Ok(report) => report.checked_pools.is_empty() && !report.failed_pools.is_empty(),
+                Err(_) => true,
+            };
+            let delay = if failed {
+                backoff.next_delay()
+            } else {
+                backoff.reset();
+                interval
+            };
+            on_poll(&result);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn submit(
+        &self,
+        order: &ConditionalOrder,
+        executor: &dyn TransactionExecutor,
+    ) -> anyhow::Result<()> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        match order.action {
+            OrderAction::Market => {
+                self.client
+                    .deep_book
+                    .place_market_order(
+                        &mut ptb,
+                        PlaceMarketOrderParams {
+                            pool_key: order.pool_key.clone(),
+                            balance_manager_key: order.balance_manager_key.clone(),
+                            client_order_id: order.client_order_id,
+                            quantity: order.quantity,
+                            is_bid: order.is_bid,
+                            self_matching_option: None,
+                            pay_with_deep: order.pay_with_deep,
+                        },
+                    )
+                    .await?;
+            }
+            OrderAction::Limit { price } => {
+                self.client
+                    .deep_book
+                    .place_limit_order(
+                        &mut ptb,
+                        PlaceLimitOrderParams {
+                            pool_key: order.pool_key.clone(),
+                            balance_manager_key: order.balance_manager_key.clone(),
+                            client_order_id: order.client_order_id,
+                            price,
+                            quantity: order.quantity,
+                            is_bid: order.is_bid,
+                            expiration: None,
+                            order_type: Some(OrderType::NoRestriction),
+                            self_matching_option: None,
+                            pay_with_deep: order.pay_with_deep,
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        let response = executor.execute(ptb.finish()).await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Success) => Ok(()),
+            Some(SuiExecutionStatus::Failure { error }) => {
+                Err(anyhow::anyhow!("Transaction failed: {}", error))
+            }
+            None => Err(anyhow::anyhow!("Transaction response has no effects")),
+        }
+    }
+}
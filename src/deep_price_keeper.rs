@@ -0,0 +1,247 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::ProgrammableTransaction;
+
+use crate::client::DeepBookClient;
+use crate::executor::TransactionExecutor;
+use crate::utils::backoff::Backoff;
+use crate::utils::config::PoolMap;
+
+/// Default number of price points added per transaction by the DeepPriceKeeper
+pub const DEFAULT_POOLS_PER_TRANSACTION: usize = 20;
+
+/// A non-DEEP pool and the DEEP pool its price points are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepPriceTarget {
+    pub pool_key: String,
+    pub reference_pool_key: String,
+}
+
+/// A target pool whose latest DEEP price point is too old
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalePool {
+    pub target: DeepPriceTarget,
+    /// Timestamp of the latest price point in milliseconds, None if the pool has none
+    pub last_update_ms: Option<u64>,
+}
+
+/// Pick the reference pool for a non-DEEP pool
+///
+/// A reference pool is a whitelisted pool trading DEEP against the target's base or quote
+/// asset; DeepBook only accepts price points from whitelisted pools. Pools against the
+/// base asset are preferred, then the lowest key.
+///
+/// @param pools - The configured pools
+/// @param pool_key - Key of the target pool
+/// @param whitelisted - Keys of the whitelisted pools
+/// @returns None if the target trades DEEP itself or no pool can serve as reference
+pub fn select_reference_pool(
+    pools: &PoolMap,
+    pool_key: &str,
+    whitelisted: &[&str],
+) -> Option<&'static str> {
+    let target = pools.get(pool_key)?;
+    if target.base_coin == "DEEP" || target.quote_coin == "DEEP" {
+        return None;
+    }
+
+    let mut candidates: Vec<(bool, &'static str)> = pools
+        .iter()
+        .filter(|(key, _)| whitelisted.contains(key))
+        .filter_map(|(key, pool)| {
+            let other = if pool.base_coin == "DEEP" {
+                &pool.quote_coin
+            } else if pool.quote_coin == "DEEP" {
+                &pool.base_coin
+            } else {
+                return None;
+            };
+            if *other == target.base_coin {
+                Some((false, *key))
+            } else if *other == target.quote_coin {
+                Some((true, *key))
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort_unstable();
+    candidates.first().map(|(_, key)| *key)
+}
+
+/// Whether a pool's DEEP price needs a new point
+///
+/// @param last_update_ms - Timestamp of the latest price point, None if there is none
+/// @param now_ms - The current on-chain time in milliseconds
+/// @param max_age - Oldest acceptable price point
+pub fn is_stale(last_update_ms: Option<u64>, now_ms: u64, max_age: Duration) -> bool {
+    match last_update_ms {
+        Some(last_update_ms) => now_ms.saturating_sub(last_update_ms) >= max_age.as_millis() as u64,
+        None => true,
+    }
+}
+
+/// Keeps the DEEP conversion of non-DEEP pools fresh by adding price points
+pub struct DeepPriceKeeper<'a> {
+    client: &'a DeepBookClient,
+    targets: Vec<DeepPriceTarget>,
+    max_age: Duration,
+    pools_per_transaction: usize,
+}
+
+impl<'a> DeepPriceKeeper<'a> {
+    /// Creates a new DeepPriceKeeper, picking a whitelisted reference pool for every target
+    ///
+    /// Fails when a target has no whitelisted DEEP pool to reference.
+    ///
+    /// @param client - The DeepBookClient used to read pools and build calls
+    /// @param pool_keys - Keys of the non-DEEP pools to keep fresh
+    /// @param max_age - Oldest acceptable price point
+    pub async fn new(
+        client: &'a DeepBookClient,
+        pool_keys: &[&str],
+        max_age: Duration,
+    ) -> anyhow::Result<Self> {
+        let pools = client.config().pools();
+        let mut whitelisted = Vec::new();
+        for (key, pool) in pools {
+            if (pool.base_coin == "DEEP" || pool.quote_coin == "DEEP")
+                && client.whitelisted(key).await?
+            {
+                whitelisted.push(*key);
+            }
+        }
+
+        let targets = pool_keys
+            .iter()
+            .map(|pool_key| {
+                client.config().get_pool(pool_key)?;
+                let reference_pool_key = select_reference_pool(pools, pool_key, &whitelisted)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("No whitelisted DEEP reference pool for {}", pool_key)
+                    })?;
+                Ok(DeepPriceTarget {
+                    pool_key: pool_key.to_string(),
+                    reference_pool_key: reference_pool_key.to_string(),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            client,
+            targets,
+            max_age,
+            pools_per_transaction: DEFAULT_POOLS_PER_TRANSACTION,
+        })
+    }
+
+    /// Set the maximum number of price points per transaction
+    ///
+    /// @param pools - The new maximum, at least one
+    pub fn with_pools_per_transaction(mut self, pools: usize) -> Self {
+        self.pools_per_transaction = pools.max(1);
+        self
+    }
+
+    pub fn targets(&self) -> &[DeepPriceTarget] {
+        &self.targets
+    }
+
+    /// Find the targets whose latest price point is older than `max_age`
+    pub async fn stale_pools(&self) -> anyhow::Result<Vec<StalePool>> {
+        let now = self.client.clock_timestamp_ms().await?;
+        let mut stale = Vec::new();
+        for target in &self.targets {
+            let last_update_ms = self
+                .client
+                .last_deep_price_point_ms(&target.pool_key)
+                .await?;
+            if is_stale(last_update_ms, now, self.max_age) {
+                stale.push(StalePool {
+                    target: target.clone(),
+                    last_update_ms,
+                });
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Build the price point transactions for stale pools
+    ///
+    /// @param pools - The StalePools to update
+    pub async fn build(&self, pools: &[StalePool]) -> anyhow::Result<Vec<ProgrammableTransaction>> {
+        let mut transactions = Vec::new();
+        for batch in pools.chunks(self.pools_per_transaction) {
+            let mut ptb = ProgrammableTransactionBuilder::new();
+            for pool in batch {
+                self.client
+                    .deep_book
+                    .add_deep_price_point(
+                        &mut ptb,
+                        &pool.target.pool_key,
+                        &pool.target.reference_pool_key,
+                    )
+                    .await?;
+            }
+            transactions.push(ptb.finish());
+        }
+        Ok(transactions)
+    }
+
+    /// Add price points to every stale target
+    ///
+    /// @param executor - The TransactionExecutor submitting the price points
+    /// @returns The updated pools
+    pub async fn update(
+        &self,
+        executor: &dyn TransactionExecutor,
+    ) -> anyhow::Result<Vec<StalePool>> {
+        let stale = self.stale_pools().await?;
+        for transaction in self.build(&stale).await? {
+            let response = executor.execute(transaction).await?;
+            if let Some(SuiExecutionStatus::Failure { error }) =
+                response.effects.as_ref().map(|effects| effects.status())
+            {
+                return Err(anyhow::anyhow!("Price point transaction failed: {}", error));
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Update on a fixed interval, backing off after failed updates
+    ///
+    /// Errors do not stop the loop; each update's outcome is passed to `on_update`.
+    ///
+    /// @param interval - Time between successful updates
+    /// @param max_backoff - Longest wait after repeated failures
+    /// @param executor - The TransactionExecutor submitting the price points
+    /// @param on_update - Called with the outcome of every update
+    pub async fn run<F>(
+        &self,
+        interval: Duration,
+        max_backoff: Duration,
+        executor: &dyn TransactionExecutor,
+        mut on_update: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut(&anyhow::Result<Vec<StalePool>>),
+    {
+        let mut backoff = Backoff::new(interval, max_backoff);
+        loop {
+            let result = self.update(executor).await;
+            let delay = if result.is_ok() {
+                backoff.reset();
+                interval
+            } else {
+                backoff.next_delay()
+            };
+            on_update(&result);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
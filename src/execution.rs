@@ -0,0 +1,593 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use tokio::sync::watch;
+
+use crate::client::PoolBookParams;
+use crate::executor::TransactionExecutor;
+use crate::oms::{OrderKey, OrderManager};
+use crate::utils::client_order_id::ClientOrderIdGenerator;
+use crate::utils::types::{
+    ClientOrderId, OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams,
+};
+
+/// Number of past `OrderFilled` events read by the first fill sync
+const FILL_SYNC_EVENTS: usize = 50;
+
+/// Source of time for the scheduler, replaceable in tests
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Milliseconds since the Unix epoch
+    fn now_ms(&self) -> u64;
+    /// Return once `now_ms` has reached `deadline_ms`
+    async fn sleep_until(&self, deadline_ms: u64);
+}
+
+/// Wall clock backed by the tokio timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    async fn sleep_until(&self, deadline_ms: u64) {
+        let now = self.now_ms();
+        if deadline_ms > now {
+            tokio::time::sleep(Duration::from_millis(deadline_ms - now)).await;
+        }
+    }
+}
+
+/// A large order to be worked through child orders
+#[derive(Debug, Clone)]
+pub struct ParentOrder {
+    pub pool_key: String,
+    pub is_bid: bool,
+    /// Total base quantity to trade
+    pub quantity: f64,
+    /// Worst acceptable price. Slices without a limit are sent as market orders
+    pub limit_price: Option<f64>,
+    pub pay_with_deep: Option<bool>,
+}
+
+/// How a parent order is split over time
+#[derive(Debug, Clone)]
+pub enum ExecutionAlgorithm {
+    /// Equal slices at a fixed interval
+    Twap { slices: usize, interval: Duration },
+    /// One slice per weight at a fixed interval, sized in proportion to the weight
+    Vwap {
+        weights: Vec<f64>,
+        interval: Duration,
+    },
+    /// A single resting order of at most `display_quantity`, refilled once it is filled.
+    /// Requires a limit price
+    Iceberg {
+        display_quantity: f64,
+        poll_interval: Duration,
+    },
+}
+
+/// A child order sent by the scheduler
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildOrder {
+    pub client_order_id: ClientOrderId,
+    pub pool_key: String,
+    pub is_bid: bool,
+    pub quantity: f64,
+    /// Limit price, None for a market order
+    pub price: Option<f64>,
+    pub order_type: OrderType,
+    pub pay_with_deep: Option<bool>,
+}
+
+/// Fill state of a child order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChildStatus {
+    pub filled_quantity: f64,
+    /// Whether the order may still fill
+    pub open: bool,
+}
+
+/// Places, tracks and cancels child orders
+#[async_trait]
+pub trait ChildOrderExecutor: Send {
+    /// Submit a child order and wait for the transaction
+    async fn place(&mut self, order: &ChildOrder) -> anyhow::Result<()>;
+    /// Current fill state of a child order
+    async fn status(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<ChildStatus>;
+    /// Cancel a resting child order
+    async fn cancel(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<()>;
+}
+
+/// Lifecycle of a parent order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionState {
+    Running,
+    Paused,
+    Cancelled,
+    Completed,
+}
+
+/// Progress of a parent order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionProgress {
+    pub state: ExecutionState,
+    pub filled_quantity: f64,
+    pub remaining_quantity: f64,
+    pub children: Vec<ClientOrderId>,
+}
+
+/// Controls a running ExecutionScheduler and observes its progress
+#[derive(Debug, Clone)]
+pub struct ExecutionHandle {
+    control: Arc<watch::Sender<ExecutionState>>,
+    progress: watch::Receiver<ExecutionProgress>,
+}
+
+impl ExecutionHandle {
+    /// Stop sending child orders until `resume` is called
+    ///
+    /// A resting iceberg slice stays on the book while paused.
+    pub fn pause(&self) {
+        self.set(ExecutionState::Paused);
+    }
+
+    /// Continue after `pause`. Remaining slices are shifted by the time spent paused
+    pub fn resume(&self) {
+        self.set(ExecutionState::Running);
+    }
+
+    /// Stop the execution, canceling a resting child order
+    pub fn cancel(&self) {
+        self.control.send_replace(ExecutionState::Cancelled);
+    }
+
+    /// The latest progress reported by the scheduler
+    pub fn progress(&self) -> ExecutionProgress {
+        self.progress.borrow().clone()
+    }
+
+    fn set(&self, state: ExecutionState) {
+        self.control.send_if_modified(|current| {
+            if *current == ExecutionState::Cancelled || *current == state {
+                return false;
+            }
+            *current = state;
+            true
+        });
+    }
+}
+
+/// Split a TWAP or VWAP parent quantity into slices
+///
+/// @param algorithm - A Twap or Vwap ExecutionAlgorithm
+/// @param quantity - The parent quantity
+/// @param start_ms - Time of the first slice
+/// @returns The due time and quantity of each slice
+pub fn slice_schedule(
+    algorithm: &ExecutionAlgorithm,
+    quantity: f64,
+    start_ms: u64,
+) -> anyhow::Result<Vec<(u64, f64)>> {
+    let (weights, interval) = match algorithm {
+        ExecutionAlgorithm::Twap { slices, interval } => (vec![1.0; *slices], *interval),
+        ExecutionAlgorithm::Vwap { weights, interval } => (weights.clone(), *interval),
+        ExecutionAlgorithm::Iceberg { .. } => {
+            return Err(anyhow::anyhow!("Iceberg orders are not sliced by time"))
+        }
+    };
+    if weights.is_empty() {
+        return Err(anyhow::anyhow!("Execution needs at least one slice"));
+    }
+    if weights
+        .iter()
+        .any(|weight| *weight < 0.0 || !weight.is_finite())
+    {
+        return Err(anyhow::anyhow!(
+            "Slice weights must be finite and non-negative"
+        ));
+    }
+
+    let total: f64 = weights.iter().sum();
+    let interval_ms = interval.as_millis() as u64;
+    Ok(weights
+        .iter()
+        .enumerate()
+        .map(|(slice, weight)| {
+            let share = if total > 0.0 {
+                weight / total
+            } else {
+                1.0 / weights.len() as f64
+            };
+            (start_ms + slice as u64 * interval_ms, quantity * share)
+        })
+        .collect())
+}
+
+/// VWAP weights from observed volume, bucketed by time of day
+///
+/// Slice `i` covers `[start_ms + i * interval, start_ms + (i + 1) * interval)`. Its weight is
+/// the volume of every sample falling in the same window of the day, on any day.
+///
+/// @param samples - Observed (timestamp_ms, volume) pairs, e.g. from `OrderFilled` events
+/// @param start_ms - Time of the first slice
+/// @param interval - Time between slices
+/// @param slices - Number of slices
+pub fn volume_weights(
+    samples: &[(u64, f64)],
+    start_ms: u64,
+    interval: Duration,
+    slices: usize,
+) -> Vec<f64> {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    let interval_ms = (interval.as_millis() as u64).max(1);
+
+    let mut weights = vec![0.0; slices];
+    for (timestamp_ms, volume) in samples {
+        let time_of_day = timestamp_ms % DAY_MS;
+        for (slice, weight) in weights.iter_mut().enumerate() {
+            let window_start = (start_ms + slice as u64 * interval_ms) % DAY_MS;
+            let offset = (time_of_day + DAY_MS - window_start) % DAY_MS;
+            if offset < interval_ms {
+                *weight += volume;
+            }
+        }
+    }
+    weights
+}
+
+/// Works a parent order through child orders on a timer
+pub struct ExecutionScheduler {
+    parent: ParentOrder,
+    algorithm: ExecutionAlgorithm,
+    book: PoolBookParams,
+    control: watch::Receiver<ExecutionState>,
+    control_open: bool,
+    progress: watch::Sender<ExecutionProgress>,
+    paused_ms: u64,
+    filled_quantity: f64,
+    children: Vec<ClientOrderId>,
+}
+
+impl ExecutionScheduler {
+    /// Creates a new ExecutionScheduler and the handle controlling it
+    ///
+    /// @param parent - The ParentOrder to work
+    /// @param algorithm - The ExecutionAlgorithm splitting it
+    /// @param book - The pool's PoolBookParams, used to round child quantities
+    pub fn new(
+        parent: ParentOrder,
+        algorithm: ExecutionAlgorithm,
+        book: PoolBookParams,
+    ) -> (Self, ExecutionHandle) {
+        let (control_sender, control) = watch::channel(ExecutionState::Running);
+        let (progress, progress_receiver) = watch::channel(ExecutionProgress {
+            state: ExecutionState::Running,
+            filled_quantity: 0.0,
+            remaining_quantity: parent.quantity,
+            children: Vec::new(),
+        });
+
+        let scheduler = Self {
+            parent,
+            algorithm,
+            book,
+            control,
+            control_open: true,
+            progress,
+            paused_ms: 0,
+            filled_quantity: 0.0,
+            children: Vec::new(),
+        };
+        let handle = ExecutionHandle {
+            control: Arc::new(control_sender),
+            progress: progress_receiver,
+        };
+        (scheduler, handle)
+    }
+
+    /// Run until the parent order is filled, every slice is sent, or the handle cancels
+    ///
+    /// Slices that round below the pool's minimum size, or fill only in part, are carried
+    /// into the next slice.
+    ///
+    /// @param executor - The ChildOrderExecutor sending child orders
+    /// @param clock - The Clock driving the schedule
+    /// @param ids - Generator for the child orders' client order IDs
+    pub async fn run(
+        &mut self,
+        executor: &mut dyn ChildOrderExecutor,
+        clock: &dyn Clock,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<ExecutionProgress> {
+        let state = match self.algorithm.clone() {
+            ExecutionAlgorithm::Iceberg {
+                display_quantity,
+                poll_interval,
+            } => {
+                self.run_iceberg(executor, clock, ids, display_quantity, poll_interval)
+                    .await?
+            }
+            algorithm => self.run_slices(executor, clock, ids, &algorithm).await?,
+        };
+        self.publish(state);
+        Ok(self.progress.borrow().clone())
+    }
+
+    async fn run_slices(
+        &mut self,
+        executor: &mut dyn ChildOrderExecutor,
+        clock: &dyn Clock,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+        algorithm: &ExecutionAlgorithm,
+    ) -> anyhow::Result<ExecutionState> {
+        let order_type = match self.parent.limit_price {
+            Some(_) => OrderType::ImmediateOrCancel,
+            None => OrderType::NoRestriction,
+        };
+
+        let mut scheduled = 0.0;
+        for (due_ms, quantity) in slice_schedule(algorithm, self.parent.quantity, clock.now_ms())? {
+            if self.wait_until(clock, due_ms).await == ExecutionState::Cancelled {
+                return Ok(ExecutionState::Cancelled);
+            }
+
+            scheduled += quantity;
+            let quantity = self.round_quantity(scheduled - self.filled_quantity);
+            if quantity < self.book.min_size || quantity <= 0.0 {
+                continue;
+            }
+
+            let child = self.child(ids, quantity, order_type)?;
+            executor.place(&child).await?;
+            let status = executor.status(child.client_order_id).await?;
+            self.filled_quantity += status.filled_quantity;
+            self.publish(ExecutionState::Running);
+        }
+        Ok(ExecutionState::Completed)
+    }
+
+    async fn run_iceberg(
+        &mut self,
+        executor: &mut dyn ChildOrderExecutor,
+        clock: &dyn Clock,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+        display_quantity: f64,
+        poll_interval: Duration,
+    ) -> anyhow::Result<ExecutionState> {
+        if self.parent.limit_price.is_none() {
+            return Err(anyhow::anyhow!("Iceberg orders need a limit price"));
+        }
+        let poll_ms = poll_interval.as_millis() as u64;
+
+        // The resting slice and the quantity of it already counted as filled
+        let mut live: Option<(ClientOrderId, f64)> = None;
+        loop {
+            if live.is_none() {
+                let quantity = self.round_quantity(display_quantity.min(self.remaining_quantity()));
+                if quantity < self.book.min_size || quantity <= 0.0 {
+                    return Ok(ExecutionState::Completed);
+                }
+                if self.wait_until(clock, clock.now_ms()).await == ExecutionState::Cancelled {
+                    return Ok(ExecutionState::Cancelled);
+                }
+
+                let child = self.child(ids, quantity, OrderType::NoRestriction)?;
+                executor.place(&child).await?;
+                live = Some((child.client_order_id, 0.0));
+            }
+
+            let state = self.wait_until(clock, clock.now_ms() + poll_ms).await;
+            if let Some((client_order_id, counted)) = live {
+                let mut status = executor.status(client_order_id).await?;
+                if state == ExecutionState::Cancelled && status.open {
+                    executor.cancel(client_order_id).await?;
+                    status = executor.status(client_order_id).await?;
+                }
+                self.filled_quantity += status.filled_quantity - counted;
+                live = status
+                    .open
+                    .then_some((client_order_id, status.filled_quantity));
+                self.publish(ExecutionState::Running);
+            }
+            if state == ExecutionState::Cancelled {
+                return Ok(ExecutionState::Cancelled);
+            }
+        }
+    }
+
+    /// Wait for `deadline_ms`, shifted by time spent paused. Returns early on cancel
+    async fn wait_until(&mut self, clock: &dyn Clock, deadline_ms: u64) -> ExecutionState {
+        loop {
+            let state = *self.control.borrow_and_update();
+            match state {
+                ExecutionState::Cancelled => return state,
+                ExecutionState::Paused => {
+                    self.publish(ExecutionState::Paused);
+                    let paused_at = clock.now_ms();
+                    if self.control.changed().await.is_err() {
+                        // Nobody is left to resume the execution.
+                        return ExecutionState::Cancelled;
+                    }
+                    self.paused_ms += clock.now_ms().saturating_sub(paused_at);
+                    self.publish(ExecutionState::Running);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let deadline_ms = deadline_ms + self.paused_ms;
+            if clock.now_ms() >= deadline_ms {
+                return state;
+            }
+            if !self.control_open {
+                clock.sleep_until(deadline_ms).await;
+                continue;
+            }
+            tokio::select! {
+                _ = clock.sleep_until(deadline_ms) => {}
+                changed = self.control.changed() => {
+                    if changed.is_err() {
+                        self.control_open = false;
+                    }
+                }
+            }
+        }
+    }
+
+    fn child(
+        &mut self,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+        quantity: f64,
+        order_type: OrderType,
+    ) -> anyhow::Result<ChildOrder> {
+        let child = ChildOrder {
+            client_order_id: ids.next_id()?,
+            pool_key: self.parent.pool_key.clone(),
+            is_bid: self.parent.is_bid,
+            quantity,
+            price: self.parent.limit_price,
+            order_type,
+            pay_with_deep: self.parent.pay_with_deep,
+        };
+        self.children.push(child.client_order_id);
+        Ok(child)
+    }
+
+    fn remaining_quantity(&self) -> f64 {
+        (self.parent.quantity - self.filled_quantity).max(0.0)
+    }
+
+    fn round_quantity(&self, quantity: f64) -> f64 {
+        let lots = (quantity.min(self.remaining_quantity()) / self.book.lot_size + 1e-9).floor();
+        (lots * self.book.lot_size * 1e9).round() / 1e9
+    }
+
+    fn publish(&self, state: ExecutionState) {
+        self.progress.send_replace(ExecutionProgress {
+            state,
+            filled_quantity: self.filled_quantity,
+            remaining_quantity: self.remaining_quantity(),
+            children: self.children.clone(),
+        });
+    }
+}
+
+/// ChildOrderExecutor tracking child orders in an OrderManager
+///
+/// Fills of resting orders are picked up from recent `OrderFilled` events.
+pub struct OmsChildExecutor<'a, 'b> {
+    oms: &'b mut OrderManager<'a>,
+    executor: &'b dyn TransactionExecutor,
+    immediate: HashSet<ClientOrderId>,
+    keys: HashMap<ClientOrderId, OrderKey>,
+}
+
+impl<'a, 'b> OmsChildExecutor<'a, 'b> {
+    /// Creates a new OmsChildExecutor
+    ///
+    /// @param oms - The OrderManager of the balance manager trading the parent order
+    /// @param executor - The TransactionExecutor submitting child orders
+    pub fn new(oms: &'b mut OrderManager<'a>, executor: &'b dyn TransactionExecutor) -> Self {
+        Self {
+            oms,
+            executor,
+            immediate: HashSet::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    fn key(&self, client_order_id: ClientOrderId) -> anyhow::Result<&OrderKey> {
+        self.keys
+            .get(&client_order_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown client order ID {}", client_order_id))
+    }
+}
+
+#[async_trait]
+impl<'a, 'b> ChildOrderExecutor for OmsChildExecutor<'a, 'b> {
+    async fn place(&mut self, order: &ChildOrder) -> anyhow::Result<()> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let key = match order.price {
+            Some(price) => {
+                self.oms
+                    .place_limit_order(
+                        &mut ptb,
+                        PlaceLimitOrderParams {
+                            pool_key: order.pool_key.clone(),
+                            balance_manager_key: String::new(),
+                            client_order_id: order.client_order_id,
+                            price,
+                            quantity: order.quantity,
+                            is_bid: order.is_bid,
+                            expiration: None,
+                            order_type: Some(order.order_type),
+                            self_matching_option: None,
+                            pay_with_deep: order.pay_with_deep,
+                        },
+                    )
+                    .await?
+            }
+            None => {
+                self.oms
+                    .place_market_order(
+                        &mut ptb,
+                        PlaceMarketOrderParams {
+                            pool_key: order.pool_key.clone(),
+                            balance_manager_key: String::new(),
+                            client_order_id: order.client_order_id,
+                            quantity: order.quantity,
+                            is_bid: order.is_bid,
+                            self_matching_option: None,
+                            pay_with_deep: order.pay_with_deep,
+                        },
+                    )
+                    .await?
+            }
+        };
+        self.keys.insert(order.client_order_id, key.clone());
+        if order.price.is_none() || order.order_type != OrderType::NoRestriction {
+            self.immediate.insert(order.client_order_id);
+        }
+
+        let response = self.executor.execute(ptb.finish()).await?;
+        self.oms.apply_transaction(&response, &[key])
+    }
+
+    async fn status(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<ChildStatus> {
+        if !self.immediate.contains(&client_order_id) {
+            self.oms.sync_fills(FILL_SYNC_EVENTS).await?;
+        }
+        let key = self.key(client_order_id)?;
+        let order = self
+            .oms
+            .order(key)
+            .ok_or_else(|| anyhow::anyhow!("Unknown client order ID {}", client_order_id))?;
+        Ok(ChildStatus {
+            filled_quantity: order.filled_quantity,
+            open: order.status.is_open() && !self.immediate.contains(&client_order_id),
+        })
+    }
+
+    async fn cancel(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<()> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        self.oms
+            .cancel_order(&mut ptb, self.key(client_order_id)?)
+            .await?;
+        let response = self.executor.execute(ptb.finish()).await?;
+        self.oms.apply_transaction(&response, &[])
+    }
+}
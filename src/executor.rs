@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use sui_sdk::rpc_types::SuiTransactionBlockResponse;
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::transaction::{ProgrammableTransaction, TransactionData};
+use sui_sdk::wallet_context::WalletContext;
+
+/// Signs and submits transactions built by the client
+#[async_trait]
+pub trait TransactionExecutor: Send + Sync {
+    /// Execute a transaction and wait for it, returning a response with effects and events
+    async fn execute(
+        &self,
+        transaction: ProgrammableTransaction,
+    ) -> anyhow::Result<SuiTransactionBlockResponse>;
+}
+
+/// TransactionExecutor signing with the active address of a Sui wallet
+pub struct WalletExecutor {
+    wallet: WalletContext,
+    sender: SuiAddress,
+    gas_budget: u64,
+}
+
+impl WalletExecutor {
+    /// Creates a new WalletExecutor
+    ///
+    /// @param wallet - The WalletContext holding the signing key
+    /// @param gas_budget - Gas budget of every transaction, in MIST
+    pub fn new(mut wallet: WalletContext, gas_budget: u64) -> anyhow::Result<Self> {
+        let sender = wallet.active_address()?;
+        Ok(Self {
+            wallet,
+            sender,
+            gas_budget,
+        })
+    }
+
+    /// The address transactions are sent from
+    pub fn sender(&self) -> SuiAddress {
+        self.sender
+    }
+}
+
+#[async_trait]
+impl TransactionExecutor for WalletExecutor {
+    async fn execute(
+        &self,
+        transaction: ProgrammableTransaction,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let gas = self
+            .wallet
+            .get_one_gas_object_owned_by_address(self.sender)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("No gas object found for {}", self.sender))?;
+        let gas_price = self.wallet.get_reference_gas_price().await?;
+
+        let data = TransactionData::new_programmable(
+            self.sender,
+            vec![gas],
+            transaction,
+            self.gas_budget,
+            gas_price,
+        );
+        let signed = self.wallet.sign_transaction(&data);
+        self.wallet.execute_transaction_may_fail(signed).await
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::{DeepBookClient, PoolDeepPrice, PoolTradeParams};
+use crate::utils::config::{DEEP_SCALAR, INPUT_FEE_MULTIPLIER};
+use crate::utils::types::{PlaceLimitOrderParams, PlaceMarketOrderParams, SwapParams};
+
+/// Whether a trade takes or provides liquidity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeRole {
+    Taker,
+    Maker,
+}
+
+/// An order or swap to plan fees for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlannedTrade {
+    pub is_bid: bool,
+    pub price: f64,
+    /// Base quantity of the trade
+    pub quantity: f64,
+    pub role: FeeRole,
+}
+
+/// The cost of paying a trade's fees in DEEP versus in the input asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeePlan {
+    /// Pay in DEEP: DEEP is the cheaper option and the available balance covers it
+    pub pay_with_deep: bool,
+    /// Whether DEEP is the cheaper option, regardless of the balance
+    pub deep_is_cheaper: bool,
+    /// DEEP charged when paying in DEEP, rounded up to DEEP's precision
+    pub deep_fee: f64,
+    /// Fee charged in the input asset otherwise
+    pub input_fee: f64,
+    /// Whether the input asset is the base asset, true for asks
+    pub input_is_base: bool,
+    /// Both fees valued in the quote asset; the DEEP fee only when a DEEP market price is known
+    pub deep_fee_value: Option<f64>,
+    pub input_fee_value: f64,
+    /// DEEP to deposit before the trade can pay in DEEP
+    pub deep_shortfall: f64,
+}
+
+impl FeePlan {
+    /// Set `pay_with_deep` on a limit order
+    pub fn apply_to_limit_order(&self, params: PlaceLimitOrderParams) -> PlaceLimitOrderParams {
+        PlaceLimitOrderParams {
+            pay_with_deep: Some(self.pay_with_deep),
+            ..params
+        }
+    }
+
+    /// Set `pay_with_deep` on a market order
+    pub fn apply_to_market_order(&self, params: PlaceMarketOrderParams) -> PlaceMarketOrderParams {
+        PlaceMarketOrderParams {
+            pay_with_deep: Some(self.pay_with_deep),
+            ..params
+        }
+    }
+
+    /// Set `deep_amount` on a swap, zero when paying in the input asset
+    pub fn apply_to_swap(&self, params: SwapParams) -> SwapParams {
+        SwapParams {
+            deep_amount: if self.pay_with_deep {
+                self.deep_fee
+            } else {
+                0.0
+            },
+            ..params
+        }
+    }
+}
+
+/// Compare paying a trade's fees in DEEP against paying them in the input asset
+///
+/// Input asset fees are charged at `INPUT_FEE_MULTIPLIER` times the DEEP rate. Without a
+/// DEEP market price the pool's own conversion is trusted and DEEP is always cheaper.
+///
+/// @param trade - The PlannedTrade to plan for
+/// @param trade_params - The pool's fees
+/// @param deep_price - The pool's DEEP conversion
+/// @param deep_balance - DEEP available to pay fees
+/// @param deep_market_price - Market price of DEEP in the quote asset, if known
+pub fn plan_fees(
+    trade: &PlannedTrade,
+    trade_params: &PoolTradeParams,
+    deep_price: &PoolDeepPrice,
+    deep_balance: f64,
+    deep_market_price: Option<f64>,
+) -> anyhow::Result<FeePlan> {
+    let rate = match trade.role {
+        FeeRole::Taker => trade_params.taker_fee,
+        FeeRole::Maker => trade_params.maker_fee,
+    };
+    let quote_quantity = trade.quantity * trade.price;
+
+    let deep = match (
+        deep_price.asset_is_base,
+        deep_price.deep_per_base,
+        deep_price.deep_per_quote,
+    ) {
+        (true, Some(deep_per_base), _) => trade.quantity * deep_per_base,
+        (false, _, Some(deep_per_quote)) => quote_quantity * deep_per_quote,
+        _ => return Err(anyhow::anyhow!("Pool has no DEEP conversion")),
+    };
+    let deep_fee = ceil_deep(deep * rate);
+
+    let input_is_base = !trade.is_bid;
+    let input_fee_value = quote_quantity * rate * INPUT_FEE_MULTIPLIER;
+    let input_fee = if input_is_base {
+        trade.quantity * rate * INPUT_FEE_MULTIPLIER
+    } else {
+        input_fee_value
+    };
+
+    let deep_fee_value = deep_market_price.map(|price| deep_fee * price);
+    let deep_is_cheaper = match deep_fee_value {
+        Some(value) => value <= input_fee_value,
+        None => true,
+    };
+    let deep_shortfall = ceil_deep((deep_fee - deep_balance).max(0.0));
+
+    Ok(FeePlan {
+        pay_with_deep: deep_is_cheaper && deep_shortfall == 0.0,
+        deep_is_cheaper,
+        deep_fee,
+        input_fee,
+        input_is_base,
+        deep_fee_value,
+        input_fee_value,
+        deep_shortfall,
+    })
+}
+
+fn ceil_deep(amount: f64) -> f64 {
+    // Trim float noise before rounding up to DEEP's precision
+    let scaled = (amount * DEEP_SCALAR as f64 * 1e3).round() / 1e3;
+    scaled.ceil() / DEEP_SCALAR as f64
+}
+
+/// Plans fees from live pool parameters and balances
+pub struct FeePlanner<'a> {
+    client: &'a DeepBookClient,
+    deep_market_price: Option<f64>,
+}
+
+impl<'a> FeePlanner<'a> {
+    /// Creates a new FeePlanner
+    ///
+    /// @param client - The DeepBookClient used to read pools and balances
+    pub fn new(client: &'a DeepBookClient) -> Self {
+        Self {
+            client,
+            deep_market_price: None,
+        }
+    }
+
+    /// Value DEEP at a market price in each pool's quote asset instead of trusting the
+    /// pool's conversion
+    ///
+    /// @param price - Market price of DEEP in the quote asset
+    pub fn with_deep_market_price(mut self, price: f64) -> Self {
+        self.deep_market_price = Some(price);
+        self
+    }
+
+    /// Plan the fees of an order paid from a balance manager
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager paying the fees
+    /// @param trade - The PlannedTrade to plan for
+    pub async fn plan_order(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+        trade: &PlannedTrade,
+    ) -> anyhow::Result<FeePlan> {
+        let (_, deep_balance) = self
+            .client
+            .check_manager_balance(manager_key, "DEEP")
+            .await?;
+        self.plan(pool_key, trade, deep_balance).await
+    }
+
+    /// Plan the fees of a swap paid from the client's wallet, priced at the mid price
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param amount - The swap's input amount
+    /// @param base_for_quote - Whether the swap sells base for quote
+    pub async fn plan_swap(
+        &self,
+        pool_key: &str,
+        amount: f64,
+        base_for_quote: bool,
+    ) -> anyhow::Result<FeePlan> {
+        let mid_price = self.client.mid_price(pool_key).await?;
+        if mid_price <= 0.0 {
+            return Err(anyhow::anyhow!("No mid price for {}", pool_key));
+        }
+
+        let deep_coin = self.client.config().get_coin("DEEP")?;
+        let balance = self
+            .client
+            .sui_client()
+            .coin_read_api()
+            .get_balance(self.client.address(), Some(deep_coin.type_name.clone()))
+            .await?;
+        let deep_balance = balance.total_balance as f64 / DEEP_SCALAR as f64;
+
+        let trade = PlannedTrade {
+            is_bid: !base_for_quote,
+            price: mid_price,
+            quantity: if base_for_quote {
+                amount
+            } else {
+                amount / mid_price
+            },
+            role: FeeRole::Taker,
+        };
+        self.plan(pool_key, &trade, deep_balance).await
+    }
+
+    async fn plan(
+        &self,
+        pool_key: &str,
+        trade: &PlannedTrade,
+        deep_balance: f64,
+    ) -> anyhow::Result<FeePlan> {
+        let trade_params = self.client.pool_trade_params(pool_key).await?;
+        let deep_price = self.client.get_pool_deep_price(pool_key).await?;
+        plan_fees(
+            trade,
+            &trade_params,
+            &deep_price,
+            deep_balance,
+            self.deep_market_price,
+        )
+    }
+}
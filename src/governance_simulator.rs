@@ -0,0 +1,338 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::types::base_types::ObjectID;
+
+use crate::client::{
+    AccountStake, DeepBookClient, GovernanceProposal, GovernanceState, PoolTradeParams,
+};
+use crate::utils::config::{DEEP_SCALAR, FLOAT_SCALAR};
+use crate::utils::types::ProposalParams;
+
+/// Stake above this amount, in raw DEEP units, counts with square-root voting power
+pub const VOTING_POWER_THRESHOLD: u64 = 100_000 * DEEP_SCALAR;
+
+/// A governance action taken by the simulated balance manager
+#[derive(Debug, Clone)]
+pub enum GovernanceAction {
+    /// Stake additional DEEP. New stake becomes active next epoch
+    Stake { amount: f64 },
+    /// Withdraw all active and inactive stake, removing any vote
+    Unstake,
+    /// Vote for a proposal with the active stake, moving any previous vote
+    Vote { proposal_id: ObjectID },
+    /// Submit a proposal, keyed by the balance manager ID, and vote for it.
+    /// The pool and balance manager keys in `params` are ignored
+    SubmitProposal { params: ProposalParams },
+}
+
+/// Outcome of the current epoch's vote once the simulated actions are applied
+#[derive(Debug)]
+pub struct GovernanceOutcome {
+    /// The proposal whose parameters take effect next epoch, if any reached quorum
+    pub winning_proposal: Option<ObjectID>,
+    pub quorum_reached: bool,
+    pub quorum: f64,
+    pub proposals: Vec<GovernanceProposal>,
+    /// Trade parameters that take effect next epoch
+    pub next_trade_params: PoolTradeParams,
+    /// Quorum of the next epoch, half of the total voting power after stake changes
+    pub next_quorum: f64,
+    pub active_stake: f64,
+    pub inactive_stake: f64,
+    pub voted_proposal: Option<ObjectID>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RawTradeParams {
+    taker_fee: u64,
+    maker_fee: u64,
+    stake_required: u64,
+}
+
+impl RawTradeParams {
+    fn from_params(params: &PoolTradeParams) -> Self {
+        let (taker_fee, maker_fee, stake_required) =
+            ProposalParams::scale(params.taker_fee, params.maker_fee, params.stake_required);
+        Self {
+            taker_fee,
+            maker_fee,
+            stake_required,
+        }
+    }
+
+    fn normalized(&self) -> PoolTradeParams {
+        PoolTradeParams {
+            taker_fee: (self.taker_fee as f64 / FLOAT_SCALAR as f64 * 1e9).round() / 1e9,
+            maker_fee: (self.maker_fee as f64 / FLOAT_SCALAR as f64 * 1e9).round() / 1e9,
+            stake_required: deep_from_raw(self.stake_required),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RawProposal {
+    id: ObjectID,
+    params: RawTradeParams,
+    votes: u64,
+}
+
+/// Replays stake, vote and proposal actions against a pool's governance state
+///
+/// Mirrors DeepBook's on-chain integer math: voting power is linear up to
+/// `VOTING_POWER_THRESHOLD` and square-root above it, quorum is fixed for the epoch,
+/// and the last proposal to cross quorum sets the next epoch's trade parameters.
+#[derive(Debug, Clone)]
+pub struct GovernanceSimulator {
+    manager_id: ObjectID,
+    quorum: u64,
+    voting_power: u64,
+    trade_params: RawTradeParams,
+    next_trade_params: RawTradeParams,
+    proposals: Vec<RawProposal>,
+    winning_proposal: Option<ObjectID>,
+    active_stake: u64,
+    inactive_stake: u64,
+    created_proposal: bool,
+    voted_proposal: Option<ObjectID>,
+}
+
+impl GovernanceSimulator {
+    /// Creates a new GovernanceSimulator from state read on-chain
+    ///
+    /// A governance state older than the account's epoch is rolled forward to it first, so
+    /// proposals the chain has already cleared cannot be voted on.
+    ///
+    /// @param manager_id - The balance manager taking the simulated actions
+    /// @param state - The pool's GovernanceState
+    /// @param account - The balance manager's AccountStake, None if it has no account
+    pub fn new(
+        manager_id: ObjectID,
+        state: &GovernanceState,
+        account: Option<&AccountStake>,
+    ) -> Self {
+        let mut state = state.clone();
+        if let Some(account) = account {
+            state.roll_forward(account.epoch);
+        }
+        let quorum = deep_to_raw(state.quorum);
+        let next_trade_params = RawTradeParams::from_params(&state.next_trade_params);
+        let proposals: Vec<RawProposal> = state
+            .proposals
+            .iter()
+            .map(|proposal| {
+                let (taker_fee, maker_fee, stake_required) = ProposalParams::scale(
+                    proposal.taker_fee,
+                    proposal.maker_fee,
+                    proposal.stake_required,
+                );
+                RawProposal {
+                    id: proposal.proposal_id,
+                    params: RawTradeParams {
+                        taker_fee,
+                        maker_fee,
+                        stake_required,
+                    },
+                    votes: deep_to_raw(proposal.votes),
+                }
+            })
+            .collect();
+
+        let winning_proposal = proposals
+            .iter()
+            .filter(|proposal| proposal.votes >= quorum && proposal.params == next_trade_params)
+            .max_by_key(|proposal| proposal.votes)
+            .map(|proposal| proposal.id);
+
+        Self {
+            manager_id,
+            quorum,
+            voting_power: deep_to_raw(state.voting_power),
+            trade_params: RawTradeParams::from_params(&state.trade_params),
+            next_trade_params,
+            proposals,
+            winning_proposal,
+            active_stake: account.map_or(0, |account| deep_to_raw(account.active_stake)),
+            inactive_stake: account.map_or(0, |account| deep_to_raw(account.inactive_stake)),
+            created_proposal: account.is_some_and(|account| account.created_proposal),
+            voted_proposal: account.and_then(|account| account.voted_proposal),
+        }
+    }
+
+    /// Load a simulator for a pool and balance manager
+    ///
+    /// Governance state and stake are both rolled forward to the chain epoch.
+    ///
+    /// @param client - The DeepBookClient used to read governance state
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn load(
+        client: &DeepBookClient,
+        pool_key: &str,
+        manager_key: &str,
+    ) -> anyhow::Result<Self> {
+        let manager = client.config().balance_manager_ref(manager_key)?;
+        let state = client.governance_state(pool_key).await?;
+        let account = client.account_stake(pool_key, manager_key).await?;
+        Ok(Self::new(manager.id, &state, account.as_ref()))
+    }
+
+    /// Apply an action, failing where the on-chain call would abort
+    ///
+    /// @param action - The GovernanceAction to simulate
+    pub fn apply(&mut self, action: &GovernanceAction) -> anyhow::Result<()> {
+        match action {
+            GovernanceAction::Stake { amount } => {
+                let amount = deep_to_raw(*amount);
+                let stake_before = self.active_stake + self.inactive_stake;
+                self.inactive_stake += amount;
+                self.adjust_voting_power(stake_before, stake_before + amount);
+            }
+            GovernanceAction::Unstake => {
+                let stake_before = self.active_stake + self.inactive_stake;
+                if stake_before == 0 {
+                    return Err(anyhow::anyhow!("No stake to withdraw"));
+                }
+                self.adjust_voting_power(stake_before, 0);
+                self.adjust_vote(self.voted_proposal, None, self.active_stake);
+                self.active_stake = 0;
+                self.inactive_stake = 0;
+                self.voted_proposal = None;
+            }
+            GovernanceAction::Vote { proposal_id } => {
+                self.ensure_stake_required()?;
+                if !self.proposals.iter().any(|p| p.id == *proposal_id) {
+                    return Err(anyhow::anyhow!("Proposal {} not found", proposal_id));
+                }
+                self.adjust_vote(self.voted_proposal, Some(*proposal_id), self.active_stake);
+                self.voted_proposal = Some(*proposal_id);
+            }
+            GovernanceAction::SubmitProposal { params } => {
+                self.ensure_stake_required()?;
+                if self.created_proposal {
+                    return Err(anyhow::anyhow!("Proposal already created this epoch"));
+                }
+                let (taker_fee, maker_fee, stake_required) = params.scaled();
+                self.proposals.push(RawProposal {
+                    id: self.manager_id,
+                    params: RawTradeParams {
+                        taker_fee,
+                        maker_fee,
+                        stake_required,
+                    },
+                    votes: 0,
+                });
+                self.created_proposal = true;
+                self.apply(&GovernanceAction::Vote {
+                    proposal_id: self.manager_id,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a sequence of actions in order
+    ///
+    /// @param actions - The GovernanceActions to simulate
+    pub fn apply_all(&mut self, actions: &[GovernanceAction]) -> anyhow::Result<()> {
+        actions.iter().try_for_each(|action| self.apply(action))
+    }
+
+    /// The outcome of the vote given the actions applied so far
+    pub fn outcome(&self) -> GovernanceOutcome {
+        GovernanceOutcome {
+            winning_proposal: self.winning_proposal,
+            quorum_reached: self.winning_proposal.is_some(),
+            quorum: deep_from_raw(self.quorum),
+            proposals: self
+                .proposals
+                .iter()
+                .map(|proposal| {
+                    let params = proposal.params.normalized();
+                    GovernanceProposal {
+                        proposal_id: proposal.id,
+                        taker_fee: params.taker_fee,
+                        maker_fee: params.maker_fee,
+                        stake_required: params.stake_required,
+                        votes: deep_from_raw(proposal.votes),
+                        quorum: deep_from_raw(self.quorum),
+                        quorum_reached: proposal.votes >= self.quorum,
+                    }
+                })
+                .collect(),
+            next_trade_params: self.next_trade_params.normalized(),
+            next_quorum: deep_from_raw(self.voting_power / 2),
+            active_stake: deep_from_raw(self.active_stake),
+            inactive_stake: deep_from_raw(self.inactive_stake),
+            voted_proposal: self.voted_proposal,
+        }
+    }
+
+    fn ensure_stake_required(&self) -> anyhow::Result<()> {
+        if self.active_stake < self.trade_params.stake_required {
+            return Err(anyhow::anyhow!(
+                "Active stake {} is below the required {}",
+                deep_from_raw(self.active_stake),
+                deep_from_raw(self.trade_params.stake_required)
+            ));
+        }
+        Ok(())
+    }
+
+    fn adjust_voting_power(&mut self, stake_before: u64, stake_after: u64) {
+        self.voting_power = self.voting_power + stake_to_voting_power(stake_after)
+            - stake_to_voting_power(stake_before).min(self.voting_power);
+    }
+
+    fn adjust_vote(&mut self, from: Option<ObjectID>, to: Option<ObjectID>, stake: u64) {
+        let votes = stake_to_voting_power(stake);
+
+        if let Some(proposal) = from.and_then(|id| self.proposals.iter_mut().find(|p| p.id == id)) {
+            let was_winning = proposal.votes >= self.quorum;
+            proposal.votes = proposal.votes.saturating_sub(votes);
+            if was_winning && proposal.votes < self.quorum {
+                self.next_trade_params = self.trade_params;
+                self.winning_proposal = None;
+            }
+        }
+
+        if let Some(proposal) = to.and_then(|id| self.proposals.iter_mut().find(|p| p.id == id)) {
+            proposal.votes += votes;
+            if proposal.votes >= self.quorum {
+                self.next_trade_params = proposal.params;
+                self.winning_proposal = Some(proposal.id);
+            }
+        }
+    }
+}
+
+/// Voting power of a stake, in raw DEEP units
+///
+/// @param stake - The stake in raw DEEP units
+pub fn stake_to_voting_power(stake: u64) -> u64 {
+    if stake <= VOTING_POWER_THRESHOLD {
+        return stake;
+    }
+    VOTING_POWER_THRESHOLD + deep_sqrt(stake) - deep_sqrt(VOTING_POWER_THRESHOLD)
+}
+
+/// Square root of a raw DEEP amount, keeping DEEP precision
+fn deep_sqrt(amount: u64) -> u64 {
+    let value = amount as u128 * DEEP_SCALAR as u128;
+    let mut root = (value as f64).sqrt() as u128;
+    while root * root > value {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+    root as u64
+}
+
+fn deep_to_raw(amount: f64) -> u64 {
+    (amount * DEEP_SCALAR as f64).round() as u64
+}
+
+fn deep_from_raw(amount: u64) -> f64 {
+    (amount as f64 / DEEP_SCALAR as f64 * 1e9).round() / 1e9
+}
@@ -0,0 +1,475 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, VecDeque};
+
+use sui_sdk::rpc_types::{EventFilter, SuiEvent};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::types::parse_sui_struct_tag;
+
+use crate::client::DeepBookClient;
+use crate::utils::config::{DeepBookConfig, DEEP_SCALAR};
+use crate::utils::json::{json_bool, json_field, json_object_id, json_u128, json_u64};
+use crate::utils::types::{ClientOrderId, OrderId, PoolRef};
+
+/// Number of events requested per page
+const EVENT_PAGE_SIZE: usize = 50;
+
+/// Whether the balance manager provided or took liquidity in a fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeRole {
+    Maker,
+    Taker,
+}
+
+/// The asset a fill's fee was paid in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeAsset {
+    Base,
+    Quote,
+    Deep,
+}
+
+/// A single fill of one of the balance manager's orders, normalized by the pool's scalars
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub pool_key: String,
+    pub pool_id: ObjectID,
+    pub order_id: OrderId,
+    pub client_order_id: ClientOrderId,
+    pub is_bid: bool,
+    pub role: TradeRole,
+    pub price: f64,
+    pub base_quantity: f64,
+    pub quote_quantity: f64,
+    pub fee: f64,
+    pub fee_is_deep: bool,
+    pub fee_asset: FeeAsset,
+    pub timestamp_ms: u64,
+    pub tx_digest: String,
+}
+
+/// Fills read by `TradeHistory::fills`
+#[derive(Debug, Clone, Default)]
+pub struct FillHistory {
+    /// Fills, oldest first
+    pub fills: Vec<Fill>,
+    /// Whether `max_events` was reached before the start of the history, so older fills
+    /// are missing. Events of every pool and manager count towards `max_events`
+    pub truncated: bool,
+}
+
+/// A fill between any two orders of a pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub price: f64,
+    pub base_quantity: f64,
+    pub quote_quantity: f64,
+    pub taker_is_bid: bool,
+    pub timestamp_ms: u64,
+}
+
+/// How realized PnL matches sells against earlier buys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostBasisMethod {
+    Fifo,
+    AverageCost,
+}
+
+/// Realized and unrealized PnL of a pool position, in the quote asset
+#[derive(Debug, Clone, Default)]
+pub struct PoolPnl {
+    /// Net base position, negative when short
+    pub position: f64,
+    /// Average entry price of the open position
+    pub average_entry_price: f64,
+    /// PnL of closed quantity before fees
+    pub realized: f64,
+    /// PnL of the open position at `mark_price`, None without a mark price
+    pub unrealized: Option<f64>,
+    pub fees_base: f64,
+    pub fees_quote: f64,
+    pub fees_deep: f64,
+}
+
+/// Compute PnL from fills in chronological order
+///
+/// Fees are reported per asset and are not deducted from realized PnL.
+///
+/// @param fills - Fills of a single pool, oldest first
+/// @param method - The CostBasisMethod used to match closing quantity
+/// @param mark_price - Price used to value the open position
+pub fn compute_pnl(fills: &[Fill], method: CostBasisMethod, mark_price: Option<f64>) -> PoolPnl {
+    let mut pnl = PoolPnl::default();
+    // Open lots as (signed base quantity, entry price)
+    let mut lots: VecDeque<(f64, f64)> = VecDeque::new();
+
+    for fill in fills {
+        match fill.fee_asset {
+            FeeAsset::Base => pnl.fees_base += fill.fee,
+            FeeAsset::Quote => pnl.fees_quote += fill.fee,
+            FeeAsset::Deep => pnl.fees_deep += fill.fee,
+        }
+
+        let mut quantity = if fill.is_bid {
+            fill.base_quantity
+        } else {
+            -fill.base_quantity
+        };
+
+        match method {
+            CostBasisMethod::Fifo => {
+                while quantity != 0.0 {
+                    let Some(lot) = lots.front_mut() else {
+                        break;
+                    };
+                    if lot.0.signum() == quantity.signum() {
+                        break;
+                    }
+                    let matched = lot.0.abs().min(quantity.abs());
+                    pnl.realized += matched * (fill.price - lot.1) * lot.0.signum();
+                    lot.0 += matched * quantity.signum();
+                    quantity -= matched * quantity.signum();
+                    if lot.0.abs() <= f64::EPSILON {
+                        lots.pop_front();
+                    }
+                }
+                if quantity.abs() > f64::EPSILON {
+                    lots.push_back((quantity, fill.price));
+                }
+            }
+            CostBasisMethod::AverageCost => {
+                let (position, entry) = lots.pop_front().unwrap_or((0.0, 0.0));
+                let next = if position == 0.0 || position.signum() == quantity.signum() {
+                    let size = position + quantity;
+                    (size, (position * entry + quantity * fill.price) / size)
+                } else {
+                    let matched = position.abs().min(quantity.abs());
+                    pnl.realized += matched * (fill.price - entry) * position.signum();
+                    let size = position + quantity;
+                    if size.signum() == position.signum() {
+                        (size, entry)
+                    } else {
+                        (size, fill.price)
+                    }
+                };
+                if next.0.abs() > f64::EPSILON {
+                    lots.push_back(next);
+                }
+            }
+        }
+    }
+
+    pnl.position = lots.iter().map(|lot| lot.0).sum();
+    if pnl.position != 0.0 {
+        pnl.average_entry_price = lots.iter().map(|lot| lot.0 * lot.1).sum::<f64>() / pnl.position;
+    }
+    pnl.unrealized = mark_price.map(|mark| {
+        lots.iter()
+            .map(|(quantity, entry)| quantity * (mark - entry))
+            .sum()
+    });
+    pnl
+}
+
+/// Reads past fills of a balance manager from DeepBook `OrderFilled` events
+///
+/// The event API cannot filter fills by balance manager, so every query pages the
+/// package-wide `OrderFilled` stream. On a busy network a bounded scan may only reach
+/// back a short time; pass `since_ms` and a `max_events` sized for the traffic, and check
+/// `FillHistory::truncated`.
+pub struct TradeHistory<'a> {
+    client: &'a DeepBookClient,
+}
+
+impl<'a> TradeHistory<'a> {
+    /// Creates a new TradeHistory
+    ///
+    /// @param client - The DeepBookClient used to query events
+    pub fn new(client: &'a DeepBookClient) -> Self {
+        Self { client }
+    }
+
+    /// Get the most recent fills of a balance manager, oldest first
+    ///
+    /// Events are scanned newest first across all DeepBook pools and managers, and
+    /// filtered by `balance_manager_id` on the client. A sender filter cannot narrow the
+    /// query, since maker fills are emitted by other users' transactions. At most
+    /// `max_events` events are read, including fills of other managers, so a quiet
+    /// manager on a busy network may find few or none of its fills. Scanning also stops at
+    /// the first event older than `since_ms`. The history is marked truncated when
+    /// `max_events` is reached first.
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    /// @param pool_key - Restrict to a single pool, or None for all configured pools
+    /// @param since_ms - Only read fills at or after this time, or None for all fills
+    /// @param max_events - Maximum number of events to scan
+    pub async fn fills(
+        &self,
+        manager_key: &str,
+        pool_key: Option<&str>,
+        since_ms: Option<u64>,
+        max_events: usize,
+    ) -> anyhow::Result<FillHistory> {
+        let config = self.client.config();
+        let manager = config.balance_manager_ref(manager_key)?;
+        let pools = pool_index(config, pool_key)?;
+
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}::order_info::OrderFilled",
+            config.deepbook_package_id()
+        ))?;
+
+        let mut history = FillHistory::default();
+        let mut cursor = None;
+        let mut scanned = 0;
+        'scan: loop {
+            if scanned >= max_events {
+                history.truncated = true;
+                break;
+            }
+            let page = self
+                .client
+                .sui_client()
+                .event_api()
+                .query_events(
+                    EventFilter::MoveEventType(event_type.clone()),
+                    cursor,
+                    Some(EVENT_PAGE_SIZE.min(max_events - scanned)),
+                    true,
+                )
+                .await?;
+            scanned += page.data.len();
+
+            for event in &page.data {
+                if since_ms.is_some_and(|since_ms| event.timestamp_ms.unwrap_or(0) < since_ms) {
+                    break 'scan;
+                }
+                // Reversed with the rest below, keeping a self-trade's maker fill first
+                history
+                    .fills
+                    .extend(parse_fills(event, manager.id, &pools)?.into_iter().rev());
+            }
+
+            if !page.has_next_page || page.data.is_empty() {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        history.fills.reverse();
+        Ok(history)
+    }
+
+    /// Read the latest trades of a pool, newest first
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param limit - Maximum number of trades returned
+    /// @param max_events - Maximum number of events to scan
+    pub async fn recent_trades(
+        &self,
+        pool_key: &str,
+        limit: usize,
+        max_events: usize,
+    ) -> anyhow::Result<Vec<Trade>> {
+        let config = self.client.config();
+        let pool = config.pool_ref(pool_key)?;
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}::order_info::OrderFilled",
+            config.deepbook_package_id()
+        ))?;
+
+        let mut trades = Vec::new();
+        let mut cursor = None;
+        let mut scanned = 0;
+        while scanned < max_events && trades.len() < limit {
+            let page = self
+                .client
+                .sui_client()
+                .event_api()
+                .query_events(
+                    EventFilter::MoveEventType(event_type.clone()),
+                    cursor,
+                    Some(EVENT_PAGE_SIZE.min(max_events - scanned)),
+                    true,
+                )
+                .await?;
+            scanned += page.data.len();
+
+            for event in &page.data {
+                let json = &event.parsed_json;
+                if json_object_id(json_field(json, &["pool_id"])?)? != pool.pool_id {
+                    continue;
+                }
+                trades.push(Trade {
+                    price: pool.price_from_raw(json_u64(json_field(json, &["price"])?)?),
+                    base_quantity: pool
+                        .base_from_raw(json_u64(json_field(json, &["base_quantity"])?)?),
+                    quote_quantity: pool
+                        .quote_from_raw(json_u64(json_field(json, &["quote_quantity"])?)?),
+                    taker_is_bid: json_bool(json_field(json, &["taker_is_bid"])?)?,
+                    timestamp_ms: json_u64(json_field(json, &["timestamp"])?)?,
+                });
+                if trades.len() == limit {
+                    break;
+                }
+            }
+
+            if !page.has_next_page || page.data.is_empty() {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        Ok(trades)
+    }
+
+    /// Compute PnL for a balance manager in a pool, marking the open position at the mid price
+    ///
+    /// Fails when `max_events` is reached before the start of the fill history, since the
+    /// position would be missing its oldest fills.
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    /// @param pool_key - Key of the pool
+    /// @param method - The CostBasisMethod used to match closing quantity
+    /// @param max_events - Maximum number of events to scan
+    pub async fn pnl(
+        &self,
+        manager_key: &str,
+        pool_key: &str,
+        method: CostBasisMethod,
+        max_events: usize,
+    ) -> anyhow::Result<PoolPnl> {
+        let history = self
+            .fills(manager_key, Some(pool_key), None, max_events)
+            .await?;
+        if history.truncated {
+            return Err(anyhow::anyhow!(
+                "Fill history of {} in {} exceeds {} events",
+                manager_key,
+                pool_key,
+                max_events
+            ));
+        }
+        let mark_price = self.client.mid_price(pool_key).await.ok();
+        Ok(compute_pnl(&history.fills, method, mark_price))
+    }
+}
+
+/// Parse the fills of a balance manager from an `OrderFilled` event
+///
+/// A self-trade, where the manager is both maker and taker, yields the maker fill followed
+/// by the taker fill.
+///
+/// @param config - The DeepBookConfig whose pools are recognized
+/// @param manager_id - Object ID of the BalanceManager
+/// @param event - The `OrderFilled` event
+pub fn fills_from_event(
+    config: &DeepBookConfig,
+    manager_id: ObjectID,
+    event: &SuiEvent,
+) -> anyhow::Result<Vec<Fill>> {
+    parse_fills(event, manager_id, &pool_index(config, None)?)
+}
+
+fn pool_index(
+    config: &DeepBookConfig,
+    pool_key: Option<&str>,
+) -> anyhow::Result<HashMap<ObjectID, (String, PoolRef)>> {
+    let mut pools = HashMap::new();
+    for key in config.pools().keys() {
+        if pool_key.is_some_and(|pool_key| pool_key != *key) {
+            continue;
+        }
+        let pool = config.pool_ref(key)?;
+        pools.insert(pool.pool_id, (key.to_string(), pool));
+    }
+    if let Some(pool_key) = pool_key {
+        if pools.is_empty() {
+            return Err(anyhow::anyhow!("Pool with key {} not found.", pool_key));
+        }
+    }
+    Ok(pools)
+}
+
+fn parse_fills(
+    event: &SuiEvent,
+    manager_id: ObjectID,
+    pools: &HashMap<ObjectID, (String, PoolRef)>,
+) -> anyhow::Result<Vec<Fill>> {
+    let json = &event.parsed_json;
+    let (pool_key, pool) = match pools.get(&json_object_id(json_field(json, &["pool_id"])?)?) {
+        Some(pool) => pool,
+        None => return Ok(Vec::new()),
+    };
+
+    let taker_is_bid = json_bool(json_field(json, &["taker_is_bid"])?)?;
+    let mut fills = Vec::new();
+    if json_object_id(json_field(json, &["maker_balance_manager_id"])?)? == manager_id {
+        fills.push(parse_side(
+            event,
+            pool_key,
+            pool,
+            TradeRole::Maker,
+            !taker_is_bid,
+        )?);
+    }
+    if json_object_id(json_field(json, &["taker_balance_manager_id"])?)? == manager_id {
+        fills.push(parse_side(
+            event,
+            pool_key,
+            pool,
+            TradeRole::Taker,
+            taker_is_bid,
+        )?);
+    }
+    Ok(fills)
+}
+
+fn parse_side(
+    event: &SuiEvent,
+    pool_key: &str,
+    pool: &PoolRef,
+    role: TradeRole,
+    is_bid: bool,
+) -> anyhow::Result<Fill> {
+    let json = &event.parsed_json;
+    let prefix = match role {
+        TradeRole::Maker => "maker",
+        TradeRole::Taker => "taker",
+    };
+
+    let fee_is_deep = json_bool(json_field(
+        json,
+        &[format!("{}_fee_is_deep", prefix).as_str()],
+    )?)?;
+    let raw_fee = json_u64(json_field(json, &[format!("{}_fee", prefix).as_str()])?)?;
+    // Fees not paid in DEEP come out of the asset the order gives up.
+    let (fee_asset, fee) = match (fee_is_deep, is_bid) {
+        (true, _) => (FeeAsset::Deep, raw_fee as f64 / DEEP_SCALAR as f64),
+        (false, true) => (FeeAsset::Quote, pool.quote_from_raw(raw_fee)),
+        (false, false) => (FeeAsset::Base, pool.base_from_raw(raw_fee)),
+    };
+
+    Ok(Fill {
+        pool_key: pool_key.to_string(),
+        pool_id: pool.pool_id,
+        order_id: OrderId(json_u128(json_field(
+            json,
+            &[format!("{}_order_id", prefix).as_str()],
+        )?)?),
+        client_order_id: ClientOrderId(json_u64(json_field(
+            json,
+            &[format!("{}_client_order_id", prefix).as_str()],
+        )?)?),
+        is_bid,
+        role,
+        price: pool.price_from_raw(json_u64(json_field(json, &["price"])?)?),
+        base_quantity: pool.base_from_raw(json_u64(json_field(json, &["base_quantity"])?)?),
+        quote_quantity: pool.quote_from_raw(json_u64(json_field(json, &["quote_quantity"])?)?),
+        fee,
+        fee_is_deep,
+        fee_asset,
+        timestamp_ms: json_u64(json_field(json, &["timestamp"])?)?,
+        tx_digest: event.id.tx_digest.to_string(),
+    })
+}
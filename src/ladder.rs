@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crate::client::{Level2TicksFromMid, NormalizedOrder};
+
+/// One price level of a depth ladder
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LadderRow {
+    pub price: f64,
+    pub bid_quantity: f64,
+    pub ask_quantity: f64,
+    /// Remaining quantity of the balance manager's own bids at this price
+    pub own_bid_quantity: f64,
+    /// Remaining quantity of the balance manager's own asks at this price
+    pub own_ask_quantity: f64,
+}
+
+impl LadderRow {
+    /// Whether the balance manager has an order at this price
+    pub fn has_own_order(&self) -> bool {
+        self.own_bid_quantity > 0.0 || self.own_ask_quantity > 0.0
+    }
+}
+
+/// Merge book depth and the balance manager's open orders into one row per price,
+/// highest price first
+///
+/// Prices are matched on the tick grid, so float noise does not split a level.
+///
+/// @param book - Depth around the mid price
+/// @param own_orders - The balance manager's open orders
+/// @param tick_size - The pool's tick size
+pub fn build_ladder(
+    book: &Level2TicksFromMid,
+    own_orders: &[NormalizedOrder],
+    tick_size: f64,
+) -> Vec<LadderRow> {
+    let tick_size = if tick_size > 0.0 { tick_size } else { 1e-9 };
+    let mut rows: BTreeMap<i64, LadderRow> = BTreeMap::new();
+
+    for (price, quantity) in book.bid_prices.iter().zip(&book.bid_quantities) {
+        level(&mut rows, *price, tick_size).bid_quantity += quantity;
+    }
+    for (price, quantity) in book.ask_prices.iter().zip(&book.ask_quantities) {
+        level(&mut rows, *price, tick_size).ask_quantity += quantity;
+    }
+    for order in own_orders {
+        let row = level(&mut rows, order.normalized_price, tick_size);
+        if order.is_bid {
+            row.own_bid_quantity += order.remaining_quantity;
+        } else {
+            row.own_ask_quantity += order.remaining_quantity;
+        }
+    }
+
+    rows.into_values().rev().collect()
+}
+
+fn level(rows: &mut BTreeMap<i64, LadderRow>, price: f64, tick_size: f64) -> &mut LadderRow {
+    let row = rows.entry((price / tick_size).round() as i64).or_default();
+    row.price = price;
+    row
+}
+
+/// Best bid, best ask and their difference, None when either side is empty
+///
+/// @param book - Depth around the mid price
+pub fn spread(book: &Level2TicksFromMid) -> Option<(f64, f64, f64)> {
+    let best_bid = book.bid_prices.iter().copied().reduce(f64::max)?;
+    let best_ask = book.ask_prices.iter().copied().reduce(f64::min)?;
+    Some((
+        best_bid,
+        best_ask,
+        ((best_ask - best_bid) * 1e9).round() / 1e9,
+    ))
+}
@@ -7,12 +7,29 @@ use sui_sdk::rpc_types::SuiObjectData;
 use sui_sdk::rpc_types::SuiObjectDataOptions;
 use sui_sdk::rpc_types::SuiTypeTag;
 use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::dynamic_field::DynamicFieldName;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_sdk::types::transaction::ObjectArg;
 use sui_sdk::types::transaction::TransactionKind;
 use sui_sdk::{types::base_types::ObjectID, SuiClient};
 
+pub mod arbitrage;
+pub mod cache;
 pub mod client;
+pub mod conditional;
+pub mod deep_price_keeper;
+pub mod execution;
+pub mod executor;
+pub mod fees;
+pub mod governance_simulator;
+pub mod history;
+pub mod ladder;
+pub mod market_maker;
+pub mod metrics;
+pub mod oms;
+pub mod portfolio;
+pub mod requote;
+pub mod sweeper;
 pub mod transactions;
 pub mod utils;
 
@@ -31,6 +48,17 @@ pub trait DataReader {
         amount: u64,
     ) -> anyhow::Result<Vec<Coin>>;
     async fn get_object(&self, object_id: ObjectID) -> anyhow::Result<SuiObjectData>;
+    /// Read a dynamic field of an object, None if the field does not exist
+    ///
+    /// Unsupported unless overridden, so existing readers keep compiling.
+    async fn get_dynamic_field_object(
+        &self,
+        parent_id: ObjectID,
+        name: DynamicFieldName,
+    ) -> anyhow::Result<Option<SuiObjectData>> {
+        let _ = (parent_id, name);
+        Err(anyhow::anyhow!("get_dynamic_field_object is not supported"))
+    }
     async fn coin_object(&self, coin: Coin) -> anyhow::Result<ObjectArg>;
     async fn share_object(&self, object_id: ObjectID) -> anyhow::Result<ObjectArg>;
     async fn share_object_mutable(&self, object_id: ObjectID) -> anyhow::Result<ObjectArg>;
@@ -39,6 +67,19 @@ pub trait DataReader {
         sender: SuiAddress,
         ptb: ProgrammableTransactionBuilder,
     ) -> anyhow::Result<Vec<(Vec<u8>, SuiTypeTag)>>;
+    /// Dev-inspect a PTB, returning the return values of every command
+    ///
+    /// Unsupported unless overridden, so existing readers keep compiling.
+    async fn dev_inspect_transaction_results(
+        &self,
+        sender: SuiAddress,
+        ptb: ProgrammableTransactionBuilder,
+    ) -> anyhow::Result<Vec<Vec<(Vec<u8>, SuiTypeTag)>>> {
+        let _ = (sender, ptb);
+        Err(anyhow::anyhow!(
+            "dev_inspect_transaction_results is not supported"
+        ))
+    }
 }
 
 #[async_trait]
@@ -77,6 +118,18 @@ impl DataReader for SuiClient {
             .ok_or(anyhow::anyhow!("Object {} not found", object_id))
     }
 
+    async fn get_dynamic_field_object(
+        &self,
+        parent_id: ObjectID,
+        name: DynamicFieldName,
+    ) -> anyhow::Result<Option<SuiObjectData>> {
+        Ok(self
+            .read_api()
+            .get_dynamic_field_object(parent_id, name)
+            .await?
+            .data)
+    }
+
     async fn coin_object(&self, coin: Coin) -> anyhow::Result<ObjectArg> {
         Ok(ObjectArg::ImmOrOwnedObject((
             coin.coin_object_id,
@@ -127,4 +180,31 @@ impl DataReader for SuiClient {
             .return_values
             .clone())
     }
+
+    async fn dev_inspect_transaction_results(
+        &self,
+        sender: SuiAddress,
+        ptb: ProgrammableTransactionBuilder,
+    ) -> anyhow::Result<Vec<Vec<(Vec<u8>, SuiTypeTag)>>> {
+        let builder = ptb.finish();
+        let dry_run_response = self
+            .read_api()
+            .dev_inspect_transaction_block(
+                sender,
+                TransactionKind::ProgrammableTransaction(builder),
+                None,
+                None,
+                None,
+            )
+            .await?;
+        if let Some(error) = dry_run_response.error {
+            return Err(anyhow::anyhow!(error));
+        }
+        Ok(dry_run_response
+            .results
+            .ok_or_else(|| anyhow::anyhow!("Failed to get results"))?
+            .into_iter()
+            .map(|result| result.return_values)
+            .collect())
+    }
 }
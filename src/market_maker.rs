@@ -0,0 +1,322 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::types::transaction::ProgrammableTransaction;
+
+use crate::client::{DeepBookClient, PoolBookParams, PoolDeepPrice, PoolTradeParams};
+use crate::fees::{plan_fees, FeeRole, PlannedTrade};
+use crate::requote::{RequoteOptions, Requoter, TargetOrder};
+use crate::utils::client_order_id::ClientOrderIdGenerator;
+use crate::utils::types::OrderType;
+
+/// Spread, size and inventory targets of a market maker
+#[derive(Debug, Clone)]
+pub struct MarketMakerConfig {
+    pub pool_key: String,
+    pub manager_key: String,
+    /// Full width between bid and ask as a fraction of the mid price, e.g. 0.002 for 20 bps
+    pub spread: f64,
+    /// Base quantity quoted on each side
+    pub order_size: f64,
+    /// Desired share of inventory value held in the base asset, between 0 and 1
+    pub target_base_ratio: f64,
+    /// Price shift, as a fraction of the mid price, applied at full inventory imbalance
+    pub max_skew: f64,
+    /// Market price of DEEP in the quote asset, used to compare DEEP fees against input fees.
+    /// When None, fees are paid in DEEP whenever the balance covers them.
+    pub deep_market_price: Option<f64>,
+}
+
+/// Market and inventory data a quote is computed from
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub mid_price: f64,
+    pub book: PoolBookParams,
+    pub trade_params: PoolTradeParams,
+    pub deep_price: PoolDeepPrice,
+    pub base_balance: f64,
+    pub quote_balance: f64,
+    pub deep_balance: f64,
+}
+
+impl MarketSnapshot {
+    /// Share of inventory value held in the base asset, None without inventory
+    pub fn base_ratio(&self) -> Option<f64> {
+        let base_value = self.base_balance * self.mid_price;
+        let total = base_value + self.quote_balance;
+        if total <= 0.0 {
+            return None;
+        }
+        Some(base_value / total)
+    }
+}
+
+/// A two-sided quote
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quotes {
+    pub bid: Option<TargetOrder>,
+    pub ask: Option<TargetOrder>,
+    pub pay_with_deep: bool,
+    /// Inventory imbalance between -1 (all quote) and 1 (all base), 0 at the target ratio
+    pub imbalance: f64,
+}
+
+impl Quotes {
+    /// The quoted orders, in the form the requote path takes
+    pub fn targets(&self) -> Vec<TargetOrder> {
+        self.bid.into_iter().chain(self.ask).collect()
+    }
+
+    /// Post-only options for placing the quotes
+    pub fn options(&self) -> RequoteOptions {
+        RequoteOptions {
+            order_type: Some(OrderType::PostOnly),
+            pay_with_deep: Some(self.pay_with_deep),
+            ..Default::default()
+        }
+    }
+}
+
+/// Computes quotes from a MarketSnapshot without touching the network
+#[derive(Debug, Clone)]
+pub struct QuoteEngine {
+    config: MarketMakerConfig,
+}
+
+impl QuoteEngine {
+    /// Creates a new QuoteEngine
+    ///
+    /// @param config - The MarketMakerConfig to quote with
+    pub fn new(config: MarketMakerConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &MarketMakerConfig {
+        &self.config
+    }
+
+    /// Compute bid and ask around the mid price
+    ///
+    /// Both prices shift down when holding more base than the target and up when holding
+    /// less, so fills move inventory back toward the target. The bid is rounded down and
+    /// the ask up to a tick, and quantities are rounded down to a lot. A side is dropped
+    /// when the balance backing it cannot fund the minimum size.
+    ///
+    /// @param snapshot - The MarketSnapshot to quote against
+    pub fn quote(&self, snapshot: &MarketSnapshot) -> anyhow::Result<Quotes> {
+        let book = &snapshot.book;
+        if book.tick_size <= 0.0 || book.lot_size <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "Invalid book params for {}",
+                self.config.pool_key
+            ));
+        }
+        if snapshot.mid_price <= 0.0 {
+            return Err(anyhow::anyhow!("No mid price for {}", self.config.pool_key));
+        }
+
+        let imbalance = self.imbalance(snapshot);
+        let center = snapshot.mid_price * (1.0 - imbalance * self.config.max_skew);
+        let half_spread = snapshot.mid_price * self.config.spread / 2.0;
+
+        let bid_price = round_down(center - half_spread, book.tick_size);
+        let mut ask_price = round_up(center + half_spread, book.tick_size);
+        if ask_price <= bid_price {
+            ask_price = round_up(bid_price + book.tick_size, book.tick_size);
+        }
+
+        let bid = (bid_price > 0.0)
+            .then(|| {
+                let affordable = snapshot.quote_balance / bid_price;
+                order(
+                    true,
+                    bid_price,
+                    self.config.order_size.min(affordable),
+                    book,
+                )
+            })
+            .flatten();
+        let ask = order(
+            false,
+            ask_price,
+            self.config.order_size.min(snapshot.base_balance),
+            book,
+        );
+
+        let mut quotes = Quotes {
+            bid,
+            ask,
+            pay_with_deep: false,
+            imbalance,
+        };
+        quotes.pay_with_deep = self.pay_with_deep(snapshot, &quotes);
+        Ok(quotes)
+    }
+
+    fn imbalance(&self, snapshot: &MarketSnapshot) -> f64 {
+        let target = self.config.target_base_ratio.clamp(0.0, 1.0);
+        let ratio = match snapshot.base_ratio() {
+            Some(ratio) => ratio,
+            None => return 0.0,
+        };
+        let imbalance = if ratio >= target {
+            (ratio - target) / (1.0 - target).max(f64::EPSILON)
+        } else {
+            (ratio - target) / target.max(f64::EPSILON)
+        };
+        imbalance.clamp(-1.0, 1.0)
+    }
+
+    /// Pay in DEEP when the balance covers the maker fees of both quotes and, if a DEEP
+    /// market price is known, DEEP is no more expensive than the input asset penalty
+    fn pay_with_deep(&self, snapshot: &MarketSnapshot, quotes: &Quotes) -> bool {
+        let mut deep_required = 0.0;
+        let mut deep_value = 0.0;
+        let mut input_value = 0.0;
+        for order in quotes.targets() {
+            let trade = PlannedTrade {
+                is_bid: order.is_bid,
+                price: order.price,
+                quantity: order.quantity,
+                role: FeeRole::Maker,
+            };
+            let plan = match plan_fees(
+                &trade,
+                &snapshot.trade_params,
+                &snapshot.deep_price,
+                snapshot.deep_balance,
+                self.config.deep_market_price,
+            ) {
+                Ok(plan) => plan,
+                Err(_) => return false,
+            };
+            deep_required += plan.deep_fee;
+            deep_value += plan.deep_fee_value.unwrap_or(0.0);
+            input_value += plan.input_fee_value;
+        }
+
+        if deep_required > snapshot.deep_balance {
+            return false;
+        }
+        self.config.deep_market_price.is_none() || deep_value <= input_value
+    }
+}
+
+/// Quotes a pool for a balance manager and feeds the quotes to the requote path
+pub struct MarketMaker<'a> {
+    client: &'a DeepBookClient,
+    engine: QuoteEngine,
+}
+
+impl<'a> MarketMaker<'a> {
+    /// Creates a new MarketMaker
+    ///
+    /// @param client - The DeepBookClient used to read the pool and the balance manager
+    /// @param config - The MarketMakerConfig to quote with
+    pub fn new(client: &'a DeepBookClient, config: MarketMakerConfig) -> Self {
+        Self {
+            client,
+            engine: QuoteEngine::new(config),
+        }
+    }
+
+    pub fn engine(&self) -> &QuoteEngine {
+        &self.engine
+    }
+
+    /// Read the mid price, pool parameters and manager balances
+    ///
+    /// Balances include the amounts locked in this pool's open orders and settled in
+    /// the manager's account.
+    pub async fn snapshot(&self) -> anyhow::Result<MarketSnapshot> {
+        let config = self.engine.config();
+        let pool = self.client.config().get_pool(&config.pool_key)?;
+
+        let (_, mut base_balance) = self
+            .client
+            .check_manager_balance(&config.manager_key, &pool.base_coin)
+            .await?;
+        let (_, mut quote_balance) = self
+            .client
+            .check_manager_balance(&config.manager_key, &pool.quote_coin)
+            .await?;
+        let (_, mut deep_balance) = self
+            .client
+            .check_manager_balance(&config.manager_key, "DEEP")
+            .await?;
+
+        // A requote cancels the open orders first, so their locked balances and the
+        // account's settled balances fund the new quotes too.
+        if self
+            .client
+            .account_exists(&config.pool_key, &config.manager_key)
+            .await?
+        {
+            // `locked_balance` includes settled balances
+            let locked = self
+                .client
+                .locked_balance(&config.pool_key, &config.manager_key)
+                .await?;
+            base_balance += locked.base;
+            quote_balance += locked.quote;
+            deep_balance += locked.deep;
+        }
+
+        Ok(MarketSnapshot {
+            mid_price: self.client.mid_price(&config.pool_key).await?,
+            book: self.client.pool_book_params(&config.pool_key).await?,
+            trade_params: self.client.pool_trade_params(&config.pool_key).await?,
+            deep_price: self.client.get_pool_deep_price(&config.pool_key).await?,
+            base_balance,
+            quote_balance,
+            deep_balance,
+        })
+    }
+
+    /// Read a snapshot and compute quotes from it
+    pub async fn quote(&self) -> anyhow::Result<Quotes> {
+        let snapshot = self.snapshot().await?;
+        self.engine.quote(&snapshot)
+    }
+
+    /// Quote and build the requote transaction moving the open orders to the new quotes,
+    /// or None if they already match
+    ///
+    /// @param ids - Generator for the placed orders' client order IDs
+    pub async fn requote(
+        &self,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<Option<ProgrammableTransaction>> {
+        let config = self.engine.config();
+        let quotes = self.quote().await?;
+        Requoter::new(self.client)
+            .requote(
+                &config.pool_key,
+                &config.manager_key,
+                &quotes.targets(),
+                &quotes.options(),
+                ids,
+            )
+            .await
+    }
+}
+
+fn order(is_bid: bool, price: f64, quantity: f64, book: &PoolBookParams) -> Option<TargetOrder> {
+    let quantity = round_down(quantity, book.lot_size);
+    if quantity < book.min_size || quantity <= 0.0 {
+        return None;
+    }
+    Some(TargetOrder {
+        is_bid,
+        price,
+        quantity,
+    })
+}
+
+fn round_down(value: f64, step: f64) -> f64 {
+    (((value / step) + 1e-9).floor() * step * 1e9).round() / 1e9
+}
+
+fn round_up(value: f64, step: f64) -> f64 {
+    (((value / step) - 1e-9).ceil() * step * 1e9).round() / 1e9
+}
@@ -0,0 +1,423 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Write;
+
+use crate::client::{DeepBookClient, PoolTradeParams, VaultBalances};
+
+/// State of one pool at sampling time
+#[derive(Debug)]
+pub struct PoolSample {
+    pub pool_key: String,
+    /// Mid price, None when either side of the book is empty
+    pub mid_price: Option<f64>,
+    /// Best bid price and the quantity resting there
+    pub best_bid: Option<(f64, f64)>,
+    /// Best ask price and the quantity resting there
+    pub best_ask: Option<(f64, f64)>,
+    pub vault: VaultBalances,
+    pub trade_params: PoolTradeParams,
+}
+
+impl PoolSample {
+    /// Best ask minus best bid, None when either side is empty
+    pub fn spread(&self) -> Option<f64> {
+        match (self.best_bid, self.best_ask) {
+            (Some((bid, _)), Some((ask, _))) => Some(((ask - bid) * 1e9).round() / 1e9),
+            _ => None,
+        }
+    }
+}
+
+/// State of one balance manager at sampling time
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagerSample {
+    pub manager_key: String,
+    /// Balance per coin key
+    pub balances: Vec<(String, f64)>,
+    /// Open order count per pool key
+    pub open_orders: Vec<(String, usize)>,
+}
+
+/// One sampling round over every pool and manager
+#[derive(Debug, Default)]
+pub struct Sample {
+    pub timestamp_ms: u64,
+    pub pools: Vec<PoolSample>,
+    pub managers: Vec<ManagerSample>,
+    /// Keys of the pools whose reads failed this round, with the error
+    pub failed_pools: Vec<(String, String)>,
+    /// Keys of the managers whose reads failed this round, with the error
+    pub failed_managers: Vec<(String, String)>,
+}
+
+/// Render a Sample in the Prometheus text exposition format
+///
+/// Every pool and manager gets a `sample_ok` series, 0 when its reads failed, so alerts
+/// can tell a failed read from a missing series.
+///
+/// @param sample - The Sample to render
+pub fn render(sample: &Sample) -> String {
+    let mut out = String::new();
+
+    family(
+        &mut out,
+        "deepbook_last_sample_timestamp_seconds",
+        "Time of the latest sampling round",
+    );
+    metric(
+        &mut out,
+        "deepbook_last_sample_timestamp_seconds",
+        &[],
+        sample.timestamp_ms as f64 / 1000.0,
+    );
+
+    family(
+        &mut out,
+        "deepbook_pool_sample_ok",
+        "Whether the pool's reads succeeded in the latest round",
+    );
+    for pool in &sample.pools {
+        metric(
+            &mut out,
+            "deepbook_pool_sample_ok",
+            &[("pool", &pool.pool_key)],
+            1.0,
+        );
+    }
+    for (pool_key, _) in &sample.failed_pools {
+        metric(
+            &mut out,
+            "deepbook_pool_sample_ok",
+            &[("pool", pool_key)],
+            0.0,
+        );
+    }
+
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_mid_price",
+        "Mid price",
+        |pool| pool.mid_price,
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_best_bid",
+        "Best bid price",
+        |pool| pool.best_bid.map(|(price, _)| price),
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_best_ask",
+        "Best ask price",
+        |pool| pool.best_ask.map(|(price, _)| price),
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_spread",
+        "Best ask minus best bid",
+        PoolSample::spread,
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_spread_ratio",
+        "Spread divided by the mid price",
+        |pool| {
+            let mid_price = pool.mid_price.filter(|mid_price| *mid_price > 0.0)?;
+            pool.spread().map(|spread| spread / mid_price)
+        },
+    );
+
+    family(
+        &mut out,
+        "deepbook_pool_top_depth",
+        "Base quantity resting at the best price of each side",
+    );
+    for pool in &sample.pools {
+        for (side, level) in [("bid", pool.best_bid), ("ask", pool.best_ask)] {
+            metric(
+                &mut out,
+                "deepbook_pool_top_depth",
+                &[("pool", &pool.pool_key), ("side", side)],
+                level.map_or(0.0, |(_, quantity)| quantity),
+            );
+        }
+    }
+
+    family(
+        &mut out,
+        "deepbook_pool_vault_balance",
+        "Assets held in the pool's vault",
+    );
+    for pool in &sample.pools {
+        for (asset, balance) in [
+            ("base", pool.vault.base),
+            ("quote", pool.vault.quote),
+            ("deep", pool.vault.deep),
+        ] {
+            metric(
+                &mut out,
+                "deepbook_pool_vault_balance",
+                &[("pool", &pool.pool_key), ("asset", asset)],
+                balance,
+            );
+        }
+    }
+
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_taker_fee",
+        "Taker fee rate",
+        |pool| Some(pool.trade_params.taker_fee),
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_maker_fee",
+        "Maker fee rate",
+        |pool| Some(pool.trade_params.maker_fee),
+    );
+    pool_gauge(
+        &mut out,
+        sample,
+        "deepbook_pool_stake_required",
+        "DEEP stake required for reduced fees",
+        |pool| Some(pool.trade_params.stake_required),
+    );
+
+    family(
+        &mut out,
+        "deepbook_manager_sample_ok",
+        "Whether the manager's reads succeeded in the latest round",
+    );
+    for manager in &sample.managers {
+        metric(
+            &mut out,
+            "deepbook_manager_sample_ok",
+            &[("manager", &manager.manager_key)],
+            1.0,
+        );
+    }
+    for (manager_key, _) in &sample.failed_managers {
+        metric(
+            &mut out,
+            "deepbook_manager_sample_ok",
+            &[("manager", manager_key)],
+            0.0,
+        );
+    }
+
+    family(
+        &mut out,
+        "deepbook_manager_balance",
+        "Balance manager holdings per coin",
+    );
+    for manager in &sample.managers {
+        for (coin, balance) in &manager.balances {
+            metric(
+                &mut out,
+                "deepbook_manager_balance",
+                &[("manager", &manager.manager_key), ("coin", coin)],
+                *balance,
+            );
+        }
+    }
+
+    family(
+        &mut out,
+        "deepbook_manager_open_orders",
+        "Open orders of the balance manager per pool",
+    );
+    for manager in &sample.managers {
+        for (pool_key, count) in &manager.open_orders {
+            metric(
+                &mut out,
+                "deepbook_manager_open_orders",
+                &[("manager", &manager.manager_key), ("pool", pool_key)],
+                *count as f64,
+            );
+        }
+    }
+
+    out
+}
+
+fn pool_gauge(
+    out: &mut String,
+    sample: &Sample,
+    name: &str,
+    help: &str,
+    value: impl Fn(&PoolSample) -> Option<f64>,
+) {
+    family(out, name, help);
+    for pool in &sample.pools {
+        if let Some(value) = value(pool) {
+            metric(out, name, &[("pool", &pool.pool_key)], value);
+        }
+    }
+}
+
+fn family(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+}
+
+fn metric(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let value = if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    };
+    let _ = writeln!(out, " {}", value);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Samples pools and balance managers for the metrics exporter
+pub struct MetricsSampler<'a> {
+    client: &'a DeepBookClient,
+    pool_keys: Vec<String>,
+    manager_keys: Vec<String>,
+}
+
+impl<'a> MetricsSampler<'a> {
+    /// Creates a new MetricsSampler
+    ///
+    /// @param client - The DeepBookClient used to read pools and managers
+    /// @param pool_keys - Keys of the pools to sample
+    /// @param manager_keys - Keys of the balance managers to sample
+    pub fn new(
+        client: &'a DeepBookClient,
+        pool_keys: Vec<String>,
+        manager_keys: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        for pool_key in &pool_keys {
+            client.config().get_pool(pool_key)?;
+        }
+        for manager_key in &manager_keys {
+            client.config().get_balance_manager(manager_key)?;
+        }
+        Ok(Self {
+            client,
+            pool_keys,
+            manager_keys,
+        })
+    }
+
+    /// Read one pool's mid price, top of book, vault and fees
+    ///
+    /// @param pool_key - Key of the pool
+    pub async fn sample_pool(&self, pool_key: &str) -> anyhow::Result<PoolSample> {
+        let book = self.client.get_level2_ticks_from_mid(pool_key, 1).await?;
+        let best = |prices: &[f64], quantities: &[f64], is_bid: bool| {
+            prices
+                .iter()
+                .copied()
+                .zip(quantities.iter().copied())
+                .reduce(|best, level| {
+                    if (level.0 > best.0) == is_bid {
+                        level
+                    } else {
+                        best
+                    }
+                })
+        };
+
+        Ok(PoolSample {
+            pool_key: pool_key.to_string(),
+            // A one-sided book has no mid price, but its other series are still sampled
+            mid_price: self.client.mid_price(pool_key).await.ok(),
+            best_bid: best(&book.bid_prices, &book.bid_quantities, true),
+            best_ask: best(&book.ask_prices, &book.ask_quantities, false),
+            vault: self.client.vault_balances(pool_key).await?,
+            trade_params: self.client.pool_trade_params(pool_key).await?,
+        })
+    }
+
+    /// Read one manager's balances of the sampled pools' coins and DEEP, and its open
+    /// orders in every sampled pool
+    ///
+    /// @param manager_key - Key of the balance manager
+    pub async fn sample_manager(&self, manager_key: &str) -> anyhow::Result<ManagerSample> {
+        let mut coins = vec!["DEEP".to_string()];
+        for pool_key in &self.pool_keys {
+            let pool = self.client.config().get_pool(pool_key)?;
+            coins.push(pool.base_coin.clone());
+            coins.push(pool.quote_coin.clone());
+        }
+        coins.sort_unstable();
+        coins.dedup();
+
+        let mut balances = Vec::with_capacity(coins.len());
+        for coin in coins {
+            let (_, balance) = self
+                .client
+                .check_manager_balance(manager_key, &coin)
+                .await?;
+            balances.push((coin, balance));
+        }
+
+        let mut open_orders = Vec::with_capacity(self.pool_keys.len());
+        for pool_key in &self.pool_keys {
+            let count = if self.client.account_exists(pool_key, manager_key).await? {
+                self.client
+                    .account_open_orders(pool_key, manager_key)
+                    .await?
+                    .len()
+            } else {
+                0
+            };
+            open_orders.push((pool_key.clone(), count));
+        }
+
+        Ok(ManagerSample {
+            manager_key: manager_key.to_string(),
+            balances,
+            open_orders,
+        })
+    }
+
+    /// Sample every pool and manager; failed reads are recorded instead of returned
+    pub async fn sample(&self) -> Sample {
+        let mut sample = Sample {
+            timestamp_ms: chrono::Utc::now().timestamp_millis() as u64,
+            ..Default::default()
+        };
+        for pool_key in &self.pool_keys {
+            match self.sample_pool(pool_key).await {
+                Ok(pool) => sample.pools.push(pool),
+                Err(e) => sample.failed_pools.push((pool_key.clone(), e.to_string())),
+            }
+        }
+        for manager_key in &self.manager_keys {
+            match self.sample_manager(manager_key).await {
+                Ok(manager) => sample.managers.push(manager),
+                Err(e) => sample
+                    .failed_managers
+                    .push((manager_key.clone(), e.to_string())),
+            }
+        }
+        sample
+    }
+}
@@ -0,0 +1,727 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::{
+    EventFilter, SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::types::event::EventID;
+use sui_sdk::types::parse_sui_struct_tag;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::Argument;
+
+use crate::client::{DeepBookClient, Order};
+use crate::utils::config::DeepBookConfig;
+use crate::utils::json::{json_bool, json_field, json_object_id, json_u128, json_u64};
+use crate::utils::types::{
+    ClientOrderId, OrderId, OrderStatus, PlaceLimitOrderParams, PlaceMarketOrderParams, PoolRef,
+};
+
+/// Number of `OrderFilled` events read per page by `sync_fills`
+const FILL_PAGE_SIZE: usize = 50;
+
+/// Status of an order tracked by the OMS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OmsOrderStatus {
+    /// Submitted, not yet seen on-chain
+    Pending,
+    Live,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Expired,
+    /// The transaction placing the order failed
+    Rejected,
+    /// Left the book while the OMS was not watching; the outcome is unknown
+    Closed,
+}
+
+impl OmsOrderStatus {
+    /// Whether the order may still rest on the book
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            OmsOrderStatus::Pending | OmsOrderStatus::Live | OmsOrderStatus::PartiallyFilled
+        )
+    }
+
+    fn from_chain(status: OrderStatus) -> Self {
+        match status {
+            OrderStatus::Live => OmsOrderStatus::Live,
+            OrderStatus::PartiallyFilled => OmsOrderStatus::PartiallyFilled,
+            OrderStatus::Filled => OmsOrderStatus::Filled,
+            OrderStatus::Canceled => OmsOrderStatus::Canceled,
+            OrderStatus::Expired => OmsOrderStatus::Expired,
+        }
+    }
+}
+
+/// Key of a tracked order; client order IDs are only unique within a pool
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OrderKey {
+    pub pool_key: String,
+    pub client_order_id: ClientOrderId,
+}
+
+impl OrderKey {
+    pub fn new(pool_key: &str, client_order_id: ClientOrderId) -> Self {
+        Self {
+            pool_key: pool_key.to_string(),
+            client_order_id,
+        }
+    }
+}
+
+/// A locally tracked order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedOrder {
+    pub client_order_id: ClientOrderId,
+    pub pool_key: String,
+    pub order_id: Option<OrderId>,
+    pub is_bid: bool,
+    pub price: f64,
+    pub quantity: f64,
+    pub filled_quantity: f64,
+    pub status: OmsOrderStatus,
+}
+
+impl ManagedOrder {
+    /// Build a ManagedOrder from an on-chain order
+    ///
+    /// @param pool_key - Key of the pool holding the order
+    /// @param pool - The PoolRef used to scale quantities and the price
+    /// @param order - The on-chain order
+    pub fn from_chain(pool_key: &str, pool: &PoolRef, order: &Order) -> Self {
+        Self {
+            client_order_id: order.client_order_id,
+            pool_key: pool_key.to_string(),
+            order_id: Some(order.order_id),
+            is_bid: order.order_id.is_bid(),
+            price: order.order_id.normalized_price(pool),
+            quantity: pool.base_from_raw(order.quantity),
+            filled_quantity: pool.base_from_raw(order.filled_quantity),
+            status: OmsOrderStatus::from_chain(order.status),
+        }
+    }
+
+    pub fn key(&self) -> OrderKey {
+        OrderKey::new(&self.pool_key, self.client_order_id)
+    }
+
+    /// Whether an on-chain order ID may belong to this order
+    fn matches(&self, order_id: OrderId) -> bool {
+        self.order_id.is_none_or(|id| id == order_id)
+    }
+}
+
+/// Differences found between the local table and the chain
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport {
+    /// Orders open on-chain that were not in the local table. They are adopted into it
+    /// unless another order already holds their key
+    pub unknown_on_chain: Vec<ManagedOrder>,
+    /// Local open orders that are no longer on-chain. They are marked `Closed`
+    pub missing_on_chain: Vec<ManagedOrder>,
+    /// Local orders whose status or fill changed to match the chain
+    pub updated: Vec<OrderKey>,
+}
+
+impl ReconcileReport {
+    /// Whether the local table already matched the chain
+    pub fn is_clean(&self) -> bool {
+        self.unknown_on_chain.is_empty()
+            && self.missing_on_chain.is_empty()
+            && self.updated.is_empty()
+    }
+}
+
+/// The OMS order table, updated from DeepBook events and on-chain orders
+#[derive(Debug, Clone, Default)]
+pub struct OrderTable {
+    orders: BTreeMap<OrderKey, ManagedOrder>,
+    seen_events: HashSet<(String, u64)>,
+}
+
+impl OrderTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an OrderTable holding the given orders
+    ///
+    /// @param orders - The orders to track; later orders replace earlier ones with the same key
+    pub fn from_orders(orders: Vec<ManagedOrder>) -> Self {
+        Self {
+            orders: orders
+                .into_iter()
+                .map(|order| (order.key(), order))
+                .collect(),
+            seen_events: HashSet::new(),
+        }
+    }
+
+    pub fn get(&self, key: &OrderKey) -> Option<&ManagedOrder> {
+        self.orders.get(key)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = &ManagedOrder> {
+        self.orders.values()
+    }
+
+    pub fn open_orders(&self) -> impl Iterator<Item = &ManagedOrder> {
+        self.orders.values().filter(|order| order.status.is_open())
+    }
+
+    /// Track a new order
+    ///
+    /// @param order - The order to track; its key must not be tracked yet
+    pub fn track(&mut self, order: ManagedOrder) -> anyhow::Result<()> {
+        self.check_untracked(&order.key())?;
+        self.orders.insert(order.key(), order);
+        Ok(())
+    }
+
+    fn check_untracked(&self, key: &OrderKey) -> anyhow::Result<()> {
+        if self.orders.contains_key(key) {
+            return Err(anyhow::anyhow!(
+                "Client order ID {} is already tracked in {}",
+                key.client_order_id,
+                key.pool_key
+            ));
+        }
+        Ok(())
+    }
+
+    /// Settle orders still pending after their transaction executed
+    ///
+    /// @param keys - The orders placed by the transaction
+    /// @param failed - Whether the transaction failed; pending orders are rejected if so and
+    /// canceled otherwise, as they left no trace on the book
+    pub fn settle_pending(&mut self, keys: &[OrderKey], failed: bool) {
+        for key in keys {
+            if let Some(order) = self.orders.get_mut(key) {
+                if order.status == OmsOrderStatus::Pending {
+                    order.status = if failed {
+                        OmsOrderStatus::Rejected
+                    } else {
+                        OmsOrderStatus::Canceled
+                    };
+                }
+            }
+        }
+    }
+
+    /// Update the table from DeepBook order events. Events already applied are skipped,
+    /// and events of other packages or of untracked pools are ignored
+    ///
+    /// @param config - The DeepBookConfig naming the DeepBook package and pools
+    /// @param manager_id - ID of the BalanceManager whose orders are tracked
+    /// @param events - Events to apply, oldest first
+    pub fn apply_events(
+        &mut self,
+        config: &DeepBookConfig,
+        manager_id: ObjectID,
+        events: &[SuiEvent],
+    ) -> anyhow::Result<()> {
+        let package_id = ObjectID::from_hex_literal(config.deepbook_package_id())?;
+        for event in events {
+            let event_id = (event.id.tx_digest.to_string(), event.id.event_seq);
+            if self.seen_events.contains(&event_id) {
+                continue;
+            }
+            if ObjectID::from(event.type_.address) == package_id {
+                self.apply_event(config, manager_id, event)?;
+            }
+            self.seen_events.insert(event_id);
+        }
+        Ok(())
+    }
+
+    fn apply_event(
+        &mut self,
+        config: &DeepBookConfig,
+        manager_id: ObjectID,
+        event: &SuiEvent,
+    ) -> anyhow::Result<()> {
+        let json = &event.parsed_json;
+        let name = event.type_.name.as_str();
+        // Fills, placements and expiries come from `order_info`, cancels and modifies from `order`
+        if !matches!(
+            (event.type_.module.as_str(), name),
+            ("order_info", "OrderFilled" | "OrderPlaced" | "OrderExpired")
+                | ("order", "OrderCanceled" | "OrderModified")
+        ) {
+            return Ok(());
+        }
+        let pool_id = json_object_id(json_field(json, &["pool_id"])?)?;
+        let Some(pool_key) = pool_key_by_id(config, pool_id) else {
+            return Ok(());
+        };
+        let pool = config.pool_ref(&pool_key)?;
+
+        if name == "OrderFilled" {
+            let taker_is_bid = json_bool(json_field(json, &["taker_is_bid"])?)?;
+            let base_quantity = json_u64(json_field(json, &["base_quantity"])?)?;
+            for (prefix, is_bid) in [("maker", !taker_is_bid), ("taker", taker_is_bid)] {
+                let owner = format!("{}_balance_manager_id", prefix);
+                if json_object_id(json_field(json, &[owner.as_str()])?)? != manager_id {
+                    continue;
+                }
+                let client_id = format!("{}_client_order_id", prefix);
+                let order_id = format!("{}_order_id", prefix);
+                let key = OrderKey::new(
+                    &pool_key,
+                    ClientOrderId(json_u64(json_field(json, &[client_id.as_str()])?)?),
+                );
+                let order_id = OrderId(json_u128(json_field(json, &[order_id.as_str()])?)?);
+                if let Some(order) = self.orders.get_mut(&key) {
+                    if !order.matches(order_id) {
+                        continue;
+                    }
+                    order.order_id = Some(order_id);
+                    order.is_bid = is_bid;
+                    // Sum and compare in on-chain units so repeated fills do not drift
+                    // below the order quantity
+                    let filled = pool.base_to_input(order.filled_quantity) + base_quantity;
+                    order.filled_quantity = pool.base_from_raw(filled);
+                    order.status = if filled >= pool.base_to_input(order.quantity) {
+                        OmsOrderStatus::Filled
+                    } else {
+                        OmsOrderStatus::PartiallyFilled
+                    };
+                }
+            }
+            return Ok(());
+        }
+
+        if json_object_id(json_field(json, &["balance_manager_id"])?)? != manager_id {
+            return Ok(());
+        }
+        let key = OrderKey::new(
+            &pool_key,
+            ClientOrderId(json_u64(json_field(json, &["client_order_id"])?)?),
+        );
+        let order_id = OrderId(json_u128(json_field(json, &["order_id"])?)?);
+        let order = match self.orders.get_mut(&key) {
+            Some(order) if order.matches(order_id) => order,
+            _ => return Ok(()),
+        };
+        order.order_id = Some(order_id);
+
+        match name {
+            "OrderPlaced" => {
+                if order.status == OmsOrderStatus::Pending {
+                    order.status = if order.filled_quantity > 0.0 {
+                        OmsOrderStatus::PartiallyFilled
+                    } else {
+                        OmsOrderStatus::Live
+                    };
+                }
+            }
+            "OrderCanceled" => order.status = OmsOrderStatus::Canceled,
+            "OrderExpired" => order.status = OmsOrderStatus::Expired,
+            "OrderModified" => {
+                order.quantity =
+                    pool.base_from_raw(json_u64(json_field(json, &["new_quantity"])?)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reconcile one pool against its open on-chain orders
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param pool - The PoolRef used to scale the on-chain orders
+    /// @param chain_orders - The balance manager's open orders in the pool
+    pub fn reconcile_pool(
+        &mut self,
+        pool_key: &str,
+        pool: &PoolRef,
+        chain_orders: &[Order],
+    ) -> ReconcileReport {
+        let mut report = ReconcileReport::default();
+
+        let mut on_chain = HashSet::new();
+        for chain_order in chain_orders {
+            on_chain.insert(chain_order.order_id);
+            let synced = ManagedOrder::from_chain(pool_key, pool, chain_order);
+            let key = synced.key();
+            match self.orders.get_mut(&key) {
+                Some(order) if order.matches(chain_order.order_id) => {
+                    if order.order_id != synced.order_id
+                        || order.status != synced.status
+                        || order.filled_quantity != synced.filled_quantity
+                    {
+                        order.order_id = synced.order_id;
+                        order.status = synced.status;
+                        order.filled_quantity = synced.filled_quantity;
+                        order.quantity = synced.quantity;
+                        report.updated.push(key);
+                    }
+                }
+                // Another order holds this key; report without replacing it
+                Some(_) => report.unknown_on_chain.push(synced),
+                None => {
+                    report.unknown_on_chain.push(synced.clone());
+                    self.orders.insert(key, synced);
+                }
+            }
+        }
+
+        for order in self.orders.values_mut() {
+            if order.pool_key != pool_key || !order.status.is_open() {
+                continue;
+            }
+            match order.order_id {
+                Some(order_id) if on_chain.contains(&order_id) => continue,
+                // A pending order without an on-chain ID may still be in flight.
+                None if order.status == OmsOrderStatus::Pending => continue,
+                _ => {}
+            }
+            order.status = OmsOrderStatus::Closed;
+            report.missing_on_chain.push(order.clone());
+        }
+
+        report
+    }
+}
+
+fn pool_key_by_id(config: &DeepBookConfig, pool_id: ObjectID) -> Option<String> {
+    config
+        .pools()
+        .iter()
+        .find(|(_, pool)| ObjectID::from_hex_literal(&pool.address).ok() == Some(pool_id))
+        .map(|(key, _)| key.to_string())
+}
+
+/// Order management system for a single balance manager, keyed by pool and client order ID
+pub struct OrderManager<'a> {
+    client: &'a DeepBookClient,
+    manager_key: String,
+    table: OrderTable,
+    /// The newest `OrderFilled` event read by `sync_fills`
+    fill_cursor: Option<EventID>,
+}
+
+impl<'a> OrderManager<'a> {
+    /// Creates a new OrderManager with an empty order table
+    ///
+    /// @param client - The DeepBookClient used to build and read orders
+    /// @param manager_key - The key of the BalanceManager placing the orders
+    pub fn new(client: &'a DeepBookClient, manager_key: &str) -> Self {
+        Self {
+            client,
+            manager_key: manager_key.to_string(),
+            table: OrderTable::new(),
+            fill_cursor: None,
+        }
+    }
+
+    /// Restore the order table saved by `save`
+    ///
+    /// @param client - The DeepBookClient used to build and read orders
+    /// @param manager_key - The key of the BalanceManager placing the orders
+    /// @param path - The JSON file holding the order table
+    pub fn load(
+        client: &'a DeepBookClient,
+        manager_key: &str,
+        path: impl AsRef<Path>,
+    ) -> anyhow::Result<Self> {
+        let mut oms = Self::new(client, manager_key);
+        let orders: Vec<ManagedOrder> = serde_json::from_slice(&std::fs::read(path)?)?;
+        oms.table = OrderTable::from_orders(orders);
+        Ok(oms)
+    }
+
+    /// Save the order table as JSON
+    ///
+    /// @param path - The file to write
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let orders: Vec<&ManagedOrder> = self.table.orders().collect();
+        std::fs::write(path, serde_json::to_vec_pretty(&orders)?)?;
+        Ok(())
+    }
+
+    pub fn table(&self) -> &OrderTable {
+        &self.table
+    }
+
+    /// Get a tracked order
+    ///
+    /// @param key - The order's pool and client order ID
+    pub fn order(&self, key: &OrderKey) -> Option<&ManagedOrder> {
+        self.table.get(key)
+    }
+
+    /// All tracked orders
+    pub fn orders(&self) -> impl Iterator<Item = &ManagedOrder> {
+        self.table.orders()
+    }
+
+    /// Tracked orders that may still rest on the book
+    pub fn open_orders(&self) -> impl Iterator<Item = &ManagedOrder> {
+        self.table.open_orders()
+    }
+
+    /// Add a limit order to a PTB and track it as pending
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param params - Parameters for the limit order. `balance_manager_key` is replaced
+    /// with the OMS's balance manager, and `client_order_id` must be unique in the pool
+    /// @returns The key of the tracked order
+    pub async fn place_limit_order(
+        &mut self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        mut params: PlaceLimitOrderParams,
+    ) -> anyhow::Result<OrderKey> {
+        let key = OrderKey::new(&params.pool_key, params.client_order_id);
+        self.table.check_untracked(&key)?;
+
+        params.balance_manager_key = self.manager_key.clone();
+        let order = ManagedOrder {
+            client_order_id: params.client_order_id,
+            pool_key: params.pool_key.clone(),
+            order_id: None,
+            is_bid: params.is_bid,
+            price: params.price,
+            quantity: params.quantity,
+            filled_quantity: 0.0,
+            status: OmsOrderStatus::Pending,
+        };
+        self.client.deep_book.place_limit_order(ptb, params).await?;
+        self.table.track(order)?;
+        Ok(key)
+    }
+
+    /// Add a market order to a PTB and track it as pending
+    ///
+    /// The order never rests on the book. Its price is recorded as zero.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param params - Parameters for the market order. `balance_manager_key` is replaced
+    /// with the OMS's balance manager, and `client_order_id` must be unique in the pool
+    /// @returns The key of the tracked order
+    pub async fn place_market_order(
+        &mut self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        mut params: PlaceMarketOrderParams,
+    ) -> anyhow::Result<OrderKey> {
+        let key = OrderKey::new(&params.pool_key, params.client_order_id);
+        self.table.check_untracked(&key)?;
+
+        params.balance_manager_key = self.manager_key.clone();
+        let order = ManagedOrder {
+            client_order_id: params.client_order_id,
+            pool_key: params.pool_key.clone(),
+            order_id: None,
+            is_bid: params.is_bid,
+            price: 0.0,
+            quantity: params.quantity,
+            filled_quantity: 0.0,
+            status: OmsOrderStatus::Pending,
+        };
+        self.client
+            .deep_book
+            .place_market_order(ptb, params)
+            .await?;
+        self.table.track(order)?;
+        Ok(key)
+    }
+
+    /// Add a cancel for a tracked order to a PTB
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param key - The order's pool and client order ID
+    pub async fn cancel_order(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        key: &OrderKey,
+    ) -> anyhow::Result<Argument> {
+        let order = self.table.get(key).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown client order ID {} in {}",
+                key.client_order_id,
+                key.pool_key
+            )
+        })?;
+        let order_id = order.order_id.ok_or_else(|| {
+            anyhow::anyhow!("Order {} has no on-chain order ID yet", key.client_order_id)
+        })?;
+        self.client
+            .deep_book
+            .cancel_order(ptb, &order.pool_key, &self.manager_key, order_id)
+            .await
+    }
+
+    /// Update the table from an executed transaction
+    ///
+    /// The response must include effects and events. If the transaction failed, pending
+    /// orders in `placed` are marked rejected. If it succeeded, pending orders that
+    /// produced no events left no trace on the book and are marked canceled.
+    ///
+    /// @param response - The executed transaction
+    /// @param placed - The orders placed by the transaction
+    pub fn apply_transaction(
+        &mut self,
+        response: &SuiTransactionBlockResponse,
+        placed: &[OrderKey],
+    ) -> anyhow::Result<()> {
+        let failed = match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Failure { .. }) => true,
+            Some(SuiExecutionStatus::Success) => false,
+            None => return Err(anyhow::anyhow!("Transaction response has no effects")),
+        };
+
+        if !failed {
+            if let Some(events) = &response.events {
+                self.apply_events(&events.data)?;
+            }
+        }
+        self.table.settle_pending(placed, failed);
+        Ok(())
+    }
+
+    /// Update the table from DeepBook order events. Events already applied are skipped
+    ///
+    /// @param events - Events emitted by DeepBook
+    pub fn apply_events(&mut self, events: &[SuiEvent]) -> anyhow::Result<()> {
+        let config = self.client.config();
+        let manager_id = config.balance_manager_ref(&self.manager_key)?.id;
+        self.table.apply_events(config, manager_id, events)
+    }
+
+    /// Apply DeepBook `OrderFilled` events published since the previous sync
+    ///
+    /// The first sync looks back over the newest `max_events` events. Later syncs page
+    /// forward from the last event read, so no fill is missed between polls. Events
+    /// already applied, e.g. by `apply_transaction`, are skipped.
+    ///
+    /// @param max_events - Number of past events read by the first sync
+    pub async fn sync_fills(&mut self, max_events: usize) -> anyhow::Result<()> {
+        let client = self.client;
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}::order_info::OrderFilled",
+            client.config().deepbook_package_id()
+        ))?;
+        let event_api = client.sui_client().event_api();
+
+        let Some(mut cursor) = self.fill_cursor.clone() else {
+            let page = event_api
+                .query_events(
+                    EventFilter::MoveEventType(event_type),
+                    None,
+                    Some(max_events),
+                    true,
+                )
+                .await?;
+            let mut events = page.data;
+            events.reverse();
+            self.apply_events(&events)?;
+            self.fill_cursor = events.last().map(|event| event.id.clone());
+            return Ok(());
+        };
+
+        loop {
+            let page = event_api
+                .query_events(
+                    EventFilter::MoveEventType(event_type.clone()),
+                    Some(cursor.clone()),
+                    Some(FILL_PAGE_SIZE),
+                    false,
+                )
+                .await?;
+            self.apply_events(&page.data)?;
+            if let Some(event) = page.data.last() {
+                cursor = event.id.clone();
+                self.fill_cursor = Some(cursor.clone());
+            }
+            if !page.has_next_page || page.data.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reconcile one pool against `account_open_orders` and `get_orders`
+    ///
+    /// @param pool_key - Key of the pool
+    pub async fn reconcile_pool(&mut self, pool_key: &str) -> anyhow::Result<ReconcileReport> {
+        let pool = self.client.config().pool_ref(pool_key)?;
+
+        let open_order_ids = if self
+            .client
+            .account_exists(pool_key, &self.manager_key)
+            .await?
+        {
+            self.client
+                .account_open_orders(pool_key, &self.manager_key)
+                .await?
+        } else {
+            Vec::new()
+        };
+        let chain_orders = if open_order_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.client
+                .get_orders(pool_key, open_order_ids)
+                .await?
+                .unwrap_or_default()
+        };
+
+        Ok(self.table.reconcile_pool(pool_key, &pool, &chain_orders))
+    }
+
+    /// Reconcile every pool that is configured or referenced by a tracked order
+    pub async fn reconcile(&mut self) -> anyhow::Result<ReconcileReport> {
+        let mut pool_keys: Vec<String> = self
+            .client
+            .config()
+            .pools()
+            .keys()
+            .map(|key| key.to_string())
+            .collect();
+        for order in self.table.orders() {
+            if !pool_keys.contains(&order.pool_key) {
+                pool_keys.push(order.pool_key.clone());
+            }
+        }
+        pool_keys.sort_unstable();
+
+        let mut report = ReconcileReport::default();
+        for pool_key in pool_keys {
+            let pool_report = self.reconcile_pool(&pool_key).await?;
+            report.unknown_on_chain.extend(pool_report.unknown_on_chain);
+            report.missing_on_chain.extend(pool_report.missing_on_chain);
+            report.updated.extend(pool_report.updated);
+        }
+        Ok(report)
+    }
+
+    /// Reconcile on a fixed interval, passing each report to `on_report`
+    ///
+    /// Returns the first reconciliation error.
+    ///
+    /// @param interval - Time between reconciliations
+    /// @param on_report - Called with the OMS and each report, e.g. to persist the table
+    pub async fn reconcile_every<F>(
+        &mut self,
+        interval: Duration,
+        mut on_report: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut(&Self, &ReconcileReport),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let report = self.reconcile().await?;
+            on_report(self, &report);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use sui_sdk::rpc_types::SuiTypeTag;
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::TypeTag;
+
+use crate::client::{account_from_bcs, DeepBookClient};
+use crate::utils::config::DEEP_SCALAR;
+use crate::utils::types::PoolRef;
+use crate::DataReader;
+
+/// Holdings of a single coin across every place DeepBook can keep it
+#[derive(Debug, Clone, Default)]
+pub struct CoinHoldings {
+    /// Coins owned by the wallet address
+    pub wallet: f64,
+    /// Free balance in the BalanceManager
+    pub manager: f64,
+    /// Balance locked in open orders across all pools
+    pub locked: f64,
+    /// Settled amounts waiting to be withdrawn into the BalanceManager
+    pub settled: f64,
+    /// Amounts owed to pools, deducted on the next settlement
+    pub owed: f64,
+    /// Unclaimed maker rebates
+    pub unclaimed_rebates: f64,
+    /// Mid price in the valuation coin, None if no pool prices this coin directly
+    pub price: Option<f64>,
+}
+
+impl CoinHoldings {
+    /// Net amount of the coin held
+    pub fn total(&self) -> f64 {
+        self.wallet + self.manager + self.locked + self.settled + self.unclaimed_rebates - self.owed
+    }
+
+    /// Net amount valued in the valuation coin
+    pub fn value(&self) -> Option<f64> {
+        self.price.map(|price| self.total() * price)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.wallet == 0.0
+            && self.manager == 0.0
+            && self.locked == 0.0
+            && self.settled == 0.0
+            && self.owed == 0.0
+            && self.unclaimed_rebates == 0.0
+    }
+}
+
+/// A snapshot of holdings per coin key, valued in `quote_coin`
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    pub quote_coin: String,
+    pub holdings: BTreeMap<String, CoinHoldings>,
+}
+
+impl Portfolio {
+    /// Total value of all holdings that could be priced
+    pub fn total_value(&self) -> f64 {
+        self.holdings.values().filter_map(CoinHoldings::value).sum()
+    }
+
+    /// Coin keys that could not be valued in the quote coin
+    pub fn unpriced(&self) -> Vec<&str> {
+        self.holdings
+            .iter()
+            .filter(|(_, holdings)| holdings.price.is_none())
+            .map(|(coin_key, _)| coin_key.as_str())
+            .collect()
+    }
+
+    /// Load a portfolio for a wallet and balance manager across all configured coins and pools
+    ///
+    /// Reads are batched into a handful of dev-inspect calls: manager balances and account
+    /// existence, then locked balances and accounts, then mid prices.
+    ///
+    /// @param client - The DeepBookClient to read with
+    /// @param owner - The wallet address holding coins
+    /// @param manager_key - The key of the BalanceManager
+    /// @param quote_coin - The coin key to value holdings in
+    pub async fn load(
+        client: &DeepBookClient,
+        owner: SuiAddress,
+        manager_key: &str,
+        quote_coin: &str,
+    ) -> anyhow::Result<Self> {
+        let config = client.config();
+        let manager = config.balance_manager_ref(manager_key)?;
+        config.get_coin(quote_coin)?;
+
+        let mut coin_keys: Vec<&str> = config.coins().keys().copied().collect();
+        coin_keys.sort_unstable();
+        let mut pool_keys: Vec<&str> = config.pools().keys().copied().collect();
+        pool_keys.sort_unstable();
+        let pools = pool_keys
+            .iter()
+            .map(|pool_key| config.pool_ref(pool_key))
+            .collect::<anyhow::Result<Vec<PoolRef>>>()?;
+
+        let mut holdings: BTreeMap<String, CoinHoldings> = coin_keys
+            .iter()
+            .map(|coin_key| (coin_key.to_string(), CoinHoldings::default()))
+            .collect();
+
+        // Manager balances and account existence
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        for coin_key in &coin_keys {
+            let coin_type = TypeTag::from_str(&config.get_coin(coin_key)?.type_name)?;
+            client
+                .balance_manager
+                .check_manager_balance_by_ref(&mut ptb, &manager, coin_type)
+                .await?;
+        }
+        for pool in &pools {
+            client
+                .deep_book
+                .account_exists_by_ref(&mut ptb, pool, &manager)
+                .await?;
+        }
+        let results = client
+            .sui_client()
+            .dev_inspect_transaction_results(client.address(), ptb)
+            .await?;
+
+        for (index, coin_key) in coin_keys.iter().enumerate() {
+            let balance = bcs::from_bytes::<u64>(&first_value(&results, index)?.0)?;
+            let scalar = config.get_coin(coin_key)?.scalar;
+            holdings.get_mut(*coin_key).unwrap().manager = balance as f64 / scalar as f64;
+        }
+        let mut active_pools = Vec::new();
+        for (index, (pool_key, pool)) in pool_keys.iter().zip(&pools).enumerate() {
+            let exists = first_value(&results, coin_keys.len() + index)?;
+            if bcs::from_bytes::<bool>(&exists.0)? {
+                active_pools.push((*pool_key, pool));
+            }
+        }
+
+        // Locked balances and accounts for pools the manager has traded in
+        if !active_pools.is_empty() {
+            let mut ptb = ProgrammableTransactionBuilder::new();
+            for (_, pool) in &active_pools {
+                client
+                    .deep_book
+                    .locked_balance_by_ref(&mut ptb, pool, &manager)
+                    .await?;
+                client
+                    .deep_book
+                    .account_by_ref(&mut ptb, pool, &manager)
+                    .await?;
+            }
+            let results = client
+                .sui_client()
+                .dev_inspect_transaction_results(client.address(), ptb)
+                .await?;
+
+            for (index, (pool_key, pool)) in active_pools.iter().enumerate() {
+                let locked = results
+                    .get(index * 2)
+                    .ok_or_else(|| anyhow::anyhow!("Failed to get locked balance"))?;
+                if locked.len() != 3 {
+                    return Err(anyhow::anyhow!("Failed to get locked balance"));
+                }
+                let locked_base = pool.base_from_raw(bcs::from_bytes::<u64>(&locked[0].0)?);
+                let locked_quote = pool.quote_from_raw(bcs::from_bytes::<u64>(&locked[1].0)?);
+                let locked_deep = bcs::from_bytes::<u64>(&locked[2].0)? as f64 / DEEP_SCALAR as f64;
+                let account = account_from_bcs(&first_value(&results, index * 2 + 1)?.0, pool)?;
+
+                let config_pool = config.get_pool(pool_key)?;
+                // `locked_balance` includes settled balances, which are reported separately.
+                for (coin_key, locked, settled, owed, rebates) in [
+                    (
+                        config_pool.base_coin.as_str(),
+                        locked_base,
+                        account.settled_balances.base,
+                        account.owed_balances.base,
+                        account.unclaimed_rebates.base,
+                    ),
+                    (
+                        config_pool.quote_coin.as_str(),
+                        locked_quote,
+                        account.settled_balances.quote,
+                        account.owed_balances.quote,
+                        account.unclaimed_rebates.quote,
+                    ),
+                    (
+                        "DEEP",
+                        locked_deep,
+                        account.settled_balances.deep,
+                        account.owed_balances.deep,
+                        account.unclaimed_rebates.deep,
+                    ),
+                ] {
+                    let entry = holdings.entry(coin_key.to_string()).or_default();
+                    entry.locked += (locked - settled).max(0.0);
+                    entry.settled += settled;
+                    entry.owed += owed;
+                    entry.unclaimed_rebates += rebates;
+                }
+            }
+        }
+
+        // Wallet coins
+        let coin_types: HashMap<String, &str> = coin_keys
+            .iter()
+            .map(|coin_key| {
+                let type_name = &config.get_coin(coin_key)?.type_name;
+                Ok::<_, anyhow::Error>((canonical_type(type_name)?, *coin_key))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let balances = client
+            .sui_client()
+            .coin_read_api()
+            .get_all_balances(owner)
+            .await?;
+        for balance in balances {
+            let coin_key = match coin_types.get(&canonical_type(&balance.coin_type)?) {
+                Some(coin_key) => *coin_key,
+                None => continue,
+            };
+            let scalar = config.get_coin(coin_key)?.scalar;
+            holdings.get_mut(coin_key).unwrap().wallet =
+                balance.total_balance as f64 / scalar as f64;
+        }
+
+        holdings.retain(|_, holdings| !holdings.is_empty());
+
+        // Valuation through pools pairing each coin directly with the quote coin
+        let mut priced = Vec::new();
+        for coin_key in holdings.keys() {
+            if coin_key == quote_coin {
+                continue;
+            }
+            let pool = pool_keys.iter().zip(&pools).find_map(|(pool_key, pool)| {
+                let config_pool = config.get_pool(pool_key).ok()?;
+                if config_pool.base_coin == *coin_key && config_pool.quote_coin == quote_coin {
+                    Some((pool, false))
+                } else if config_pool.base_coin == quote_coin && config_pool.quote_coin == *coin_key
+                {
+                    Some((pool, true))
+                } else {
+                    None
+                }
+            });
+            if let Some((pool, inverse)) = pool {
+                priced.push((coin_key.clone(), pool, inverse));
+            }
+        }
+
+        let mid_prices = mid_prices(client, priced.iter().map(|(_, pool, _)| *pool)).await;
+        for ((coin_key, _, inverse), mid_price) in priced.into_iter().zip(mid_prices) {
+            let price = match mid_price {
+                Some(mid_price) if inverse && mid_price > 0.0 => Some(1.0 / mid_price),
+                Some(mid_price) if !inverse => Some(mid_price),
+                _ => None,
+            };
+            holdings.get_mut(&coin_key).unwrap().price = price;
+        }
+        if let Some(holdings) = holdings.get_mut(quote_coin) {
+            holdings.price = Some(1.0);
+        }
+
+        Ok(Self {
+            quote_coin: quote_coin.to_string(),
+            holdings,
+        })
+    }
+}
+
+/// Mid prices for a set of pools in one dev-inspect call, falling back to one call per
+/// pool when a pool aborts, for example because one side of its book is empty
+async fn mid_prices<'p>(
+    client: &DeepBookClient,
+    pools: impl Iterator<Item = &'p PoolRef>,
+) -> Vec<Option<f64>> {
+    let pools: Vec<&PoolRef> = pools.collect();
+    if pools.is_empty() {
+        return Vec::new();
+    }
+
+    let batched = async {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        for pool in &pools {
+            client.deep_book.mid_price_by_ref(&mut ptb, pool).await?;
+        }
+        let results = client
+            .sui_client()
+            .dev_inspect_transaction_results(client.address(), ptb)
+            .await?;
+        pools
+            .iter()
+            .enumerate()
+            .map(|(index, pool)| {
+                let raw = bcs::from_bytes::<u64>(&first_value(&results, index)?.0)?;
+                Ok::<_, anyhow::Error>(Some(pool.price_from_raw(raw)))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+    };
+    if let Ok(prices) = batched.await {
+        return prices;
+    }
+
+    let mut prices = Vec::with_capacity(pools.len());
+    for pool in pools {
+        prices.push(client.mid_price_by_ref(pool).await.ok());
+    }
+    prices
+}
+
+pub(crate) fn first_value(
+    results: &[Vec<(Vec<u8>, SuiTypeTag)>],
+    index: usize,
+) -> anyhow::Result<&(Vec<u8>, SuiTypeTag)> {
+    results
+        .get(index)
+        .and_then(|values| values.first())
+        .ok_or_else(|| anyhow::anyhow!("Failed to get result {}", index))
+}
+
+fn canonical_type(type_name: &str) -> anyhow::Result<String> {
+    Ok(TypeTag::from_str(type_name)?.to_canonical_string(true))
+}
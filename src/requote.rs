@@ -0,0 +1,361 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::ProgrammableTransaction;
+
+use crate::client::{DeepBookClient, PoolBookParams};
+use crate::utils::client_order_id::ClientOrderIdGenerator;
+use crate::utils::types::{
+    OrderId, OrderType, PlaceLimitOrderParams, PoolRef, SelfMatchingOptions,
+};
+
+/// An order the balance manager wants resting in the book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetOrder {
+    pub is_bid: bool,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+/// An open order of the balance manager, as read from the pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveOrder {
+    pub order_id: OrderId,
+    pub is_bid: bool,
+    pub price: f64,
+    pub quantity: f64,
+    pub filled_quantity: f64,
+    /// Time after which the order can no longer fill, in milliseconds
+    pub expire_timestamp: u64,
+}
+
+impl LiveOrder {
+    /// Quantity still resting in the book
+    pub fn remaining_quantity(&self) -> f64 {
+        self.quantity - self.filled_quantity
+    }
+
+    /// Whether the order expired before `now_ms`
+    ///
+    /// @param now_ms - The current on-chain time in milliseconds
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.expire_timestamp < now_ms
+    }
+}
+
+/// A quantity reduction of an open order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderModification {
+    pub order_id: OrderId,
+    /// The order's new total quantity, including what has already been filled
+    pub new_quantity: f64,
+}
+
+/// The changes that turn the open orders into the target book
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookDiff {
+    pub cancels: Vec<OrderId>,
+    pub modifies: Vec<OrderModification>,
+    pub placements: Vec<TargetOrder>,
+    /// Open orders left as they are, keeping their queue priority
+    pub unchanged: Vec<OrderId>,
+}
+
+impl BookDiff {
+    /// Whether the open orders already match the target book
+    pub fn is_empty(&self) -> bool {
+        self.cancels.is_empty() && self.modifies.is_empty() && self.placements.is_empty()
+    }
+
+    /// Number of move calls needed to apply the diff, excluding the trade proof
+    pub fn call_count(&self) -> usize {
+        self.cancels.len() + self.modifies.len() + self.placements.len()
+    }
+}
+
+/// Diff open orders against a target book
+///
+/// Prices and quantities are compared in on-chain units. At each price level the oldest
+/// orders are kept first; an order that would overshoot the target is reduced with a
+/// modify, and orders beyond the target are canceled. A level that ends up short gets a
+/// new order for the difference. Orders at prices missing from the target are canceled.
+///
+/// Reduced and placed quantities are rounded down to a whole lot. A reduction that would
+/// leave less than `min_size` resting cancels the order instead, and a shortfall below
+/// `min_size` is not placed.
+///
+/// Expired orders can no longer fill but still lock their balance, so they are always
+/// canceled and never count towards the target.
+///
+/// @param pool - The PoolRef used to convert prices and quantities
+/// @param book - The pool's PoolBookParams
+/// @param current - The open orders of the balance manager
+/// @param target - The desired resting orders, several orders at one price are summed
+/// @param now_ms - The current on-chain time in milliseconds
+pub fn diff_orders(
+    pool: &PoolRef,
+    book: &PoolBookParams,
+    current: &[LiveOrder],
+    target: &[TargetOrder],
+    now_ms: u64,
+) -> BookDiff {
+    let lot_size = pool.base_to_input(book.lot_size).max(1);
+    let min_size = pool.base_to_input(book.min_size);
+
+    let mut wanted: BTreeMap<(bool, u64), u64> = BTreeMap::new();
+    for order in target {
+        let quantity = pool.base_to_input(order.quantity);
+        if quantity > 0 {
+            *wanted
+                .entry((order.is_bid, pool.price_to_input(order.price)))
+                .or_insert(0) += quantity;
+        }
+    }
+
+    let mut diff = BookDiff::default();
+    let mut levels: BTreeMap<(bool, u64), Vec<&LiveOrder>> = BTreeMap::new();
+    for order in current {
+        if order.is_expired(now_ms) {
+            diff.cancels.push(order.order_id);
+            continue;
+        }
+        levels
+            .entry((order.is_bid, pool.price_to_input(order.price)))
+            .or_default()
+            .push(order);
+    }
+
+    let mut short: BTreeMap<(bool, u64), u64> = BTreeMap::new();
+    for (level, mut orders) in levels {
+        // Order IDs sort in queue priority, oldest first within a price level.
+        orders.sort_by_key(|order| order.order_id);
+
+        let mut left = wanted.remove(&level).unwrap_or(0);
+        for order in orders {
+            let filled = pool.base_to_input(order.filled_quantity);
+            let remaining = pool.base_to_input(order.quantity).saturating_sub(filled);
+            if left == 0 {
+                diff.cancels.push(order.order_id);
+            } else if remaining <= left {
+                diff.unchanged.push(order.order_id);
+                left -= remaining;
+            } else {
+                let new_remaining = left - left % lot_size;
+                if new_remaining < min_size {
+                    diff.cancels.push(order.order_id);
+                } else {
+                    diff.modifies.push(OrderModification {
+                        order_id: order.order_id,
+                        new_quantity: pool.base_from_raw(filled + new_remaining),
+                    });
+                }
+                left = 0;
+            }
+        }
+        if left > 0 {
+            short.insert(level, left);
+        }
+    }
+    short.extend(wanted);
+
+    for ((is_bid, price), quantity) in short {
+        let quantity = quantity - quantity % lot_size;
+        if quantity < min_size || quantity == 0 {
+            continue;
+        }
+        diff.placements.push(TargetOrder {
+            is_bid,
+            price: pool.price_from_raw(price),
+            quantity: pool.base_from_raw(quantity),
+        });
+    }
+    diff
+}
+
+/// Options applied to every order placed by a requote
+#[derive(Debug, Clone, Default)]
+pub struct RequoteOptions {
+    pub order_type: Option<OrderType>,
+    pub self_matching_option: Option<SelfMatchingOptions>,
+    pub pay_with_deep: Option<bool>,
+    pub expiration: Option<u64>,
+}
+
+/// A requote of one balance manager in one pool
+#[derive(Debug, Clone)]
+pub struct RequotePlan {
+    pub pool_key: String,
+    pub manager_key: String,
+    pub diff: BookDiff,
+}
+
+/// Builds atomic cancel-and-replace transactions from a target book
+pub struct Requoter<'a> {
+    client: &'a DeepBookClient,
+}
+
+impl<'a> Requoter<'a> {
+    /// Creates a new Requoter
+    ///
+    /// @param client - The DeepBookClient used to read orders and build calls
+    pub fn new(client: &'a DeepBookClient) -> Self {
+        Self { client }
+    }
+
+    /// Read the open orders of a balance manager in a pool
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn live_orders(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+    ) -> anyhow::Result<Vec<LiveOrder>> {
+        let pool = self.client.config().pool_ref(pool_key)?;
+        let order_ids = self
+            .client
+            .account_open_orders(pool_key, manager_key)
+            .await?;
+        if order_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let orders = self
+            .client
+            .get_orders(pool_key, order_ids)
+            .await?
+            .unwrap_or_default();
+
+        let orders = orders
+            .iter()
+            .map(|order| LiveOrder {
+                order_id: order.order_id,
+                is_bid: order.order_id.is_bid(),
+                price: order.order_id.normalized_price(&pool),
+                quantity: pool.base_from_raw(order.quantity),
+                filled_quantity: pool.base_from_raw(order.filled_quantity),
+                expire_timestamp: order.expire_timestamp,
+            })
+            .collect();
+        Ok(orders)
+    }
+
+    /// Diff the open orders of a balance manager against a target book
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    /// @param target - The desired resting orders
+    pub async fn plan(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+        target: &[TargetOrder],
+    ) -> anyhow::Result<RequotePlan> {
+        let pool = self.client.config().pool_ref(pool_key)?;
+        let book = self.client.pool_book_params(pool_key).await?;
+        let current = self.live_orders(pool_key, manager_key).await?;
+        let now_ms = self.client.clock_timestamp_ms().await?;
+
+        Ok(RequotePlan {
+            pool_key: pool_key.to_string(),
+            manager_key: manager_key.to_string(),
+            diff: diff_orders(&pool, &book, &current, target, now_ms),
+        })
+    }
+
+    /// Add the calls for a plan to a PTB, sharing one trade proof
+    ///
+    /// Cancels and modifies come first so the balances they release can fund the new orders.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param plan - The RequotePlan to execute
+    /// @param options - Options for the placed orders
+    /// @param ids - Generator for the placed orders' client order IDs
+    pub async fn build(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        plan: &RequotePlan,
+        options: &RequoteOptions,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<()> {
+        if plan.diff.is_empty() {
+            return Ok(());
+        }
+
+        let config = self.client.config();
+        let pool = config.pool_ref(&plan.pool_key)?;
+        let balance_manager = config.balance_manager_ref(&plan.manager_key)?;
+        let trade_proof = self
+            .client
+            .balance_manager
+            .generate_proof_by_ref(ptb, &balance_manager)
+            .await?;
+
+        for order_id in &plan.diff.cancels {
+            self.client
+                .deep_book
+                .cancel_order_with_proof(ptb, &pool, &balance_manager, trade_proof, *order_id)
+                .await?;
+        }
+        for modification in &plan.diff.modifies {
+            self.client
+                .deep_book
+                .modify_order_with_proof(
+                    ptb,
+                    &pool,
+                    &balance_manager,
+                    trade_proof,
+                    modification.order_id,
+                    modification.new_quantity,
+                )
+                .await?;
+        }
+        for order in &plan.diff.placements {
+            let params = PlaceLimitOrderParams {
+                pool_key: plan.pool_key.clone(),
+                balance_manager_key: plan.manager_key.clone(),
+                client_order_id: ids.next_id()?,
+                price: order.price,
+                quantity: order.quantity,
+                is_bid: order.is_bid,
+                expiration: options.expiration,
+                order_type: options.order_type,
+                self_matching_option: options.self_matching_option,
+                pay_with_deep: options.pay_with_deep,
+            };
+            self.client
+                .deep_book
+                .place_limit_order_with_proof(ptb, &pool, &balance_manager, trade_proof, params)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Plan and build a single requote transaction, or None if the book already matches
+    ///
+    /// @param pool_key - Key of the pool
+    /// @param manager_key - The key of the BalanceManager
+    /// @param target - The desired resting orders
+    /// @param options - Options for the placed orders
+    /// @param ids - Generator for the placed orders' client order IDs
+    pub async fn requote(
+        &self,
+        pool_key: &str,
+        manager_key: &str,
+        target: &[TargetOrder],
+        options: &RequoteOptions,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<Option<ProgrammableTransaction>> {
+        let plan = self.plan(pool_key, manager_key, target).await?;
+        if plan.diff.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        self.build(&mut ptb, &plan, options, ids).await?;
+        Ok(Some(ptb.finish()))
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::ProgrammableTransaction;
+
+use crate::client::{account_from_bcs, Balances, DeepBookClient};
+use crate::executor::TransactionExecutor;
+use crate::portfolio::first_value;
+use crate::utils::backoff::Backoff;
+use crate::utils::config::DeepBookConfig;
+use crate::utils::types::{OrderId, PoolRef};
+use crate::DataReader;
+
+/// Rebates and settled balances waiting in a single pool
+#[derive(Debug, Clone)]
+pub struct PoolSweep {
+    pub pool_key: String,
+    pub unclaimed_rebates: Balances,
+    pub settled_balances: Balances,
+    /// Amounts owed to the pool, netted against the proceeds when the account settles
+    pub owed_balances: Balances,
+}
+
+impl PoolSweep {
+    fn has_rebates(&self) -> bool {
+        is_non_zero(&self.unclaimed_rebates)
+    }
+
+    fn has_settled(&self) -> bool {
+        is_non_zero(&self.settled_balances)
+    }
+}
+
+/// The pools to sweep for a balance manager and the proceeds per coin key
+#[derive(Debug, Clone)]
+pub struct SweepPlan {
+    pub manager_key: String,
+    pub pools: Vec<PoolSweep>,
+    pub proceeds: BTreeMap<String, f64>,
+}
+
+impl SweepPlan {
+    /// Build a plan from per-pool balances, dropping pools with nothing to claim or settle
+    ///
+    /// Proceeds are net of owed balances, and only coins with a positive net amount are kept.
+    ///
+    /// @param config - The DeepBookConfig resolving each pool's coins
+    /// @param manager_key - The key of the BalanceManager
+    /// @param pools - Rebates, settled and owed balances per pool
+    pub fn new(
+        config: &DeepBookConfig,
+        manager_key: &str,
+        pools: Vec<PoolSweep>,
+    ) -> anyhow::Result<Self> {
+        let pools: Vec<PoolSweep> = pools
+            .into_iter()
+            .filter(|sweep| sweep.has_rebates() || sweep.has_settled())
+            .collect();
+
+        let mut proceeds = BTreeMap::new();
+        for sweep in &pools {
+            let pool = config.get_pool(&sweep.pool_key)?;
+            for (balances, sign) in [
+                (&sweep.unclaimed_rebates, 1.0),
+                (&sweep.settled_balances, 1.0),
+                (&sweep.owed_balances, -1.0),
+            ] {
+                *proceeds.entry(pool.base_coin.clone()).or_insert(0.0) += sign * balances.base;
+                *proceeds.entry(pool.quote_coin.clone()).or_insert(0.0) += sign * balances.quote;
+                *proceeds.entry("DEEP".to_string()).or_insert(0.0) += sign * balances.deep;
+            }
+        }
+        proceeds.retain(|_, amount| *amount > 0.0);
+
+        Ok(Self {
+            manager_key: manager_key.to_string(),
+            pools,
+            proceeds,
+        })
+    }
+
+    /// Whether there is nothing to claim or settle
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+}
+
+/// Claims rebates and withdraws settled amounts across every configured pool
+pub struct Sweeper<'a> {
+    client: &'a DeepBookClient,
+}
+
+impl<'a> Sweeper<'a> {
+    /// Creates a new Sweeper
+    ///
+    /// @param client - The DeepBookClient used to read accounts and build calls
+    pub fn new(client: &'a DeepBookClient) -> Self {
+        Self { client }
+    }
+
+    /// Read the account of a balance manager in every configured pool and collect
+    /// the pools holding unclaimed rebates or settled balances
+    ///
+    /// Account existence for every pool is read in one dev-inspect, then the accounts of
+    /// the pools the manager has traded in in a second one.
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn plan(&self, manager_key: &str) -> anyhow::Result<SweepPlan> {
+        let config = self.client.config();
+        let manager = config.balance_manager_ref(manager_key)?;
+        let mut pool_keys: Vec<&str> = config.pools().keys().copied().collect();
+        pool_keys.sort_unstable();
+        let pools = pool_keys
+            .iter()
+            .map(|pool_key| config.pool_ref(pool_key))
+            .collect::<anyhow::Result<Vec<PoolRef>>>()?;
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        for pool in &pools {
+            self.client
+                .deep_book
+                .account_exists_by_ref(&mut ptb, pool, &manager)
+                .await?;
+        }
+        let results = self
+            .client
+            .sui_client()
+            .dev_inspect_transaction_results(self.client.address(), ptb)
+            .await?;
+        let mut active_pools = Vec::new();
+        for (index, (pool_key, pool)) in pool_keys.iter().zip(&pools).enumerate() {
+            if bcs::from_bytes::<bool>(&first_value(&results, index)?.0)? {
+                active_pools.push((*pool_key, pool));
+            }
+        }
+
+        let mut sweeps = Vec::with_capacity(active_pools.len());
+        if !active_pools.is_empty() {
+            let mut ptb = ProgrammableTransactionBuilder::new();
+            for (_, pool) in &active_pools {
+                self.client
+                    .deep_book
+                    .account_by_ref(&mut ptb, pool, &manager)
+                    .await?;
+            }
+            let results = self
+                .client
+                .sui_client()
+                .dev_inspect_transaction_results(self.client.address(), ptb)
+                .await?;
+            for (index, (pool_key, pool)) in active_pools.iter().enumerate() {
+                let account = account_from_bcs(&first_value(&results, index)?.0, pool)?;
+                sweeps.push(PoolSweep {
+                    pool_key: pool_key.to_string(),
+                    unclaimed_rebates: account.unclaimed_rebates,
+                    settled_balances: account.settled_balances,
+                    owed_balances: account.owed_balances,
+                });
+            }
+        }
+
+        SweepPlan::new(config, manager_key, sweeps)
+    }
+
+    /// Add the claim and settle calls for a plan to a PTB, sharing one trade proof
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param plan - The SweepPlan to execute
+    /// @param recipient - If set, withdraw the net proceeds from the BalanceManager to this address
+    pub async fn build(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        plan: &SweepPlan,
+        recipient: Option<SuiAddress>,
+    ) -> anyhow::Result<()> {
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        let config = self.client.config();
+        let balance_manager = config.balance_manager_ref(&plan.manager_key)?;
+        let trade_proof = self
+            .client
+            .balance_manager
+            .generate_proof_by_ref(ptb, &balance_manager)
+            .await?;
+        for sweep in &plan.pools {
+            let pool = config.pool_ref(&sweep.pool_key)?;
+            if sweep.has_rebates() {
+                self.client
+                    .deep_book
+                    .claim_rebates_with_proof(ptb, &pool, &balance_manager, trade_proof)
+                    .await?;
+            }
+            if sweep.has_settled() {
+                self.client
+                    .deep_book
+                    .withdraw_settled_amounts_with_proof(ptb, &pool, &balance_manager, trade_proof)
+                    .await?;
+            }
+        }
+
+        if let Some(recipient) = recipient {
+            for (coin_key, amount) in &plan.proceeds {
+                self.client
+                    .balance_manager
+                    .withdraw_from_manager(ptb, &plan.manager_key, coin_key, *amount, recipient)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Plan and build a single sweep transaction for a balance manager
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    /// @param recipient - If set, withdraw the proceeds from the BalanceManager to this address
+    /// @returns None if no pool holds rebates or settled balances
+    pub async fn sweep(
+        &self,
+        manager_key: &str,
+        recipient: Option<SuiAddress>,
+    ) -> anyhow::Result<Option<(SweepPlan, ProgrammableTransaction)>> {
+        let plan = self.plan(manager_key).await?;
+        if plan.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        self.build(&mut ptb, &plan, recipient).await?;
+        Ok(Some((plan, ptb.finish())))
+    }
+}
+
+/// Default number of cancels per transaction for the ExpiredOrderSweeper
+pub const DEFAULT_CANCELS_PER_TRANSACTION: usize = 100;
+
+/// An open order past its `expire_timestamp`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredOrder {
+    pub pool_key: String,
+    pub order_id: OrderId,
+    pub expire_timestamp: u64,
+}
+
+/// Outcome of one ExpiredOrderSweeper sweep
+#[derive(Debug, Clone, Default)]
+pub struct ExpiredSweepReport {
+    /// Orders whose cancel transaction succeeded
+    pub canceled: Vec<ExpiredOrder>,
+    /// Batches whose cancel transaction could not be built or failed, with the error
+    pub failed: Vec<(Vec<ExpiredOrder>, String)>,
+}
+
+/// Cancels a balance manager's expired orders to release their locked balances
+///
+/// Expired orders stay in the book, holding their locked balance, until they are
+/// matched against or canceled.
+pub struct ExpiredOrderSweeper<'a> {
+    client: &'a DeepBookClient,
+    cancels_per_transaction: usize,
+}
+
+impl<'a> ExpiredOrderSweeper<'a> {
+    /// Creates a new ExpiredOrderSweeper
+    ///
+    /// @param client - The DeepBookClient used to read orders and build calls
+    pub fn new(client: &'a DeepBookClient) -> Self {
+        Self {
+            client,
+            cancels_per_transaction: DEFAULT_CANCELS_PER_TRANSACTION,
+        }
+    }
+
+    /// Set the maximum number of cancels per transaction
+    ///
+    /// @param cancels - The new maximum, at least one
+    pub fn with_cancels_per_transaction(mut self, cancels: usize) -> Self {
+        self.cancels_per_transaction = cancels.max(1);
+        self
+    }
+
+    /// Find the open orders of a balance manager that expired before the on-chain time
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    pub async fn find_expired(&self, manager_key: &str) -> anyhow::Result<Vec<ExpiredOrder>> {
+        let now = self.client.clock_timestamp_ms().await?;
+        let mut pool_keys: Vec<&str> = self.client.config().pools().keys().copied().collect();
+        pool_keys.sort_unstable();
+
+        let mut expired = Vec::new();
+        for pool_key in pool_keys {
+            if !self.client.account_exists(pool_key, manager_key).await? {
+                continue;
+            }
+            let order_ids = self
+                .client
+                .account_open_orders(pool_key, manager_key)
+                .await?;
+            if order_ids.is_empty() {
+                continue;
+            }
+
+            let orders = self
+                .client
+                .get_orders(pool_key, order_ids)
+                .await?
+                .unwrap_or_default();
+            expired.extend(
+                orders
+                    .into_iter()
+                    .filter(|order| order.expire_timestamp < now)
+                    .map(|order| ExpiredOrder {
+                        pool_key: pool_key.to_string(),
+                        order_id: order.order_id,
+                        expire_timestamp: order.expire_timestamp,
+                    }),
+            );
+        }
+        Ok(expired)
+    }
+
+    /// Build the cancel transactions for expired orders, one trade proof per transaction
+    ///
+    /// @param manager_key - The key of the BalanceManager owning the orders
+    /// @param orders - The ExpiredOrders to cancel
+    pub async fn build(
+        &self,
+        manager_key: &str,
+        orders: &[ExpiredOrder],
+    ) -> anyhow::Result<Vec<ProgrammableTransaction>> {
+        let mut transactions = Vec::new();
+        for batch in orders.chunks(self.cancels_per_transaction) {
+            transactions.push(self.build_batch(manager_key, batch).await?);
+        }
+        Ok(transactions)
+    }
+
+    async fn build_batch(
+        &self,
+        manager_key: &str,
+        batch: &[ExpiredOrder],
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        let config = self.client.config();
+        let balance_manager = config.balance_manager_ref(manager_key)?;
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let trade_proof = self
+            .client
+            .balance_manager
+            .generate_proof_by_ref(&mut ptb, &balance_manager)
+            .await?;
+        for order in batch {
+            let pool = config.pool_ref(&order.pool_key)?;
+            self.client
+                .deep_book
+                .cancel_order_with_proof(
+                    &mut ptb,
+                    &pool,
+                    &balance_manager,
+                    trade_proof,
+                    order.order_id,
+                )
+                .await?;
+        }
+        Ok(ptb.finish())
+    }
+
+    async fn cancel_batch(
+        &self,
+        manager_key: &str,
+        batch: &[ExpiredOrder],
+        executor: &dyn TransactionExecutor,
+    ) -> anyhow::Result<()> {
+        let transaction = self.build_batch(manager_key, batch).await?;
+        let response = executor.execute(transaction).await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Success) => Ok(()),
+            Some(SuiExecutionStatus::Failure { error }) => {
+                Err(anyhow::anyhow!("Cancel transaction failed: {}", error))
+            }
+            None => Err(anyhow::anyhow!("Transaction response has no effects")),
+        }
+    }
+
+    /// Find and cancel the expired orders of a balance manager
+    ///
+    /// A failed batch is recorded in the report and the remaining batches are still
+    /// submitted.
+    ///
+    /// @param manager_key - The key of the BalanceManager
+    /// @param executor - The TransactionExecutor submitting the cancels
+    /// @returns The ExpiredSweepReport, or the error finding the expired orders
+    pub async fn sweep(
+        &self,
+        manager_key: &str,
+        executor: &dyn TransactionExecutor,
+    ) -> anyhow::Result<ExpiredSweepReport> {
+        let expired = self.find_expired(manager_key).await?;
+        let mut report = ExpiredSweepReport::default();
+        for batch in expired.chunks(self.cancels_per_transaction) {
+            match self.cancel_batch(manager_key, batch, executor).await {
+                Ok(()) => report.canceled.extend_from_slice(batch),
+                Err(e) => report.failed.push((batch.to_vec(), e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Sweep repeatedly, passing the result of every sweep to `on_sweep`
+    ///
+    /// Errors do not stop the loop. While sweeps fail, the delay doubles from `interval`
+    /// up to `max_backoff`.
+    ///
+    /// @param interval - Time between successful sweeps
+    /// @param max_backoff - Longest delay after failed sweeps
+    /// @param manager_key - The key of the BalanceManager
+    /// @param executor - The TransactionExecutor submitting the cancels
+    /// @param on_sweep - Called with the result of each sweep
+    pub async fn run<F>(
+        &self,
+        interval: Duration,
+        max_backoff: Duration,
+        manager_key: &str,
+        executor: &dyn TransactionExecutor,
+        mut on_sweep: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut(&anyhow::Result<ExpiredSweepReport>),
+    {
+        let mut backoff = Backoff::new(interval, max_backoff);
+        loop {
+            let result = self.sweep(manager_key, executor).await;
+            let failed = match &result {
+                Ok(report) => !report.failed.is_empty(),
+                Err(_) => true,
+            };
+            let delay = if failed {
+                backoff.next_delay()
+            } else {
+                backoff.reset();
+                interval
+            };
+            on_sweep(&result);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+fn is_non_zero(balances: &Balances) -> bool {
+    balances.base > 0.0 || balances.quote > 0.0 || balances.deep > 0.0
+}
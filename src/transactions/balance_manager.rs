@@ -9,6 +9,7 @@ use sui_sdk::types::{Identifier, TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
 use sui_sdk::SuiClient;
 
 use crate::utils::config::DeepBookConfig;
+use crate::utils::types::BalanceManagerRef;
 
 use crate::DataReader;
 
@@ -193,22 +194,34 @@ impl BalanceManagerContract {
         manager_key: &str,
         coin_key: &str,
     ) -> anyhow::Result<Argument> {
-        let manager_address = self
-            .config
-            .get_balance_manager(manager_key)?
-            .address
-            .as_str();
-        let manager_id = ObjectID::from_hex_literal(manager_address)?;
-        let package_id = ObjectID::from_hex_literal(self.config.deepbook_package_id())?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
         let coin = self.config.get_coin(coin_key)?;
+        let coin_type = TypeTag::from_str(coin.type_name.as_str())?;
 
-        let arguments = vec![ptb.obj(self.client.share_object(manager_id).await?)?];
+        self.check_manager_balance_by_ref(ptb, &manager, coin_type)
+            .await
+    }
+
+    /// Check the balance of a BalanceManager addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param manager - The BalanceManagerRef to check
+    /// @param coin_type - The type tag of the coin
+    pub async fn check_manager_balance_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        manager: &BalanceManagerRef,
+        coin_type: TypeTag,
+    ) -> anyhow::Result<Argument> {
+        let package_id = ObjectID::from_hex_literal(self.config.deepbook_package_id())?;
+
+        let arguments = vec![ptb.obj(self.client.share_object(manager.id).await?)?];
 
         Ok(ptb.programmable_move_call(
             package_id,
             Identifier::new("balance_manager")?,
             Identifier::new("balance")?,
-            vec![TypeTag::from_str(coin.type_name.as_str())?],
+            vec![coin_type],
             arguments,
         ))
     }
@@ -222,18 +235,25 @@ impl BalanceManagerContract {
         ptb: &mut ProgrammableTransactionBuilder,
         manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let balance_manager = self.config.get_balance_manager(manager_key)?;
-        let manager_address = balance_manager.address.as_str();
-        let trade_cap = balance_manager.trade_cap.clone();
-        let manager_id = ObjectID::from_hex_literal(manager_address)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
+        self.generate_proof_by_ref(ptb, &manager).await
+    }
 
-        if let Some(trade_cap) = trade_cap {
-            let trade_cap_id = ObjectID::from_hex_literal(trade_cap.as_str())?;
-            Ok(self
-                .generate_proof_as_trader(ptb, &manager_id, &trade_cap_id)
-                .await?)
-        } else {
-            Ok(self.generate_proof_as_owner(ptb, &manager_id).await?)
+    /// Generate a trade proof for a BalanceManager addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param manager - The BalanceManagerRef to generate the proof for
+    pub async fn generate_proof_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
+        match &manager.trade_cap {
+            Some(trade_cap_id) => {
+                self.generate_proof_as_trader(ptb, &manager.id, trade_cap_id)
+                    .await
+            }
+            None => self.generate_proof_as_owner(ptb, &manager.id).await,
         }
     }
 
@@ -0,0 +1,267 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{Argument, ProgrammableTransaction};
+use sui_sdk::SuiClient;
+
+use crate::client::PoolBookParams;
+use crate::utils::client_order_id::ClientOrderIdGenerator;
+use crate::utils::config::DeepBookConfig;
+use crate::utils::types::{OrderType, PlaceLimitOrderParams, SelfMatchingOptions};
+
+use super::balance_manager::BalanceManagerContract;
+use super::deepbook::DeepBookContract;
+
+/// Default number of orders per transaction
+///
+/// A conservative choice rather than a protocol constant. Each order is one Move call,
+/// against Sui's limit of 1,024 commands per PTB, but transaction size and gas bound a
+/// batch first. Tune it with `set_max_orders_per_transaction` after measuring gas for
+/// the target pools.
+pub const DEFAULT_MAX_ORDERS_PER_TRANSACTION: usize = 100;
+
+/// Which sides of the book a grid quotes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridSide {
+    Bids,
+    Asks,
+    Both,
+}
+
+/// How order size changes with distance from the center price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SizeCurve {
+    /// Every level uses the base size
+    Flat,
+    /// Each level adds a fixed amount to the previous level's size
+    Linear(f64),
+    /// Each level multiplies the previous level's size by a ratio
+    Geometric(f64),
+}
+
+impl SizeCurve {
+    /// Size of a level, where level 0 is closest to the center
+    ///
+    /// @param base_size - The size of level 0
+    /// @param level - The level index
+    pub fn size(&self, base_size: f64, level: usize) -> f64 {
+        match self {
+            SizeCurve::Flat => base_size,
+            SizeCurve::Linear(step) => base_size + step * level as f64,
+            SizeCurve::Geometric(ratio) => base_size * ratio.powi(level as i32),
+        }
+    }
+}
+
+/// A ladder of limit orders around a center price
+#[derive(Debug, Clone)]
+pub struct GridSpec {
+    pub pool_key: String,
+    pub center_price: f64,
+    /// Distance between levels, in ticks
+    pub step_ticks: u64,
+    /// Number of levels on each quoted side
+    pub levels: usize,
+    pub base_size: f64,
+    pub size_curve: SizeCurve,
+    pub side: GridSide,
+    pub order_type: Option<OrderType>,
+    pub self_matching_option: Option<SelfMatchingOptions>,
+    pub pay_with_deep: Option<bool>,
+    pub expiration: Option<u64>,
+}
+
+impl GridSpec {
+    /// Expand the grid into limit orders aligned to the pool's tick and lot sizes
+    ///
+    /// Bids start one step below the center rounded down to a tick, asks one step above
+    /// the center rounded up to a tick. Levels whose size rounds below the pool's minimum
+    /// size, or whose price is not positive, are skipped.
+    ///
+    /// @param balance_manager_key - The key of the BalanceManager placing the orders
+    /// @param book - The pool's PoolBookParams
+    /// @param ids - Generator for the orders' client order IDs
+    pub fn orders(
+        &self,
+        balance_manager_key: &str,
+        book: &PoolBookParams,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<Vec<PlaceLimitOrderParams>> {
+        if book.tick_size <= 0.0 || book.lot_size <= 0.0 {
+            return Err(anyhow::anyhow!("Invalid book params for {}", self.pool_key));
+        }
+        if self.step_ticks == 0 {
+            return Err(anyhow::anyhow!("Grid step must be at least one tick"));
+        }
+
+        let center_ticks = self.center_price / book.tick_size;
+        let bid_start = (center_ticks + 1e-9).floor() as i64;
+        let ask_start = (center_ticks - 1e-9).ceil() as i64;
+        let step = self.step_ticks as i64;
+
+        let mut sides = Vec::new();
+        if self.side != GridSide::Asks {
+            sides.push(true);
+        }
+        if self.side != GridSide::Bids {
+            sides.push(false);
+        }
+
+        let mut orders = Vec::new();
+        for level in 0..self.levels {
+            for &is_bid in &sides {
+                let offset = step * (level as i64 + 1);
+                let ticks = if is_bid {
+                    bid_start - offset
+                } else {
+                    ask_start + offset
+                };
+                if ticks <= 0 {
+                    continue;
+                }
+
+                let size = self.size_curve.size(self.base_size, level);
+                let quantity = ((size / book.lot_size) + 1e-9).floor() * book.lot_size;
+                if quantity < book.min_size || quantity <= 0.0 {
+                    continue;
+                }
+
+                orders.push(PlaceLimitOrderParams {
+                    pool_key: self.pool_key.clone(),
+                    balance_manager_key: balance_manager_key.to_string(),
+                    client_order_id: ids.next_id()?,
+                    price: ticks as f64 * book.tick_size,
+                    quantity,
+                    is_bid,
+                    expiration: self.expiration,
+                    order_type: self.order_type,
+                    self_matching_option: self.self_matching_option,
+                    pay_with_deep: self.pay_with_deep,
+                });
+            }
+        }
+        Ok(orders)
+    }
+}
+
+/// BulkOrderContract struct for placing many limit orders with a single trade proof
+pub struct BulkOrderContract {
+    config: DeepBookConfig,
+    deep_book: DeepBookContract,
+    balance_manager_contract: BalanceManagerContract,
+    max_orders_per_transaction: usize,
+}
+
+impl BulkOrderContract {
+    /// Creates a new BulkOrderContract instance
+    ///
+    /// @param client - The SuiClient instance
+    /// @param config - The DeepBookConfig instance
+    /// @param balance_manager_contract - The BalanceManagerContract instance
+    pub fn new(
+        client: SuiClient,
+        config: DeepBookConfig,
+        balance_manager_contract: BalanceManagerContract,
+    ) -> Self {
+        Self {
+            deep_book: DeepBookContract::new(
+                client,
+                config.clone(),
+                balance_manager_contract.clone(),
+            ),
+            config,
+            balance_manager_contract,
+            max_orders_per_transaction: DEFAULT_MAX_ORDERS_PER_TRANSACTION,
+        }
+    }
+
+    /// Maximum number of orders `build_limit_orders` puts in one transaction
+    pub fn max_orders_per_transaction(&self) -> usize {
+        self.max_orders_per_transaction
+    }
+
+    /// Set the maximum number of orders per transaction
+    ///
+    /// @param max_orders - The new maximum, at least one
+    pub fn set_max_orders_per_transaction(&mut self, max_orders: usize) {
+        self.max_orders_per_transaction = max_orders.max(1);
+    }
+
+    /// Place limit orders in one PTB, generating the trade proof once
+    ///
+    /// The `balance_manager_key` field of each order is ignored in favour of `manager_key`.
+    /// Orders may target different pools.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param manager_key - The key of the BalanceManager placing the orders
+    /// @param orders - The orders to place
+    /// @returns The place limit order calls, in order
+    pub async fn place_limit_orders(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        manager_key: &str,
+        orders: Vec<PlaceLimitOrderParams>,
+    ) -> anyhow::Result<Vec<Argument>> {
+        if orders.len() > self.max_orders_per_transaction {
+            return Err(anyhow::anyhow!(
+                "{} orders exceed the limit of {} per transaction",
+                orders.len(),
+                self.max_orders_per_transaction
+            ));
+        }
+
+        let balance_manager = self.config.balance_manager_ref(manager_key)?;
+        let trade_proof = self
+            .balance_manager_contract
+            .generate_proof_by_ref(ptb, &balance_manager)
+            .await?;
+
+        let mut results = Vec::with_capacity(orders.len());
+        for order in orders {
+            let pool = self.config.pool_ref(&order.pool_key)?;
+            results.push(
+                self.deep_book
+                    .place_limit_order_with_proof(ptb, &pool, &balance_manager, trade_proof, order)
+                    .await?,
+            );
+        }
+        Ok(results)
+    }
+
+    /// Build as many transactions as needed to place all orders, each with one trade proof
+    ///
+    /// @param manager_key - The key of the BalanceManager placing the orders
+    /// @param orders - The orders to place
+    pub async fn build_limit_orders(
+        &self,
+        manager_key: &str,
+        orders: Vec<PlaceLimitOrderParams>,
+    ) -> anyhow::Result<Vec<ProgrammableTransaction>> {
+        let mut transactions = Vec::new();
+        for chunk in orders.chunks(self.max_orders_per_transaction) {
+            let mut ptb = ProgrammableTransactionBuilder::new();
+            self.place_limit_orders(&mut ptb, manager_key, chunk.to_vec())
+                .await?;
+            transactions.push(ptb.finish());
+        }
+        Ok(transactions)
+    }
+
+    /// Build the transactions placing every order of a grid
+    ///
+    /// @param manager_key - The key of the BalanceManager placing the orders
+    /// @param grid - The GridSpec to expand
+    /// @param book - The pool's PoolBookParams
+    /// @param ids - Generator for the orders' client order IDs
+    pub async fn build_grid(
+        &self,
+        manager_key: &str,
+        grid: &GridSpec,
+        book: &PoolBookParams,
+        ids: &(dyn ClientOrderIdGenerator + Sync),
+    ) -> anyhow::Result<Vec<ProgrammableTransaction>> {
+        let orders = grid.orders(manager_key, book, ids)?;
+        self.build_limit_orders(manager_key, orders).await
+    }
+}
@@ -11,9 +11,10 @@ use sui_sdk::{
 };
 
 use crate::utils::{
-    config::{DeepBookConfig, DEEP_SCALAR, FLOAT_SCALAR, MAX_TIMESTAMP},
+    config::{DeepBookConfig, DEEP_SCALAR, MAX_TIMESTAMP},
     types::{
-        OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams, SelfMatchingOptions, SwapParams,
+        BalanceManagerRef, OrderId, OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams,
+        PoolRef, SelfMatchingOptions, SwapParams,
     },
 };
 
@@ -56,25 +57,61 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         params: PlaceLimitOrderParams,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(&params.pool_key)?;
+        let pool = self.config.pool_ref(&params.pool_key)?;
         let balance_manager = self
             .config
-            .get_balance_manager(&params.balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+            .balance_manager_ref(&params.balance_manager_key)?;
 
-        let input_price = ((params.price * FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-            / base_coin.scalar as f64)
-            .round() as u64;
-        let input_quantity = (params.quantity * base_coin.scalar as f64).round() as u64;
+        self.place_limit_order_by_ref(ptb, &pool, &balance_manager, params)
+            .await
+    }
 
+    /// Place a limit order on a pool addressed by object ID
+    ///
+    /// The `pool_key` and `balance_manager_key` fields of `params` are ignored.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to place the order in
+    /// @param balance_manager - The BalanceManagerRef placing the order
+    /// @param params - The PlaceLimitOrderParams instance
+    /// @returns The place limit order call
+    pub async fn place_limit_order_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        params: PlaceLimitOrderParams,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, &params.balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
+        self.place_limit_order_with_proof(ptb, pool, balance_manager, trade_proof, params)
+            .await
+    }
+
+    /// Place a limit order using an already generated trade proof
+    ///
+    /// The `pool_key` and `balance_manager_key` fields of `params` are ignored.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to place the order in
+    /// @param balance_manager - The BalanceManagerRef placing the order
+    /// @param trade_proof - The trade proof generated for the balance manager
+    /// @param params - The PlaceLimitOrderParams instance
+    /// @returns The place limit order call
+    pub async fn place_limit_order_with_proof(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        trade_proof: Argument,
+        params: PlaceLimitOrderParams,
+    ) -> anyhow::Result<Argument> {
+        let input_price = pool.price_to_input(params.price);
+        let input_quantity = pool.base_to_input(params.quantity);
+
         let expiration = params.expiration.unwrap_or(MAX_TIMESTAMP);
         let order_type = params.order_type.unwrap_or(OrderType::NoRestriction);
         let self_matching_option = params
@@ -82,16 +119,13 @@ impl DeepBookContract {
             .unwrap_or(SelfMatchingOptions::SelfMatchingAllowed);
         let pay_with_deep = params.pay_with_deep.unwrap_or(true);
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
             ptb.pure(params.client_order_id)?,
-            ptb.pure(order_type as u8)?,
-            ptb.pure(self_matching_option as u8)?,
+            ptb.pure(u8::from(order_type))?,
+            ptb.pure(u8::from(self_matching_option))?,
             ptb.pure(input_price)?,
             ptb.pure(input_quantity)?,
             ptb.pure(params.is_bid)?,
@@ -104,7 +138,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("place_limit_order")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -119,35 +153,48 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         params: PlaceMarketOrderParams,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(&params.pool_key)?;
+        let pool = self.config.pool_ref(&params.pool_key)?;
         let balance_manager = self
             .config
-            .get_balance_manager(&params.balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+            .balance_manager_ref(&params.balance_manager_key)?;
 
-        let input_quantity = (params.quantity * base_coin.scalar as f64).round() as u64;
+        self.place_market_order_by_ref(ptb, &pool, &balance_manager, params)
+            .await
+    }
+
+    /// Place a market order on a pool addressed by object ID
+    ///
+    /// The `pool_key` and `balance_manager_key` fields of `params` are ignored.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to place the order in
+    /// @param balance_manager - The BalanceManagerRef placing the order
+    /// @param params - The PlaceMarketOrderParams instance
+    /// @returns The place market order call
+    pub async fn place_market_order_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        params: PlaceMarketOrderParams,
+    ) -> anyhow::Result<Argument> {
+        let input_quantity = pool.base_to_input(params.quantity);
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, &params.balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
         let self_matching_option = params
             .self_matching_option
             .unwrap_or(SelfMatchingOptions::SelfMatchingAllowed);
         let pay_with_deep = params.pay_with_deep.unwrap_or(true);
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
             ptb.pure(params.client_order_id)?,
-            ptb.pure(self_matching_option as u8)?,
+            ptb.pure(u8::from(self_matching_option))?,
             ptb.pure(input_quantity)?,
             ptb.pure(params.is_bid)?,
             ptb.pure(pay_with_deep)?,
@@ -158,7 +205,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("place_market_order")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -176,33 +223,72 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         balance_manager_key: &str,
-        order_id: &str,
+        order_id: OrderId,
         new_quantity: f64,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let balance_manager = self.config.get_balance_manager(balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let balance_manager = self.config.balance_manager_ref(balance_manager_key)?;
 
-        let input_quantity = (new_quantity * base_coin.scalar as f64).round() as u64;
+        self.modify_order_by_ref(ptb, &pool, &balance_manager, order_id, new_quantity)
+            .await
+    }
+
+    /// Modify an existing order on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the order
+    /// @param balance_manager - The BalanceManagerRef owning the order
+    /// @param order_id - The ID of the order to modify
+    /// @param new_quantity - The new quantity to set for the order
+    /// @returns The modify order call
+    pub async fn modify_order_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        order_id: OrderId,
+        new_quantity: f64,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
-        let order_id = ObjectID::from_hex_literal(order_id)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.modify_order_with_proof(
+            ptb,
+            pool,
+            balance_manager,
+            trade_proof,
+            order_id,
+            new_quantity,
+        )
+        .await
+    }
 
+    /// Modify an existing order using an already generated trade proof
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the order
+    /// @param balance_manager - The BalanceManagerRef owning the order
+    /// @param trade_proof - The trade proof generated for the balance manager
+    /// @param order_id - The ID of the order to modify
+    /// @param new_quantity - The new quantity to set for the order
+    /// @returns The modify order call
+    pub async fn modify_order_with_proof(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        trade_proof: Argument,
+        order_id: OrderId,
+        new_quantity: f64,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
-            ptb.pure(order_id)?,
-            ptb.pure(input_quantity)?,
+            ptb.pure(order_id.value())?,
+            ptb.pure(pool.base_to_input(new_quantity))?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
 
@@ -210,7 +296,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("modify_order")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -227,30 +313,59 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         balance_manager_key: &str,
-        order_id: &str,
+        order_id: OrderId,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let balance_manager = self.config.get_balance_manager(balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let balance_manager = self.config.balance_manager_ref(balance_manager_key)?;
 
+        self.cancel_order_by_ref(ptb, &pool, &balance_manager, order_id)
+            .await
+    }
+
+    /// Cancel an existing order on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the order
+    /// @param balance_manager - The BalanceManagerRef owning the order
+    /// @param order_id - The ID of the order to cancel
+    /// @returns The cancel order call
+    pub async fn cancel_order_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        order_id: OrderId,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
-        let order_id = ObjectID::from_hex_literal(order_id)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.cancel_order_with_proof(ptb, pool, balance_manager, trade_proof, order_id)
+            .await
+    }
 
+    /// Cancel an existing order using an already generated trade proof
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the order
+    /// @param balance_manager - The BalanceManagerRef owning the order
+    /// @param trade_proof - The trade proof generated for the balance manager
+    /// @param order_id - The ID of the order to cancel
+    /// @returns The cancel order call
+    pub async fn cancel_order_with_proof(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        trade_proof: Argument,
+        order_id: OrderId,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
-            ptb.pure(order_id)?,
+            ptb.pure(order_id.value())?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
 
@@ -258,7 +373,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("cancel_order")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -275,25 +390,33 @@ impl DeepBookContract {
         pool_key: &str,
         balance_manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let balance_manager = self.config.get_balance_manager(balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let balance_manager = self.config.balance_manager_ref(balance_manager_key)?;
+
+        self.cancel_all_orders_by_ref(ptb, &pool, &balance_manager)
+            .await
+    }
 
+    /// Cancel all open orders for a balance manager on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the orders
+    /// @param balance_manager - The BalanceManagerRef owning the orders
+    /// @returns The cancel all orders call
+    pub async fn cancel_all_orders_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
@@ -302,7 +425,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("cancel_all_orders")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -319,25 +442,51 @@ impl DeepBookContract {
         pool_key: &str,
         balance_manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let balance_manager = self.config.get_balance_manager(balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let balance_manager = self.config.balance_manager_ref(balance_manager_key)?;
 
+        self.withdraw_settled_amounts_by_ref(ptb, &pool, &balance_manager)
+            .await
+    }
+
+    /// Withdraw settled amounts for a balance manager on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the settled amounts
+    /// @param balance_manager - The BalanceManagerRef to settle into
+    /// @returns The withdraw settled amounts call
+    pub async fn withdraw_settled_amounts_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.withdraw_settled_amounts_with_proof(ptb, pool, balance_manager, trade_proof)
+            .await
+    }
 
+    /// Withdraw settled amounts using an already generated trade proof
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the settled amounts
+    /// @param balance_manager - The BalanceManagerRef to settle into
+    /// @param trade_proof - The trade proof generated for the balance manager
+    /// @returns The withdraw settled amounts call
+    pub async fn withdraw_settled_amounts_with_proof(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        trade_proof: Argument,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
         ];
 
@@ -345,7 +494,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("withdraw_settled_amounts")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -362,20 +511,32 @@ impl DeepBookContract {
         target_pool_key: &str,
         reference_pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let target_pool = self.config.get_pool(target_pool_key)?;
-        let reference_pool = self.config.get_pool(reference_pool_key)?;
-
-        let target_base_coin = self.config.get_coin(&target_pool.base_coin)?;
-        let target_quote_coin = self.config.get_coin(&target_pool.quote_coin)?;
-        let reference_base_coin = self.config.get_coin(&reference_pool.base_coin)?;
-        let reference_quote_coin = self.config.get_coin(&reference_pool.quote_coin)?;
+        let target_pool = self.config.pool_ref(target_pool_key)?;
+        let reference_pool = self.config.pool_ref(reference_pool_key)?;
 
-        let target_pool_id = ObjectID::from_hex_literal(&target_pool.address)?;
-        let reference_pool_id = ObjectID::from_hex_literal(&reference_pool.address)?;
+        self.add_deep_price_point_by_ref(ptb, &target_pool, &reference_pool)
+            .await
+    }
 
+    /// Add a deep price point for a target pool using a reference pool, both addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param target_pool - The PoolRef of the target pool
+    /// @param reference_pool - The PoolRef of the reference pool
+    /// @returns The add deep price point call
+    pub async fn add_deep_price_point_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        target_pool: &PoolRef,
+        reference_pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(target_pool_id).await?)?,
-            ptb.obj(self.client.share_object(reference_pool_id).await?)?,
+            ptb.obj(
+                self.client
+                    .share_object_mutable(target_pool.pool_id)
+                    .await?,
+            )?,
+            ptb.obj(self.client.share_object(reference_pool.pool_id).await?)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
 
@@ -384,10 +545,10 @@ impl DeepBookContract {
             Identifier::new("pool")?,
             Identifier::new("add_deep_price_point")?,
             vec![
-                TypeTag::from_str(&target_base_coin.type_name)?,
-                TypeTag::from_str(&target_quote_coin.type_name)?,
-                TypeTag::from_str(&reference_base_coin.type_name)?,
-                TypeTag::from_str(&reference_quote_coin.type_name)?,
+                target_pool.base_type.clone(),
+                target_pool.quote_type.clone(),
+                reference_pool.base_type.clone(),
+                reference_pool.quote_type.clone(),
             ],
             arguments,
         ))
@@ -405,25 +566,51 @@ impl DeepBookContract {
         pool_key: &str,
         balance_manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let balance_manager = self.config.get_balance_manager(balance_manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let balance_manager = self.config.balance_manager_ref(balance_manager_key)?;
+
+        self.claim_rebates_by_ref(ptb, &pool, &balance_manager)
+            .await
+    }
 
+    /// Claim rebates for a balance manager on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the rebates
+    /// @param balance_manager - The BalanceManagerRef claiming the rebates
+    /// @returns The claim rebates call
+    pub async fn claim_rebates_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let trade_proof = self
             .balance_manager_contract
-            .generate_proof(ptb, balance_manager_key)
+            .generate_proof_by_ref(ptb, balance_manager)
             .await?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let balance_manager_id = ObjectID::from_hex_literal(&balance_manager.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.claim_rebates_with_proof(ptb, pool, balance_manager, trade_proof)
+            .await
+    }
 
+    /// Claim rebates using an already generated trade proof
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the rebates
+    /// @param balance_manager - The BalanceManagerRef claiming the rebates
+    /// @param trade_proof - The trade proof generated for the balance manager
+    /// @returns The claim rebates call
+    pub async fn claim_rebates_with_proof(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        balance_manager: &BalanceManagerRef,
+        trade_proof: Argument,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(balance_manager_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(balance_manager.id).await?)?,
             trade_proof,
         ];
 
@@ -431,7 +618,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("claim_rebates")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -446,27 +633,34 @@ impl DeepBookContract {
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
-        order_id: u128,
+        order_id: OrderId,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_order_by_ref(ptb, &pool, order_id).await
+    }
 
+    /// Gets an order from a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the order
+    /// @param order_id - The ID of the order to get
+    /// @returns The order
+    pub async fn get_order_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        order_id: OrderId,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.pure(order_id)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
+            ptb.pure(order_id.value())?,
         ];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_order")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -481,23 +675,28 @@ impl DeepBookContract {
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
-        order_ids: Vec<String>,
+        order_ids: Vec<OrderId>,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let order_ids: Vec<ObjectID> = order_ids
-            .iter()
-            .map(|id| ObjectID::from_hex_literal(id))
-            .collect::<Result<Vec<_>, _>>()?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_orders_by_ref(ptb, &pool, order_ids).await
+    }
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+    /// Gets multiple orders from a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef holding the orders
+    /// @param order_ids - Array of order IDs to retrieve
+    /// @returns The orders
+    pub async fn get_orders_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        order_ids: Vec<OrderId>,
+    ) -> anyhow::Result<Argument> {
+        let order_ids: Vec<u128> = order_ids.iter().map(OrderId::value).collect();
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(order_ids)?,
         ];
 
@@ -505,7 +704,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_orders")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -520,18 +719,24 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.burn_deep_by_ref(ptb, &pool).await
+    }
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
+    /// Burns DEEP tokens from a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to burn from
+    /// @returns The burn deep call
+    pub async fn burn_deep_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
         let treasury_id = ObjectID::from_hex_literal(self.config.deep_treasury_id())?;
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.obj(self.client.share_object(treasury_id).await?)?,
         ];
 
@@ -539,7 +744,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("burn_deep")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -554,17 +759,22 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.mid_price_by_ref(ptb, &pool).await
+    }
 
+    /// Gets the mid price for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The mid price
+    pub async fn mid_price_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
 
@@ -572,7 +782,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("mid_price")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -587,22 +797,27 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.whitelisted_by_ref(ptb, &pool).await
+    }
 
-        let arguments = vec![ptb.obj(self.client.share_object(pool_id).await?)?];
+    /// Checks if a pool addressed by object ID is whitelisted
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The whitelisted status
+    pub async fn whitelisted_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![ptb.obj(self.client.share_object(pool.pool_id).await?)?];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("whitelisted")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -619,18 +834,27 @@ impl DeepBookContract {
         pool_key: &str,
         base_quantity: f64,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let input_quantity = (base_quantity * base_coin.scalar as f64).round() as u64;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_quote_quantity_out_by_ref(ptb, &pool, base_quantity)
+            .await
+    }
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+    /// Gets the quote quantity out for a given base quantity in, on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param base_quantity - Base quantity to convert
+    /// @returns The quote quantity out
+    pub async fn get_quote_quantity_out_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        base_quantity: f64,
+    ) -> anyhow::Result<Argument> {
+        let input_quantity = pool.base_to_input(base_quantity);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(input_quantity)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
@@ -639,7 +863,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_quote_quantity_out")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -656,18 +880,27 @@ impl DeepBookContract {
         pool_key: &str,
         quote_quantity: f64,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let input_quantity = (quote_quantity * quote_coin.scalar as f64).round() as u64;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_base_quantity_out_by_ref(ptb, &pool, quote_quantity)
+            .await
+    }
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+    /// Gets the base quantity out for a given quote quantity in, on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param quote_quantity - Quote quantity to convert
+    /// @returns The base quantity out
+    pub async fn get_base_quantity_out_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        quote_quantity: f64,
+    ) -> anyhow::Result<Argument> {
+        let input_quantity = pool.quote_to_input(quote_quantity);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(input_quantity)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
@@ -676,7 +909,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_base_quantity_out")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -695,19 +928,30 @@ impl DeepBookContract {
         base_quantity: f64,
         quote_quantity: f64,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let base_input = (base_quantity * base_coin.scalar as f64).round() as u64;
-        let quote_input = (quote_quantity * quote_coin.scalar as f64).round() as u64;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_quantity_out_by_ref(ptb, &pool, base_quantity, quote_quantity)
+            .await
+    }
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+    /// Gets the quantity out for a given base or quote quantity, on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param base_quantity - Base quantity to convert
+    /// @param quote_quantity - Quote quantity to convert
+    /// @returns The quantity out
+    pub async fn get_quantity_out_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        base_quantity: f64,
+        quote_quantity: f64,
+    ) -> anyhow::Result<Argument> {
+        let base_input = pool.base_to_input(base_quantity);
+        let quote_input = pool.quote_to_input(quote_quantity);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(base_input)?,
             ptb.pure(quote_input)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
@@ -717,7 +961,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_quantity_out")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -734,27 +978,34 @@ impl DeepBookContract {
         pool_key: &str,
         manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let manager = self.config.get_balance_manager(manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
 
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let manager_id = ObjectID::from_hex_literal(&manager.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.account_open_orders_by_ref(ptb, &pool, &manager).await
+    }
 
+    /// Gets open orders for a balance manager in a pool, both addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef owning the orders
+    /// @returns The open orders
+    pub async fn account_open_orders_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(manager_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object(manager.id).await?)?,
         ];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("account_open_orders")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -775,23 +1026,32 @@ impl DeepBookContract {
         price_high: f64,
         is_bid: bool,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let low_price = ((price_low * FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-            / base_coin.scalar as f64)
-            .round() as u64;
-        let high_price = ((price_high * FLOAT_SCALAR as f64 * quote_coin.scalar as f64)
-            / base_coin.scalar as f64)
-            .round() as u64;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_level2_range_by_ref(ptb, &pool, price_low, price_high, is_bid)
+            .await
+    }
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+    /// Gets level 2 order book specifying range of price, on a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param price_low - Lower bound of the price range
+    /// @param price_high - Upper bound of the price range
+    /// @param is_bid - Whether to get bid or ask orders
+    /// @returns The level 2 order book ticks from mid-price
+    pub async fn get_level2_range_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        price_low: f64,
+        price_high: f64,
+        is_bid: bool,
+    ) -> anyhow::Result<Argument> {
+        let low_price = pool.price_to_input(price_low);
+        let high_price = pool.price_to_input(price_high);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(low_price)?,
             ptb.pure(high_price)?,
             ptb.pure(is_bid)?,
@@ -802,7 +1062,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_level2_range")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -819,17 +1079,25 @@ impl DeepBookContract {
         pool_key: &str,
         tick_from_mid: u64,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_level2_ticks_from_mid_by_ref(ptb, &pool, tick_from_mid)
+            .await
+    }
 
+    /// Gets level 2 order book ticks from mid-price for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param tick_from_mid - Number of ticks from mid-price
+    /// @returns The level 2 order book ticks from mid-price
+    pub async fn get_level2_ticks_from_mid_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        tick_from_mid: u64,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.pure(tick_from_mid)?,
             ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
         ];
@@ -838,7 +1106,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_level2_ticks_from_mid")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -853,22 +1121,27 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.vault_balances_by_ref(ptb, &pool).await
+    }
 
-        let arguments = vec![ptb.obj(self.client.share_object(pool_id).await?)?];
+    /// Gets the vault balances for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The vault balances
+    pub async fn vault_balances_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![ptb.obj(self.client.share_object(pool.pool_id).await?)?];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("vault_balances")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -909,6 +1182,24 @@ impl DeepBookContract {
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         params: SwapParams,
+    ) -> anyhow::Result<()> {
+        let pool = self.config.pool_ref(&params.pool_key)?;
+        self.swap_exact_base_for_quote_by_ref(ptb, &pool, params)
+            .await
+    }
+
+    /// Swap exact base amount for quote amount on a pool addressed by object ID
+    ///
+    /// The `pool_key` field of `params` is ignored.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to swap through
+    /// @param params - Parameters for the swap
+    pub async fn swap_exact_base_for_quote_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        params: SwapParams,
     ) -> anyhow::Result<()> {
         if params.quote_coin.is_some() {
             return Err(anyhow::anyhow!(
@@ -916,25 +1207,21 @@ impl DeepBookContract {
             ));
         }
 
-        let pool = self.config.get_pool(&params.pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
         let deep_coin = self.config.get_coin("DEEP")?;
 
-        let base_amount = (params.amount * base_coin.scalar as f64).round() as u64;
+        let base_amount = pool.base_to_input(params.amount);
         let deep_amount = (params.deep_amount * DEEP_SCALAR as f64).round() as u64;
-        let min_quote = (params.min_out * quote_coin.scalar as f64).round() as u64;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let min_quote = pool.quote_to_input(params.min_out);
 
         let base_coin = match params.base_coin {
             Some(coin) => coin,
             None => {
                 self.client
-                    .get_coin_object(params.sender, base_coin.type_name.clone(), base_amount)
+                    .get_coin_object(
+                        params.sender,
+                        pool.base_type.to_canonical_string(true),
+                        base_amount,
+                    )
                     .await?
             }
         };
@@ -949,7 +1236,7 @@ impl DeepBookContract {
         };
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.obj(self.client.coin_object(base_coin).await?)?,
             ptb.obj(self.client.coin_object(deep_coin).await?)?,
             ptb.pure(min_quote)?,
@@ -960,7 +1247,7 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("swap_exact_base_for_quote")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         );
 
@@ -975,6 +1262,24 @@ impl DeepBookContract {
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         params: SwapParams,
+    ) -> anyhow::Result<()> {
+        let pool = self.config.pool_ref(&params.pool_key)?;
+        self.swap_exact_quote_for_base_by_ref(ptb, &pool, params)
+            .await
+    }
+
+    /// Swap exact quote amount for base amount on a pool addressed by object ID
+    ///
+    /// The `pool_key` field of `params` is ignored.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to swap through
+    /// @param params - Parameters for the swap
+    pub async fn swap_exact_quote_for_base_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        params: SwapParams,
     ) -> anyhow::Result<()> {
         if params.base_coin.is_some() {
             return Err(anyhow::anyhow!(
@@ -982,25 +1287,21 @@ impl DeepBookContract {
             ));
         }
 
-        let pool = self.config.get_pool(&params.pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
         let deep_coin = self.config.get_coin("DEEP")?;
 
-        let quote_amount = (params.amount * quote_coin.scalar as f64).round() as u64;
+        let quote_amount = pool.quote_to_input(params.amount);
         let deep_amount = (params.deep_amount * DEEP_SCALAR as f64).round() as u64;
-        let min_base = (params.min_out * base_coin.scalar as f64).round() as u64;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
+        let min_base = pool.base_to_input(params.min_out);
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
-
-        let quote_coin = match params.base_coin {
+        let quote_coin = match params.quote_coin {
             Some(coin) => coin,
             None => {
                 self.client
-                    .get_coin_object(params.sender, quote_coin.type_name.clone(), quote_amount)
+                    .get_coin_object(
+                        params.sender,
+                        pool.quote_type.to_canonical_string(true),
+                        quote_amount,
+                    )
                     .await?
             }
         };
@@ -1015,7 +1316,7 @@ impl DeepBookContract {
         };
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
             ptb.obj(self.client.coin_object(quote_coin).await?)?,
             ptb.obj(self.client.coin_object(deep_coin).await?)?,
             ptb.pure(min_base)?,
@@ -1026,13 +1327,83 @@ impl DeepBookContract {
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("swap_exact_quote_for_base")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         );
 
         Ok(())
     }
 
+    /// Swap exact base amount for quote amount using coins already in the PTB
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to swap through
+    /// @param base_coin - The base coin to swap
+    /// @param deep_coin - The DEEP coin used to pay fees
+    /// @param min_quote - Minimum quote amount to receive
+    /// @returns A tuple of the base coin remainder, the quote coin out and the DEEP coin remainder
+    pub async fn swap_exact_base_for_quote_with_coins(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        base_coin: Argument,
+        deep_coin: Argument,
+        min_quote: f64,
+    ) -> anyhow::Result<(Argument, Argument, Argument)> {
+        let arguments = vec![
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            base_coin,
+            deep_coin,
+            ptb.pure(pool.quote_to_input(min_quote))?,
+            ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
+        ];
+
+        let result = ptb.programmable_move_call(
+            ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
+            Identifier::new("pool")?,
+            Identifier::new("swap_exact_base_for_quote")?,
+            pool.type_args(),
+            arguments,
+        );
+
+        split_swap_result(result)
+    }
+
+    /// Swap exact quote amount for base amount using coins already in the PTB
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to swap through
+    /// @param quote_coin - The quote coin to swap
+    /// @param deep_coin - The DEEP coin used to pay fees
+    /// @param min_base - Minimum base amount to receive
+    /// @returns A tuple of the base coin out, the quote coin remainder and the DEEP coin remainder
+    pub async fn swap_exact_quote_for_base_with_coins(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        quote_coin: Argument,
+        deep_coin: Argument,
+        min_base: f64,
+    ) -> anyhow::Result<(Argument, Argument, Argument)> {
+        let arguments = vec![
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            quote_coin,
+            deep_coin,
+            ptb.pure(pool.base_to_input(min_base))?,
+            ptb.obj(self.client.share_object(SUI_CLOCK_OBJECT_ID).await?)?,
+        ];
+
+        let result = ptb.programmable_move_call(
+            ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
+            Identifier::new("pool")?,
+            Identifier::new("swap_exact_quote_for_base")?,
+            pool.type_args(),
+            arguments,
+        );
+
+        split_swap_result(result)
+    }
+
     /// Get the trade parameters for a given pool
     ///
     /// @param ptb - ProgrammableTransactionBuilder instance
@@ -1043,22 +1414,27 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.pool_trade_params_by_ref(ptb, &pool).await
+    }
 
-        let arguments = vec![ptb.obj(self.client.share_object(pool_id).await?)?];
+    /// Get the trade parameters for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The trade parameters
+    pub async fn pool_trade_params_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![ptb.obj(self.client.share_object(pool.pool_id).await?)?];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("pool_trade_params")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -1073,22 +1449,27 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.pool_book_params_by_ref(ptb, &pool).await
+    }
 
-        let arguments = vec![ptb.obj(self.client.share_object(pool_id).await?)?];
+    /// Get the book parameters for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The book parameters
+    pub async fn pool_book_params_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![ptb.obj(self.client.share_object(pool.pool_id).await?)?];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("pool_book_params")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -1105,27 +1486,60 @@ impl DeepBookContract {
         pool_key: &str,
         manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let manager = self.config.get_balance_manager(manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let manager_id = ObjectID::from_hex_literal(&manager.address)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.account_by_ref(ptb, &pool, &manager).await
+    }
 
+    /// Get the account information for a pool and balance manager addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    /// @returns The account information
+    pub async fn account_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(manager_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object(manager.id).await?)?,
         ];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("account")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
+            arguments,
+        ))
+    }
+
+    /// Check whether a balance manager has an account in a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    /// @returns Whether the account exists
+    pub async fn account_exists_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object(manager.id).await?)?,
+        ];
+
+        Ok(ptb.programmable_move_call(
+            ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
+            Identifier::new("pool")?,
+            Identifier::new("account_exists")?,
+            pool.type_args(),
             arguments,
         ))
     }
@@ -1142,27 +1556,34 @@ impl DeepBookContract {
         pool_key: &str,
         manager_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let manager = self.config.get_balance_manager(manager_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-        let manager_id = ObjectID::from_hex_literal(&manager.address)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        let manager = self.config.balance_manager_ref(manager_key)?;
 
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        self.locked_balance_by_ref(ptb, &pool, &manager).await
+    }
 
+    /// Get the locked balance for a pool and balance manager addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @param manager - The BalanceManagerRef to query
+    /// @returns The locked balance
+    pub async fn locked_balance_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        manager: &BalanceManagerRef,
+    ) -> anyhow::Result<Argument> {
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.obj(self.client.share_object(manager_id).await?)?,
+            ptb.obj(self.client.share_object(pool.pool_id).await?)?,
+            ptb.obj(self.client.share_object(manager.id).await?)?,
         ];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("locked_balance")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
@@ -1177,23 +1598,40 @@ impl DeepBookContract {
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-
-        let pool_id = ObjectID::from_hex_literal(&pool.address)?;
-
-        let base_coin_tag = TypeTag::from_str(&base_coin.type_name)?;
-        let quote_coin_tag = TypeTag::from_str(&quote_coin.type_name)?;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.get_pool_deep_price_by_ref(ptb, &pool).await
+    }
 
-        let arguments = vec![ptb.obj(self.client.share_object(pool_id).await?)?];
+    /// Get the DEEP price conversion for a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to query
+    /// @returns The DEEP price conversion
+    pub async fn get_pool_deep_price_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+    ) -> anyhow::Result<Argument> {
+        let arguments = vec![ptb.obj(self.client.share_object(pool.pool_id).await?)?];
 
         Ok(ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("get_order_deep_price")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         ))
     }
 }
+
+/// Split the `(Coin<Base>, Coin<Quote>, Coin<DEEP>)` tuple returned by a swap into its nested results
+fn split_swap_result(result: Argument) -> anyhow::Result<(Argument, Argument, Argument)> {
+    match result {
+        Argument::Result(idx) => Ok((
+            Argument::NestedResult(idx, 0),
+            Argument::NestedResult(idx, 1),
+            Argument::NestedResult(idx, 2),
+        )),
+        _ => Err(anyhow::anyhow!("Unexpected swap result")),
+    }
+}
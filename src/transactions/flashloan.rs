@@ -1,21 +1,27 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
-
 use crate::utils::config::DeepBookConfig;
+use crate::utils::types::PoolRef;
 use sui_sdk::{
     types::{
-        base_types::{ObjectID, ObjectRef},
+        base_types::ObjectID,
         programmable_transaction_builder::ProgrammableTransactionBuilder,
-        transaction::{Argument, Command, ObjectArg},
-        Identifier, TypeTag,
+        transaction::{Argument, Command},
+        Identifier,
     },
     SuiClient,
 };
 
 use crate::DataReader;
 
+/// The pool asset borrowed by a flash loan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlashLoanAsset {
+    Base,
+    Quote,
+}
+
 /// FlashLoanContract struct for managing flash loans.
 pub struct FlashLoanContract {
     client: SuiClient,
@@ -27,45 +33,89 @@ impl FlashLoanContract {
     ///
     /// @param client - SuiClient instance
     /// @param config - Configuration object for DeepBook
-    /// @param balance_manager_contract - BalanceManagerContract instance
     pub fn new(client: SuiClient, config: DeepBookConfig) -> Self {
         Self { client, config }
     }
 
+    /// Start a flash loan session that borrows from and repays to the same pool
+    ///
+    /// @param pool_key - The key to identify the pool
+    /// @param asset - Whether to borrow the base or the quote asset
+    /// @param borrow_amount - The amount to borrow
+    pub fn session(
+        &self,
+        pool_key: &str,
+        asset: FlashLoanAsset,
+        borrow_amount: f64,
+    ) -> anyhow::Result<FlashLoanSession<'_>> {
+        let pool = self.config.pool_ref(pool_key)?;
+        Ok(self.session_by_ref(pool, asset, borrow_amount))
+    }
+
+    /// Start a flash loan session on a pool addressed by object ID
+    ///
+    /// @param pool - The PoolRef to borrow from
+    /// @param asset - Whether to borrow the base or the quote asset
+    /// @param borrow_amount - The amount to borrow
+    pub fn session_by_ref(
+        &self,
+        pool: PoolRef,
+        asset: FlashLoanAsset,
+        borrow_amount: f64,
+    ) -> FlashLoanSession<'_> {
+        FlashLoanSession {
+            contract: self,
+            pool,
+            asset,
+            borrow_amount,
+        }
+    }
+
     /// Borrow base asset from the pool
     ///
     /// @param ptb - ProgrammableTransactionBuilder instance
     /// @param pool_key - The key to identify the pool
     /// @param borrow_amount - The amount to borrow
     /// @returns A tuple containing the base coin result and flash loan object
-    pub async fn borrow_base_asset<'a>(
+    pub async fn borrow_base_asset(
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         borrow_amount: f64,
-    ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-        let input_quantity = (borrow_amount * base_coin.scalar as f64).round() as u64;
-
-        let pool_id = ObjectID::from_hex_literal(pool.address.as_str())?;
+    ) -> anyhow::Result<(Argument, Argument)> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.borrow_base_asset_by_ref(ptb, &pool, borrow_amount)
+            .await
+    }
 
-        let base_coin_tag = TypeTag::from_str(base_coin.type_name.as_str())?;
-        let quote_coin_tag = TypeTag::from_str(quote_coin.type_name.as_str())?;
+    /// Borrow base asset from a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to borrow from
+    /// @param borrow_amount - The amount to borrow
+    /// @returns A tuple containing the base coin result and flash loan object
+    pub async fn borrow_base_asset_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        borrow_amount: f64,
+    ) -> anyhow::Result<(Argument, Argument)> {
+        let input_quantity = pool.base_to_input(borrow_amount);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
             ptb.pure(input_quantity)?,
         ];
 
-        Ok(ptb.programmable_move_call(
+        let result = ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("borrow_flashloan_base")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
-        ))
+        );
+
+        split_borrow_result(result)
     }
 
     /// Return base asset to the pool after a flash loan
@@ -74,43 +124,56 @@ impl FlashLoanContract {
     /// @param pool_key - The key to identify the pool
     /// @param borrow_amount - The amount of the base asset to return
     /// @param base_coin_input - Coin object representing the base asset to be returned
-    /// @param flash_loan - FlashLoan object representing the loan to be settled
-    pub async fn return_base_asset<'a>(
+    /// @param flash_loan - FlashLoan result returned by `borrow_base_asset`
+    /// @returns The remainder of `base_coin_input` after repayment
+    pub async fn return_base_asset(
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         borrow_amount: f64,
         base_coin_input: Argument,
-        flash_loan: ObjectRef,
+        flash_loan: Argument,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-        let borrow_scalar = base_coin.scalar;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.return_base_asset_by_ref(ptb, &pool, borrow_amount, base_coin_input, flash_loan)
+            .await
+    }
 
-        let return_amount = ptb.pure((borrow_amount * borrow_scalar as f64).round() as u64)?;
+    /// Return base asset to a pool addressed by object ID after a flash loan
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef the loan was borrowed from
+    /// @param borrow_amount - The amount of the base asset to return
+    /// @param base_coin_input - Coin object representing the base asset to be returned
+    /// @param flash_loan - FlashLoan result returned by `borrow_base_asset`
+    /// @returns The remainder of `base_coin_input` after repayment
+    pub async fn return_base_asset_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        borrow_amount: f64,
+        base_coin_input: Argument,
+        flash_loan: Argument,
+    ) -> anyhow::Result<Argument> {
+        let return_amount = ptb.pure(pool.base_to_input(borrow_amount))?;
         let base_coin_return =
             ptb.command(Command::SplitCoins(base_coin_input, vec![return_amount]));
 
-        let pool_id = ObjectID::from_hex_literal(pool.address.as_str())?;
-        let base_coin_tag = TypeTag::from_str(base_coin.type_name.as_str())?;
-        let quote_coin_tag = TypeTag::from_str(quote_coin.type_name.as_str())?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.pure(base_coin_return)?,
-            ptb.obj(ObjectArg::ImmOrOwnedObject(flash_loan))?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            base_coin_return,
+            flash_loan,
         ];
 
         ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("return_flashloan_base")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
         );
 
-        Ok(base_coin_return)
+        Ok(base_coin_input)
     }
 
     /// Borrow quote asset from the pool
@@ -119,33 +182,45 @@ impl FlashLoanContract {
     /// @param pool_key - The key to identify the pool
     /// @param borrow_amount - The amount to borrow
     /// @returns A tuple containing the quote coin result and flash loan object
-    pub async fn borrow_quote_asset<'a>(
+    pub async fn borrow_quote_asset(
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         borrow_amount: f64,
-    ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-        let input_quantity = (borrow_amount * quote_coin.scalar as f64).round() as u64;
+    ) -> anyhow::Result<(Argument, Argument)> {
+        let pool = self.config.pool_ref(pool_key)?;
+        self.borrow_quote_asset_by_ref(ptb, &pool, borrow_amount)
+            .await
+    }
 
-        let pool_id = ObjectID::from_hex_literal(pool.address.as_str())?;
-        let base_coin_tag = TypeTag::from_str(base_coin.type_name.as_str())?;
-        let quote_coin_tag = TypeTag::from_str(quote_coin.type_name.as_str())?;
+    /// Borrow quote asset from a pool addressed by object ID
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef to borrow from
+    /// @param borrow_amount - The amount to borrow
+    /// @returns A tuple containing the quote coin result and flash loan object
+    pub async fn borrow_quote_asset_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        borrow_amount: f64,
+    ) -> anyhow::Result<(Argument, Argument)> {
+        let input_quantity = pool.quote_to_input(borrow_amount);
 
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
             ptb.pure(input_quantity)?,
         ];
 
-        Ok(ptb.programmable_move_call(
+        let result = ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("borrow_flashloan_quote")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
-        ))
+        );
+
+        split_borrow_result(result)
     }
 
     /// Return quote asset to the pool after a flash loan
@@ -154,40 +229,145 @@ impl FlashLoanContract {
     /// @param pool_key - The key to identify the pool
     /// @param borrow_amount - The amount of the quote asset to return
     /// @param quote_coin_input - Coin object representing the quote asset to be returned
-    /// @param flash_loan - FlashLoan object representing the loan to be settled
-    pub async fn return_quote_asset<'a>(
+    /// @param flash_loan - FlashLoan result returned by `borrow_quote_asset`
+    /// @returns The remainder of `quote_coin_input` after repayment
+    pub async fn return_quote_asset(
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool_key: &str,
         borrow_amount: f64,
         quote_coin_input: Argument,
-        flash_loan: ObjectRef,
+        flash_loan: Argument,
     ) -> anyhow::Result<Argument> {
-        let pool = self.config.get_pool(pool_key)?;
-        let base_coin = self.config.get_coin(&pool.base_coin)?;
-        let quote_coin = self.config.get_coin(&pool.quote_coin)?;
-        let borrow_scalar = quote_coin.scalar;
+        let pool = self.config.pool_ref(pool_key)?;
+        self.return_quote_asset_by_ref(ptb, &pool, borrow_amount, quote_coin_input, flash_loan)
+            .await
+    }
 
-        let return_amount = ptb.pure((borrow_amount * borrow_scalar as f64).round() as u64)?;
+    /// Return quote asset to a pool addressed by object ID after a flash loan
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param pool - The PoolRef the loan was borrowed from
+    /// @param borrow_amount - The amount of the quote asset to return
+    /// @param quote_coin_input - Coin object representing the quote asset to be returned
+    /// @param flash_loan - FlashLoan result returned by `borrow_quote_asset`
+    /// @returns The remainder of `quote_coin_input` after repayment
+    pub async fn return_quote_asset_by_ref(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        pool: &PoolRef,
+        borrow_amount: f64,
+        quote_coin_input: Argument,
+        flash_loan: Argument,
+    ) -> anyhow::Result<Argument> {
+        let return_amount = ptb.pure(pool.quote_to_input(borrow_amount))?;
         let quote_coin_return =
             ptb.command(Command::SplitCoins(quote_coin_input, vec![return_amount]));
 
-        let pool_id = ObjectID::from_hex_literal(pool.address.as_str())?;
-        let base_coin_tag = TypeTag::from_str(base_coin.type_name.as_str())?;
-        let quote_coin_tag = TypeTag::from_str(quote_coin.type_name.as_str())?;
-
         let arguments = vec![
-            ptb.obj(self.client.share_object(pool_id).await?)?,
-            ptb.pure(quote_coin_return)?,
-            ptb.obj(ObjectArg::ImmOrOwnedObject(flash_loan))?,
+            ptb.obj(self.client.share_object_mutable(pool.pool_id).await?)?,
+            quote_coin_return,
+            flash_loan,
         ];
 
-        Ok(ptb.programmable_move_call(
+        ptb.programmable_move_call(
             ObjectID::from_hex_literal(self.config.deepbook_package_id())?,
             Identifier::new("pool")?,
             Identifier::new("return_flashloan_quote")?,
-            vec![base_coin_tag, quote_coin_tag],
+            pool.type_args(),
             arguments,
-        ))
+        );
+
+        Ok(quote_coin_input)
+    }
+}
+
+/// A flash loan scoped to a single PTB.
+///
+/// The `FlashLoan` hot potato never leaves the session: `run` borrows, hands the
+/// borrowed coin to the caller, and always appends the matching return call, so a
+/// PTB built through a session cannot forget to repay the loan.
+pub struct FlashLoanSession<'a> {
+    contract: &'a FlashLoanContract,
+    pool: PoolRef,
+    asset: FlashLoanAsset,
+    borrow_amount: f64,
+}
+
+impl FlashLoanSession<'_> {
+    /// Borrow, run `body` with the borrowed coin, then repay the loan
+    ///
+    /// `body` adds its commands to the builder and returns the coin to repay from. The
+    /// return call splits exactly the borrowed quantity off that coin, so it must be a coin
+    /// of the borrowed asset holding at least `borrow_amount`; otherwise the transaction
+    /// aborts on chain. On error the builder keeps the commands added so far, including
+    /// the unpaid borrow, and should be discarded.
+    ///
+    /// @param ptb - ProgrammableTransactionBuilder instance
+    /// @param body - Commands to run while the loan is outstanding
+    /// @returns The remainder of the coin returned by `body` after repayment
+    pub async fn run<F>(
+        self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        body: F,
+    ) -> anyhow::Result<Argument>
+    where
+        F: AsyncFnOnce(&mut ProgrammableTransactionBuilder, Argument) -> anyhow::Result<Argument>,
+    {
+        let (borrowed_coin, flash_loan) = match self.asset {
+            FlashLoanAsset::Base => {
+                self.contract
+                    .borrow_base_asset_by_ref(ptb, &self.pool, self.borrow_amount)
+                    .await?
+            }
+            FlashLoanAsset::Quote => {
+                self.contract
+                    .borrow_quote_asset_by_ref(ptb, &self.pool, self.borrow_amount)
+                    .await?
+            }
+        };
+
+        let repay_coin = body(ptb, borrowed_coin).await?;
+        if repay_coin == flash_loan {
+            return Err(anyhow::anyhow!(
+                "Flash loan body must return a coin, not the FlashLoan"
+            ));
+        }
+
+        match self.asset {
+            FlashLoanAsset::Base => {
+                self.contract
+                    .return_base_asset_by_ref(
+                        ptb,
+                        &self.pool,
+                        self.borrow_amount,
+                        repay_coin,
+                        flash_loan,
+                    )
+                    .await
+            }
+            FlashLoanAsset::Quote => {
+                self.contract
+                    .return_quote_asset_by_ref(
+                        ptb,
+                        &self.pool,
+                        self.borrow_amount,
+                        repay_coin,
+                        flash_loan,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+/// Split the `(Coin, FlashLoan)` tuple returned by a borrow call into its nested results
+fn split_borrow_result(result: Argument) -> anyhow::Result<(Argument, Argument)> {
+    match result {
+        Argument::Result(idx) => Ok((
+            Argument::NestedResult(idx, 0),
+            Argument::NestedResult(idx, 1),
+        )),
+        _ => Err(anyhow::anyhow!("Unexpected flash loan borrow result")),
     }
 }
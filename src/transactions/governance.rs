@@ -6,7 +6,7 @@ use std::str::FromStr;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_sdk::SuiClient;
 
-use crate::utils::config::{DeepBookConfig, DEEP_SCALAR, FLOAT_SCALAR};
+use crate::utils::config::{DeepBookConfig, DEEP_SCALAR};
 use crate::utils::types::ProposalParams;
 
 use sui_sdk::types::base_types::ObjectID;
@@ -140,9 +140,7 @@ impl GovernanceContract {
         let base_coin = self.config.get_coin(&pool.base_coin)?;
         let quote_coin = self.config.get_coin(&pool.quote_coin)?;
 
-        let taker_fee = (params.taker_fee * FLOAT_SCALAR as f64).round() as u64;
-        let maker_fee = (params.maker_fee * FLOAT_SCALAR as f64).round() as u64;
-        let stake_required = (params.stake_required * DEEP_SCALAR as f64).round() as u64;
+        let (taker_fee, maker_fee, stake_required) = params.scaled();
 
         let pool_id = ObjectID::from_hex_literal(pool.address.as_str())?;
         let balance_manager_id = ObjectID::from_hex_literal(balance_manager.address.as_str())?;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod balance_manager;
+pub mod bulk;
 pub mod deepbook;
 pub mod deepbook_admin;
 pub mod flashloan;
@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+/// Exponential backoff between retries
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    /// Creates a new Backoff
+    ///
+    /// @param initial - The first delay
+    /// @param max - The longest delay
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max: max.max(initial),
+            current: initial,
+        }
+    }
+
+    /// The delay before the next retry, doubling the one after it
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+        self.current = (self.current * 2).min(self.max);
+        delay
+    }
+
+    /// Start over from the first delay
+    pub fn reset(&mut self) {
+        self.current = self.initial;
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::types::ClientOrderId;
+
+/// Source of unique client order IDs
+pub trait ClientOrderIdGenerator {
+    /// The next unused client order ID
+    fn next_id(&self) -> anyhow::Result<ClientOrderId>;
+}
+
+/// Counter starting at a given value, unique within the process
+///
+/// Once `u64::MAX` is reached every further call fails instead of wrapping around.
+#[derive(Debug, Default)]
+pub struct MonotonicIdGenerator {
+    next: AtomicU64,
+}
+
+impl MonotonicIdGenerator {
+    /// Creates a new MonotonicIdGenerator
+    ///
+    /// @param start - The first ID handed out
+    pub fn new(start: u64) -> Self {
+        Self {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl ClientOrderIdGenerator for MonotonicIdGenerator {
+    fn next_id(&self) -> anyhow::Result<ClientOrderId> {
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+            .map(ClientOrderId)
+            .map_err(|_| anyhow::anyhow!("Client order ID space exhausted"))
+    }
+}
+
+/// Millisecond timestamp in the upper 44 bits and a counter in the lower 20 bits
+///
+/// IDs stay unique across restarts as long as fewer than 2^20 IDs are issued per
+/// millisecond and the clock does not move backwards between runs.
+#[derive(Debug, Default)]
+pub struct TimePrefixedIdGenerator {
+    last: AtomicU64,
+}
+
+impl TimePrefixedIdGenerator {
+    const COUNTER_BITS: u32 = 20;
+
+    /// Creates a new TimePrefixedIdGenerator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The timestamp, in milliseconds since the Unix epoch, encoded in an ID
+    ///
+    /// @param id - An ID issued by a TimePrefixedIdGenerator
+    pub fn timestamp_ms(id: ClientOrderId) -> u64 {
+        id.0 >> Self::COUNTER_BITS
+    }
+}
+
+impl ClientOrderIdGenerator for TimePrefixedIdGenerator {
+    fn next_id(&self) -> anyhow::Result<ClientOrderId> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let floor = now << Self::COUNTER_BITS;
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let next = floor.max(last + 1);
+            match self
+                .last
+                .compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return Ok(ClientOrderId(next)),
+                Err(current) => last = current,
+            }
+        }
+    }
+}
+
+/// A 16-bit strategy namespace in the upper bits and a 48-bit sequence in the lower bits
+///
+/// The sequence is persisted to a state file in blocks, so IDs are never reused across
+/// restarts. At most one block of IDs is skipped after a crash. The file is replaced
+/// atomically, so a crash mid-write never leaves it truncated.
+#[derive(Debug)]
+pub struct NamespacedIdGenerator {
+    namespace: u16,
+    path: PathBuf,
+    block_size: u64,
+    state: Mutex<NamespacedState>,
+}
+
+#[derive(Debug)]
+struct NamespacedState {
+    next: u64,
+    reserved_until: u64,
+}
+
+impl NamespacedIdGenerator {
+    const SEQUENCE_BITS: u32 = 48;
+    const SEQUENCE_MASK: u64 = (1 << Self::SEQUENCE_BITS) - 1;
+
+    /// Open the generator for a namespace, resuming after the last reserved block
+    ///
+    /// @param namespace - The strategy namespace
+    /// @param path - The state file, created if it does not exist
+    /// @param block_size - Number of IDs reserved per write to the state file
+    pub fn open(namespace: u16, path: impl Into<PathBuf>, block_size: u64) -> anyhow::Result<Self> {
+        let path = path.into();
+        let next = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents.trim().parse::<u64>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            namespace,
+            path,
+            block_size: block_size.max(1),
+            state: Mutex::new(NamespacedState {
+                next,
+                reserved_until: next,
+            }),
+        })
+    }
+
+    /// The namespace encoded in an ID
+    ///
+    /// @param id - An ID issued by a NamespacedIdGenerator
+    pub fn namespace_of(id: ClientOrderId) -> u16 {
+        (id.0 >> Self::SEQUENCE_BITS) as u16
+    }
+}
+
+impl ClientOrderIdGenerator for NamespacedIdGenerator {
+    fn next_id(&self) -> anyhow::Result<ClientOrderId> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Client order ID state poisoned"))?;
+
+        if state.next >= state.reserved_until {
+            let reserved_until = state.next + self.block_size;
+            if reserved_until > Self::SEQUENCE_MASK {
+                return Err(anyhow::anyhow!(
+                    "Client order ID space exhausted for namespace {}",
+                    self.namespace
+                ));
+            }
+            let temp = self.path.with_extension("tmp");
+            std::fs::write(&temp, reserved_until.to_string())?;
+            std::fs::rename(temp, &self.path)?;
+            state.reserved_until = reserved_until;
+        }
+
+        let sequence = state.next;
+        state.next += 1;
+        Ok(ClientOrderId(
+            ((self.namespace as u64) << Self::SEQUENCE_BITS) | sequence,
+        ))
+    }
+}
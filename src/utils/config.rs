@@ -1,28 +1,33 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::Deserialize;
 use std::collections::HashMap;
-use sui_sdk::types::base_types::SuiAddress;
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::TypeTag;
 
 use crate::utils::constants::{
     MAINNET_COINS, MAINNET_PACKAGE_IDS, MAINNET_POOLS, TESTNET_COINS, TESTNET_PACKAGE_IDS,
     TESTNET_POOLS,
 };
 
-use super::types::{BalanceManager, Coin, DeepBookPackageIds, Pool};
+use super::types::{BalanceManager, BalanceManagerRef, Coin, DeepBookPackageIds, Pool, PoolRef};
 
 // Constants
 pub const FLOAT_SCALAR: u64 = 1_000_000_000;
 pub const MAX_TIMESTAMP: u64 = 1_844_674_407_370_955_161;
 pub const GAS_BUDGET: u64 = 250_000_000; // 0.5 * 500000000
 pub const DEEP_SCALAR: u64 = 1_000_000;
+pub const INPUT_FEE_MULTIPLIER: f64 = 1.25; // Fees paid in the input asset instead of DEEP
 
 // Type aliases
 pub type CoinMap = HashMap<&'static str, Coin>;
 pub type PoolMap = HashMap<&'static str, Pool>;
 pub type BalanceManagerMap = HashMap<&'static str, BalanceManager>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Environment {
     Mainnet,
     Testnet,
@@ -85,7 +90,6 @@ impl DeepBookConfig {
     }
 
     pub fn get_balance_manager(&self, manager_key: &str) -> anyhow::Result<&BalanceManager> {
-        println!("Balance managers: {:?}", self.balance_managers);
         self.balance_managers
             .get(manager_key)
             .ok_or(anyhow::anyhow!(
@@ -94,6 +98,47 @@ impl DeepBookConfig {
             ))
     }
 
+    /// Resolve a pool key into a PoolRef
+    ///
+    /// @param key - The key of the pool
+    pub fn pool_ref(&self, key: &str) -> anyhow::Result<PoolRef> {
+        let pool = self.get_pool(key)?;
+        let base_coin = self.get_coin(&pool.base_coin)?;
+        let quote_coin = self.get_coin(&pool.quote_coin)?;
+
+        Ok(PoolRef::new(
+            ObjectID::from_hex_literal(&pool.address)?,
+            TypeTag::from_str(&base_coin.type_name)?,
+            TypeTag::from_str(&quote_coin.type_name)?,
+            base_coin.scalar,
+            quote_coin.scalar,
+        ))
+    }
+
+    /// Resolve a balance manager key into a BalanceManagerRef
+    ///
+    /// @param manager_key - The key of the balance manager
+    pub fn balance_manager_ref(&self, manager_key: &str) -> anyhow::Result<BalanceManagerRef> {
+        let balance_manager = self.get_balance_manager(manager_key)?;
+        let trade_cap = match &balance_manager.trade_cap {
+            Some(trade_cap) => Some(ObjectID::from_hex_literal(trade_cap)?),
+            None => None,
+        };
+
+        Ok(BalanceManagerRef::new(
+            ObjectID::from_hex_literal(&balance_manager.address)?,
+            trade_cap,
+        ))
+    }
+
+    pub fn coins(&self) -> &CoinMap {
+        &self.coins
+    }
+
+    pub fn pools(&self) -> &PoolMap {
+        &self.pools
+    }
+
     pub fn address(&self) -> &SuiAddress {
         &self.address
     }
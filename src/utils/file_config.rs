@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::wallet_context::WalletContext;
+use sui_sdk::SuiClientBuilder;
+
+use crate::client::DeepBookClient;
+use crate::executor::WalletExecutor;
+
+use super::config::{BalanceManagerMap, Environment, GAS_BUDGET};
+use super::types::BalanceManager;
+
+/// A balance manager entry of a FileConfig
+#[derive(Debug, Clone, Deserialize)]
+pub struct BalanceManagerEntry {
+    pub address: String,
+    #[serde(default)]
+    pub trade_cap: Option<String>,
+}
+
+/// Client settings read from a JSON file, shared by the command-line tools
+///
+/// ```json
+/// {
+///     "env": "mainnet",
+///     "balance_managers": {
+///         "MANAGER_1": { "address": "0x344c..." }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileConfig {
+    pub env: Environment,
+    /// Fullnode URL, the public fullnode of `env` when missing
+    #[serde(default)]
+    pub rpc_url: Option<String>,
+    /// Sender of read-only calls, the wallet's active address when missing
+    #[serde(default)]
+    pub address: Option<SuiAddress>,
+    /// Sui wallet config used to sign, `~/.sui/sui_config/client.yaml` when missing
+    #[serde(default)]
+    pub wallet_config: Option<PathBuf>,
+    /// Gas budget of every transaction, in MIST
+    #[serde(default)]
+    pub gas_budget: Option<u64>,
+    #[serde(default)]
+    pub admin_cap: Option<String>,
+    #[serde(default)]
+    pub balance_managers: HashMap<String, BalanceManagerEntry>,
+}
+
+impl FileConfig {
+    /// Read a FileConfig from a JSON file
+    ///
+    /// @param path - Path of the file
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&contents)
+    }
+
+    /// Parse a FileConfig from JSON
+    ///
+    /// @param contents - The JSON text
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        match (&self.rpc_url, self.env) {
+            (Some(rpc_url), _) => rpc_url,
+            (None, Environment::Mainnet) => "https://fullnode.mainnet.sui.io:443",
+            (None, Environment::Testnet) => "https://fullnode.testnet.sui.io:443",
+        }
+    }
+
+    pub fn gas_budget(&self) -> u64 {
+        self.gas_budget.unwrap_or(GAS_BUDGET)
+    }
+
+    /// Path of the Sui wallet config
+    pub fn wallet_config(&self) -> anyhow::Result<PathBuf> {
+        match &self.wallet_config {
+            Some(path) => Ok(path.clone()),
+            None => {
+                let home = std::env::var("HOME")
+                    .map_err(|_| anyhow::anyhow!("HOME is not set, configure wallet_config"))?;
+                Ok(Path::new(&home).join(".sui/sui_config/client.yaml"))
+            }
+        }
+    }
+
+    /// The configured balance managers, keyed as the client expects
+    ///
+    /// Keys are leaked to satisfy the `'static` keys of BalanceManagerMap; a config is
+    /// loaded once per process.
+    pub fn balance_managers(&self) -> BalanceManagerMap {
+        self.balance_managers
+            .iter()
+            .map(|(key, entry)| {
+                let key: &'static str = Box::leak(key.clone().into_boxed_str());
+                (
+                    key,
+                    BalanceManager {
+                        address: entry.address.clone(),
+                        trade_cap: entry.trade_cap.clone(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Open the Sui wallet
+    pub fn wallet(&self) -> anyhow::Result<WalletContext> {
+        WalletContext::new(&self.wallet_config()?)
+    }
+
+    /// Open the Sui wallet as a WalletExecutor with the configured gas budget
+    pub fn executor(&self) -> anyhow::Result<WalletExecutor> {
+        WalletExecutor::new(self.wallet()?, self.gas_budget())
+    }
+
+    /// Connect to the fullnode and build a DeepBookClient
+    ///
+    /// @param address - Sender of read-only calls, overriding the configured address
+    pub async fn client(&self, address: Option<SuiAddress>) -> anyhow::Result<DeepBookClient> {
+        let sui_client = SuiClientBuilder::default().build(self.rpc_url()).await?;
+        Ok(DeepBookClient::new(
+            sui_client,
+            address.or(self.address).unwrap_or(SuiAddress::ZERO),
+            self.env,
+            Some(self.balance_managers()),
+            None,
+            None,
+            self.admin_cap.clone(),
+        ))
+    }
+}
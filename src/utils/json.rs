@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::rpc_types::{SuiObjectData, SuiParsedData};
+use sui_sdk::types::base_types::ObjectID;
+
+/// Move content of an object as JSON
+pub(crate) fn object_json(object: SuiObjectData) -> anyhow::Result<serde_json::Value> {
+    match object.content {
+        Some(SuiParsedData::MoveObject(object)) => Ok(object.fields.to_json_value()),
+        _ => Err(anyhow::anyhow!(
+            "Object {} has no Move content",
+            object.object_id
+        )),
+    }
+}
+
+/// Walk a path of field names, looking through `fields` wrappers of nested structs
+pub(crate) fn json_field<'a>(
+    value: &'a serde_json::Value,
+    path: &[&str],
+) -> anyhow::Result<&'a serde_json::Value> {
+    path.iter().try_fold(value, |value, key| {
+        let value = value.get("fields").unwrap_or(value);
+        value
+            .get(*key)
+            .ok_or_else(|| anyhow::anyhow!("Missing field {}", key))
+    })
+}
+
+pub(crate) fn json_u64(value: &serde_json::Value) -> anyhow::Result<u64> {
+    match value {
+        serde_json::Value::String(value) => Ok(value.parse::<u64>()?),
+        serde_json::Value::Number(value) => value
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse {} as u64", value)),
+        _ => Err(anyhow::anyhow!("Failed to parse {} as u64", value)),
+    }
+}
+
+pub(crate) fn json_bool(value: &serde_json::Value) -> anyhow::Result<bool> {
+    value
+        .as_bool()
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse {} as bool", value))
+}
+
+/// Unwrap an `Option` that may be rendered as a value or as a `vec` of zero or one element
+pub(crate) fn json_option(value: &serde_json::Value) -> Option<&serde_json::Value> {
+    let value = value.get("fields").unwrap_or(value);
+    match value.get("vec").unwrap_or(value) {
+        serde_json::Value::Null => None,
+        serde_json::Value::Array(values) => values.first(),
+        value => Some(value),
+    }
+}
+
+/// Parse an `ID` or `UID` rendered either as a hex string or as a nested `{ id }` object
+pub(crate) fn json_object_id(value: &serde_json::Value) -> anyhow::Result<ObjectID> {
+    let value = value.get("fields").unwrap_or(value);
+    match value {
+        serde_json::Value::String(id) => Ok(ObjectID::from_hex_literal(id)?),
+        _ => json_object_id(
+            value
+                .get("id")
+                .or_else(|| value.get("bytes"))
+                .ok_or_else(|| anyhow::anyhow!("Failed to parse {} as ID", value))?,
+        ),
+    }
+}
+
+pub(crate) fn json_u128(value: &serde_json::Value) -> anyhow::Result<u128> {
+    match value {
+        serde_json::Value::String(value) => Ok(value.parse::<u128>()?),
+        serde_json::Value::Number(value) => value
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse {} as u128", value)),
+        _ => Err(anyhow::anyhow!("Failed to parse {} as u128", value)),
+    }
+}
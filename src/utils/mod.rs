@@ -1,6 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod backoff;
+pub mod client_order_id;
 pub mod config;
 pub mod constants;
+pub mod file_config;
+pub(crate) mod json;
 pub mod types;
@@ -1,7 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
 use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::TypeTag;
+
+use super::config::{DEEP_SCALAR, FLOAT_SCALAR, MAX_TIMESTAMP};
 
 /// Represents a balance manager in the system
 #[derive(Debug, Clone)]
@@ -26,6 +35,95 @@ pub struct Pool {
     pub quote_coin: String,
 }
 
+/// Addresses a pool directly by object ID and coin types, without a config key
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolRef {
+    pub pool_id: ObjectID,
+    pub base_type: TypeTag,
+    pub quote_type: TypeTag,
+    pub base_scalar: u64,
+    pub quote_scalar: u64,
+}
+
+impl PoolRef {
+    /// Creates a new PoolRef
+    ///
+    /// @param pool_id - The object ID of the pool
+    /// @param base_type - The type tag of the base coin
+    /// @param quote_type - The type tag of the quote coin
+    /// @param base_scalar - The scalar of the base coin
+    /// @param quote_scalar - The scalar of the quote coin
+    pub fn new(
+        pool_id: ObjectID,
+        base_type: TypeTag,
+        quote_type: TypeTag,
+        base_scalar: u64,
+        quote_scalar: u64,
+    ) -> Self {
+        Self {
+            pool_id,
+            base_type,
+            quote_type,
+            base_scalar,
+            quote_scalar,
+        }
+    }
+
+    /// The `<Base, Quote>` type arguments for pool move calls
+    pub fn type_args(&self) -> Vec<TypeTag> {
+        vec![self.base_type.clone(), self.quote_type.clone()]
+    }
+
+    /// Convert a human price into the on-chain price representation
+    pub fn price_to_input(&self, price: f64) -> u64 {
+        ((price * FLOAT_SCALAR as f64 * self.quote_scalar as f64) / self.base_scalar as f64).round()
+            as u64
+    }
+
+    /// Convert an on-chain price into a human price
+    pub fn price_from_raw(&self, raw_price: u64) -> f64 {
+        (raw_price as f64 * self.base_scalar as f64)
+            / (FLOAT_SCALAR as f64 * self.quote_scalar as f64)
+    }
+
+    /// Convert a base quantity into its on-chain representation
+    pub fn base_to_input(&self, quantity: f64) -> u64 {
+        (quantity * self.base_scalar as f64).round() as u64
+    }
+
+    /// Convert a quote quantity into its on-chain representation
+    pub fn quote_to_input(&self, quantity: f64) -> u64 {
+        (quantity * self.quote_scalar as f64).round() as u64
+    }
+
+    /// Convert an on-chain base quantity into a human quantity
+    pub fn base_from_raw(&self, raw_quantity: u64) -> f64 {
+        raw_quantity as f64 / self.base_scalar as f64
+    }
+
+    /// Convert an on-chain quote quantity into a human quantity
+    pub fn quote_from_raw(&self, raw_quantity: u64) -> f64 {
+        raw_quantity as f64 / self.quote_scalar as f64
+    }
+}
+
+/// Addresses a balance manager directly by object ID, without a config key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceManagerRef {
+    pub id: ObjectID,
+    pub trade_cap: Option<ObjectID>,
+}
+
+impl BalanceManagerRef {
+    /// Creates a new BalanceManagerRef
+    ///
+    /// @param id - The object ID of the balance manager
+    /// @param trade_cap - The optional TradeCap used to trade on behalf of the owner
+    pub fn new(id: ObjectID, trade_cap: Option<ObjectID>) -> Self {
+        Self { id, trade_cap }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DeepBookPackageIds {
     pub deepbook_package_id: &'static str,
@@ -34,20 +132,243 @@ pub struct DeepBookPackageIds {
 }
 
 /// Trading order types
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Passed on-chain as a u8, and (de)serialized as one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+#[repr(u8)]
 pub enum OrderType {
-    NoRestriction,
-    ImmediateOrCancel,
-    FillOrKill,
-    PostOnly,
+    NoRestriction = 0,
+    ImmediateOrCancel = 1,
+    FillOrKill = 2,
+    PostOnly = 3,
+}
+
+impl TryFrom<u8> for OrderType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OrderType::NoRestriction),
+            1 => Ok(OrderType::ImmediateOrCancel),
+            2 => Ok(OrderType::FillOrKill),
+            3 => Ok(OrderType::PostOnly),
+            _ => Err(anyhow::anyhow!("Invalid order type: {}", value)),
+        }
+    }
+}
+
+impl From<OrderType> for u8 {
+    fn from(value: OrderType) -> Self {
+        value as u8
+    }
 }
 
 /// Self-matching options for orders
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Passed on-chain as a u8, and (de)serialized as one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+#[repr(u8)]
 pub enum SelfMatchingOptions {
-    SelfMatchingAllowed,
-    CancelTaker,
-    CancelMaker,
+    SelfMatchingAllowed = 0,
+    CancelTaker = 1,
+    CancelMaker = 2,
+}
+
+impl TryFrom<u8> for SelfMatchingOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SelfMatchingOptions::SelfMatchingAllowed),
+            1 => Ok(SelfMatchingOptions::CancelTaker),
+            2 => Ok(SelfMatchingOptions::CancelMaker),
+            _ => Err(anyhow::anyhow!("Invalid self-matching option: {}", value)),
+        }
+    }
+}
+
+impl From<SelfMatchingOptions> for u8 {
+    fn from(value: SelfMatchingOptions) -> Self {
+        value as u8
+    }
+}
+
+/// Status of an order in the book
+///
+/// Stored on-chain as a u8, and (de)serialized as one so it decodes straight from BCS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum OrderStatus {
+    Live = 0,
+    PartiallyFilled = 1,
+    Filled = 2,
+    Canceled = 3,
+    Expired = 4,
+}
+
+impl OrderStatus {
+    /// Whether the order still rests on the book
+    pub fn is_open(&self) -> bool {
+        matches!(self, OrderStatus::Live | OrderStatus::PartiallyFilled)
+    }
+}
+
+impl TryFrom<u8> for OrderStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OrderStatus::Live),
+            1 => Ok(OrderStatus::PartiallyFilled),
+            2 => Ok(OrderStatus::Filled),
+            3 => Ok(OrderStatus::Canceled),
+            4 => Ok(OrderStatus::Expired),
+            _ => Err(anyhow::anyhow!("Invalid order status: {}", value)),
+        }
+    }
+}
+
+impl From<OrderStatus> for u8 {
+    fn from(value: OrderStatus) -> Self {
+        value as u8
+    }
+}
+
+/// Client-assigned order ID, stored on-chain as a u64
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct ClientOrderId(pub u64);
+
+impl ClientOrderId {
+    /// The raw u64 value
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ClientOrderId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ClientOrderId> for u64 {
+    fn from(value: ClientOrderId) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ClientOrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ClientOrderId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse::<u64>()?))
+    }
+}
+
+/// On-chain order ID, encoding the side, the raw price and a sequence number
+///
+/// Bids are encoded with a zero top bit and a sequence counting down from `u64::MAX`,
+/// asks with a set top bit and a sequence counting up, so the u128 value orders each side
+/// of the book. `Ord` follows book priority: bids before asks, bids from the highest price
+/// and asks from the lowest, and older orders first within a price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OrderId(pub u128);
+
+impl OrderId {
+    const PRICE_MASK: u128 = (1 << 63) - 1;
+
+    /// Encode an order ID
+    ///
+    /// @param is_bid - Whether the order is a bid
+    /// @param price - The price in its on-chain representation, below 2^63
+    /// @param sequence - The order's sequence number in the book
+    pub fn encode(is_bid: bool, price: u64, sequence: u64) -> Self {
+        let side = if is_bid { 0 } else { 1u128 << 127 };
+        Self(side | ((price as u128 & Self::PRICE_MASK) << 64) | sequence as u128)
+    }
+
+    /// The raw u128 value
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+
+    /// Whether the order is a bid
+    pub fn is_bid(&self) -> bool {
+        self.0 >> 127 == 0
+    }
+
+    /// The price in its on-chain representation
+    pub fn price(&self) -> u64 {
+        ((self.0 >> 64) & Self::PRICE_MASK) as u64
+    }
+
+    /// The order's sequence number in the book
+    pub fn sequence(&self) -> u64 {
+        self.0 as u64
+    }
+
+    /// The price as a human price for the pool holding the order
+    ///
+    /// @param pool - The PoolRef holding the order
+    pub fn normalized_price(&self, pool: &PoolRef) -> f64 {
+        (pool.price_from_raw(self.price()) * 1e9).round() / 1e9
+    }
+}
+
+impl Ord for OrderId {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self.is_bid(), other.is_bid()) {
+            (true, true) => other.0.cmp(&self.0),
+            (false, false) => self.0.cmp(&other.0),
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for OrderId {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u128> for OrderId {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<OrderId> for u128 {
+    fn from(value: OrderId) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for OrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for OrderId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse::<u128>()?))
+    }
 }
 
 /// Parameters for placing a limit order
@@ -55,7 +376,7 @@ pub enum SelfMatchingOptions {
 pub struct PlaceLimitOrderParams {
     pub pool_key: String,
     pub balance_manager_key: String,
-    pub client_order_id: String,
+    pub client_order_id: ClientOrderId,
     pub price: f64,
     pub quantity: f64,
     pub is_bid: bool,
@@ -65,12 +386,62 @@ pub struct PlaceLimitOrderParams {
     pub pay_with_deep: Option<bool>,
 }
 
+impl PlaceLimitOrderParams {
+    /// Set `expiration` from a TimeInForce
+    ///
+    /// @param time_in_force - How long the order rests on the book
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.expiration = Some(time_in_force.expiration());
+        self
+    }
+}
+
+/// How long a limit order rests on the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInForce {
+    GoodTilCancelled,
+    /// Expires at a timestamp in milliseconds, as measured by the on-chain `Clock`
+    GoodTilTime(u64),
+}
+
+impl TimeInForce {
+    /// Expire a duration after a `Clock` timestamp
+    ///
+    /// @param clock_timestamp_ms - The current on-chain time in milliseconds
+    /// @param duration - How long the order rests on the book
+    pub fn good_til_duration(clock_timestamp_ms: u64, duration: Duration) -> Self {
+        let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        TimeInForce::GoodTilTime(
+            clock_timestamp_ms
+                .saturating_add(duration_ms)
+                .min(MAX_TIMESTAMP),
+        )
+    }
+
+    /// Expire at a point in time
+    ///
+    /// @param time - The expiry time, in any time zone
+    pub fn good_til_datetime<Tz: TimeZone>(time: &DateTime<Tz>) -> anyhow::Result<Self> {
+        let timestamp_ms = u64::try_from(time.timestamp_millis())
+            .map_err(|_| anyhow::anyhow!("Expiry {} is before the Unix epoch", time.naive_utc()))?;
+        Ok(TimeInForce::GoodTilTime(timestamp_ms.min(MAX_TIMESTAMP)))
+    }
+
+    /// The `expiration` value for the order
+    pub fn expiration(&self) -> u64 {
+        match self {
+            TimeInForce::GoodTilCancelled => MAX_TIMESTAMP,
+            TimeInForce::GoodTilTime(timestamp_ms) => *timestamp_ms,
+        }
+    }
+}
+
 /// Parameters for placing a market order
 #[derive(Debug, Clone)]
 pub struct PlaceMarketOrderParams {
     pub pool_key: String,
     pub balance_manager_key: String,
-    pub client_order_id: String,
+    pub client_order_id: ClientOrderId,
     pub quantity: f64,
     pub is_bid: bool,
     pub self_matching_option: Option<SelfMatchingOptions>,
@@ -87,6 +458,28 @@ pub struct ProposalParams {
     pub stake_required: f64,
 }
 
+impl ProposalParams {
+    /// Scale fees and stake to their on-chain representation
+    ///
+    /// @returns The taker fee, maker fee and stake required as u64
+    pub fn scaled(&self) -> (u64, u64, u64) {
+        Self::scale(self.taker_fee, self.maker_fee, self.stake_required)
+    }
+
+    /// Scale a set of trade parameters to their on-chain representation
+    ///
+    /// @param taker_fee - The taker fee, e.g. 0.001 for 10 bps
+    /// @param maker_fee - The maker fee
+    /// @param stake_required - The DEEP stake required
+    pub fn scale(taker_fee: f64, maker_fee: f64, stake_required: f64) -> (u64, u64, u64) {
+        (
+            (taker_fee * FLOAT_SCALAR as f64).round() as u64,
+            (maker_fee * FLOAT_SCALAR as f64).round() as u64,
+            (stake_required * DEEP_SCALAR as f64).round() as u64,
+        )
+    }
+}
+
 /// Parameters for swap operations
 #[derive(Debug, Clone)]
 pub struct SwapParams {
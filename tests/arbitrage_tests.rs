@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use sui_deepbookv3::{
+    arbitrage::{
+        find_cycles, ArbitrageConfig, ArbitrageCycle, ArbitrageLeg, ArbitragePlanner, CycleQuote,
+        LegQuote, SwapDirection,
+    },
+    client::{DeepBookClient, PoolDeepPrice},
+    transactions::flashloan::FlashLoanAsset,
+    utils::config::{Environment, PoolMap},
+};
+use sui_sdk::{
+    types::{base_types::SuiAddress, transaction::Command},
+    SuiClientBuilder,
+};
+use utils::pool_config;
+
+mod utils;
+
+fn triangle() -> PoolMap {
+    HashMap::from([
+        ("DEEP_SUI", pool_config("DEEP", "SUI")),
+        ("SUI_USDC", pool_config("SUI", "USDC")),
+        ("DEEP_USDC", pool_config("DEEP", "USDC")),
+    ])
+}
+
+#[test]
+fn test_find_cycles_two_legs_needs_parallel_pools() {
+    // With two legs a cycle has to return through a second pool on the same pair.
+    assert!(find_cycles(&triangle(), 2).is_empty());
+
+    let mut pools = triangle();
+    pools.insert("DEEP_SUI_2", pool_config("DEEP", "SUI"));
+    let cycles = find_cycles(&pools, 2);
+
+    assert!(cycles.iter().any(|cycle| cycle.loan_pool_key == "DEEP_USDC"
+        && cycle.loan_asset == FlashLoanAsset::Base
+        && cycle.legs.len() == 2));
+    assert!(cycles.iter().all(|cycle| cycle.legs.len() == 2));
+}
+
+#[test]
+fn test_find_cycles_triangle() {
+    // Every closing leg of a three-pool triangle is the loan pool itself.
+    assert!(find_cycles(&triangle(), 3).is_empty());
+
+    let mut pools = triangle();
+    pools.insert("SUI_USDC_2", pool_config("SUI", "USDC"));
+    let cycles = find_cycles(&pools, 3);
+
+    for cycle in &cycles {
+        assert!(cycle
+            .legs
+            .iter()
+            .all(|leg| leg.pool_key != cycle.loan_pool_key));
+    }
+
+    let cycle = cycles
+        .iter()
+        .find(|cycle| {
+            cycle.loan_pool_key == "SUI_USDC"
+                && cycle.loan_asset == FlashLoanAsset::Base
+                && cycle.legs.len() == 3
+        })
+        .expect("triangle through the second SUI/USDC pool");
+    assert_eq!(cycle.loan_coin_key, "SUI");
+    assert_eq!(cycle.legs[0].pool_key, "DEEP_SUI");
+    assert_eq!(cycle.legs[0].direction, SwapDirection::QuoteToBase);
+    assert_eq!(cycle.legs[1].pool_key, "DEEP_USDC");
+    assert_eq!(cycle.legs[1].direction, SwapDirection::BaseToQuote);
+    assert_eq!(cycle.legs[2].pool_key, "SUI_USDC_2");
+    assert_eq!(cycle.legs[2].direction, SwapDirection::QuoteToBase);
+}
+
+// Borrow SUI from DEEP_SUI, sell it for DBUSDC and buy it back in SUI_DBUSDC
+fn round_trip() -> ArbitrageCycle {
+    ArbitrageCycle {
+        loan_pool_key: "DEEP_SUI".to_string(),
+        loan_asset: FlashLoanAsset::Quote,
+        loan_coin_key: "SUI".to_string(),
+        legs: vec![
+            ArbitrageLeg {
+                pool_key: "SUI_DBUSDC".to_string(),
+                direction: SwapDirection::BaseToQuote,
+            },
+            ArbitrageLeg {
+                pool_key: "SUI_DBUSDC".to_string(),
+                direction: SwapDirection::QuoteToBase,
+            },
+        ],
+    }
+}
+
+fn leg_quote(
+    direction: SwapDirection,
+    amount_in: f64,
+    amount_out: f64,
+    deep_required: f64,
+) -> LegQuote {
+    LegQuote {
+        pool_key: "SUI_DBUSDC".to_string(),
+        direction,
+        amount_in,
+        amount_out,
+        deep_required,
+    }
+}
+
+fn deep_price(deep_per_base: Option<f64>, deep_per_quote: Option<f64>) -> PoolDeepPrice {
+    PoolDeepPrice {
+        asset_is_base: deep_per_base.is_some(),
+        deep_per_base,
+        deep_per_quote,
+    }
+}
+
+#[test]
+fn test_cycle_quote_values_deep_fees_in_loan_asset() {
+    let quote = CycleQuote::new(
+        &round_trip(),
+        100.0,
+        vec![
+            (
+                leg_quote(SwapDirection::BaseToQuote, 100.0, 200.0, 2.0),
+                Some(deep_price(Some(4.0), None)),
+            ),
+            (
+                leg_quote(SwapDirection::QuoteToBase, 200.0, 101.0, 1.0),
+                Some(deep_price(None, Some(4.0))),
+            ),
+        ],
+    )
+    .unwrap();
+
+    // 2 DEEP at 4 DEEP per SUI costs 0.5 SUI. 1 DEEP at 4 DEEP per DBUSDC costs
+    // 0.25 DBUSDC, worth 0.125 SUI at the 2 DBUSDC per SUI realized by the first leg
+    assert_eq!(quote.amount_out, 101.0);
+    assert_eq!(quote.deep_required, 3.0);
+    assert_eq!(quote.deep_cost, 0.625);
+    assert_eq!(quote.profit, 0.375);
+    assert_eq!(quote.profit_ratio(), 0.00375);
+}
+
+#[test]
+fn test_cycle_quote_without_deep_fees() {
+    let quote = CycleQuote::new(
+        &round_trip(),
+        100.0,
+        vec![
+            (
+                leg_quote(SwapDirection::BaseToQuote, 100.0, 200.0, 0.0),
+                None,
+            ),
+            (
+                leg_quote(SwapDirection::QuoteToBase, 200.0, 99.0, 0.0),
+                None,
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(quote.deep_required, 0.0);
+    assert_eq!(quote.deep_cost, 0.0);
+    assert_eq!(quote.profit, -1.0);
+}
+
+#[test]
+fn test_cycle_quote_requires_deep_price() {
+    let legs = |price: Option<PoolDeepPrice>| {
+        vec![
+            (
+                leg_quote(SwapDirection::BaseToQuote, 100.0, 200.0, 1.0),
+                price,
+            ),
+            (
+                leg_quote(SwapDirection::QuoteToBase, 200.0, 101.0, 0.0),
+                None,
+            ),
+        ]
+    };
+
+    assert!(CycleQuote::new(&round_trip(), 100.0, legs(None)).is_err());
+    assert!(CycleQuote::new(&round_trip(), 100.0, legs(Some(deep_price(None, None)))).is_err());
+    assert!(CycleQuote::new(
+        &round_trip(),
+        100.0,
+        legs(Some(deep_price(Some(0.0), None)))
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_build_transaction_borrows_swaps_and_repays() {
+    let sui_client = SuiClientBuilder::default().build_testnet().await.unwrap();
+    let client = DeepBookClient::new(
+        sui_client,
+        SuiAddress::ZERO,
+        Environment::Testnet,
+        None,
+        None,
+        None,
+        None,
+    );
+    let planner = ArbitragePlanner::new(&client, ArbitrageConfig::default());
+    let quote = CycleQuote::new(
+        &round_trip(),
+        10.0,
+        vec![
+            (leg_quote(SwapDirection::BaseToQuote, 10.0, 20.0, 0.0), None),
+            (leg_quote(SwapDirection::QuoteToBase, 20.0, 10.5, 0.0), None),
+        ],
+    )
+    .unwrap();
+
+    let transaction = planner
+        .build_transaction(&quote, SuiAddress::ZERO)
+        .await
+        .unwrap();
+    let layout: Vec<&str> = transaction
+        .commands
+        .iter()
+        .map(|command| match command {
+            Command::MoveCall(call) => call.function.as_str(),
+            Command::TransferObjects(..) => "transfer",
+            Command::SplitCoins(..) => "split",
+            Command::MergeCoins(..) => "merge",
+            _ => "other",
+        })
+        .collect();
+
+    // A zero DEEP coin, the borrow, each swap sending its leftover input back, the
+    // unused DEEP, the repayment split off the final coin, and the profit
+    assert_eq!(
+        layout,
+        vec![
+            "zero",
+            "borrow_flashloan_quote",
+            "swap_exact_base_for_quote",
+            "transfer",
+            "swap_exact_quote_for_base",
+            "transfer",
+            "transfer",
+            "split",
+            "return_flashloan_quote",
+            "transfer",
+        ]
+    );
+}
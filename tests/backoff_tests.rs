@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+use sui_deepbookv3::utils::backoff::Backoff;
+
+#[test]
+fn test_backoff_doubles_up_to_max_and_resets() {
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+    assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+    assert_eq!(backoff.next_delay(), Duration::from_secs(4));
+    assert_eq!(backoff.next_delay(), Duration::from_secs(5));
+    backoff.reset();
+    assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+}
@@ -0,0 +1,73 @@
+use sui_deepbookv3::{
+    client::PoolBookParams,
+    transactions::bulk::{GridSide, GridSpec, SizeCurve},
+    utils::client_order_id::MonotonicIdGenerator,
+};
+
+fn book() -> PoolBookParams {
+    PoolBookParams {
+        tick_size: 0.01,
+        lot_size: 0.1,
+        min_size: 0.5,
+    }
+}
+
+fn grid(side: GridSide, size_curve: SizeCurve) -> GridSpec {
+    GridSpec {
+        pool_key: "DEEP_SUI".to_string(),
+        center_price: 1.005,
+        step_ticks: 2,
+        levels: 3,
+        base_size: 1.0,
+        size_curve,
+        side,
+        order_type: None,
+        self_matching_option: None,
+        pay_with_deep: None,
+        expiration: None,
+    }
+}
+
+#[test]
+fn test_grid_prices_align_to_ticks() {
+    let ids = MonotonicIdGenerator::new(1);
+    let orders = grid(GridSide::Both, SizeCurve::Flat)
+        .orders("MANAGER_1", &book(), &ids)
+        .unwrap();
+    assert_eq!(orders.len(), 6);
+
+    let bids: Vec<f64> = orders
+        .iter()
+        .filter(|o| o.is_bid)
+        .map(|o| o.price)
+        .collect();
+    let asks: Vec<f64> = orders
+        .iter()
+        .filter(|o| !o.is_bid)
+        .map(|o| o.price)
+        .collect();
+    for (price, expected) in bids.iter().zip([0.98, 0.96, 0.94]) {
+        assert!((price - expected).abs() < 1e-9);
+    }
+    for (price, expected) in asks.iter().zip([1.03, 1.05, 1.07]) {
+        assert!((price - expected).abs() < 1e-9);
+    }
+
+    let client_order_ids: Vec<u64> = orders.iter().map(|o| o.client_order_id.0).collect();
+    assert_eq!(client_order_ids, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_grid_sizes_follow_curve_and_lot_size() {
+    let ids = MonotonicIdGenerator::new(1);
+    let orders = grid(GridSide::Bids, SizeCurve::Geometric(0.55))
+        .orders("MANAGER_1", &book(), &ids)
+        .unwrap();
+
+    // 1.0, 0.55 -> 0.5, 0.3025 -> below min size
+    let sizes: Vec<f64> = orders.iter().map(|o| o.quantity).collect();
+    assert_eq!(sizes.len(), 2);
+    assert!((sizes[0] - 1.0).abs() < 1e-9);
+    assert!((sizes[1] - 0.5).abs() < 1e-9);
+    assert!(orders.iter().all(|o| o.is_bid));
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use sui_deepbookv3::cache::TtlCache;
+
+#[test]
+fn test_fresh_entries_are_returned() {
+    let cache = TtlCache::new(Duration::from_secs(60));
+    assert_eq!(cache.get("mid/SUI_USDC"), None);
+
+    cache.insert("mid/SUI_USDC", 1.5);
+    assert_eq!(cache.get("mid/SUI_USDC"), Some(1.5));
+    assert_eq!(cache.get("mid/DEEP_SUI"), None);
+}
+
+#[test]
+fn test_expired_entries_are_dropped() {
+    let cache = TtlCache::new(Duration::ZERO);
+    cache.insert("a", 1);
+    assert_eq!(cache.get("a"), None);
+
+    cache.insert("b", 2);
+    assert_eq!(cache.len(), 1);
+}
+
+#[tokio::test]
+async fn test_reads_only_on_miss() {
+    let cache = TtlCache::new(Duration::from_secs(60));
+    let first = cache
+        .get_or_try_insert_with("book", || async { Ok(1) })
+        .await
+        .unwrap();
+    let second = cache
+        .get_or_try_insert_with("book", || async { Ok(2) })
+        .await
+        .unwrap();
+    assert_eq!((first, second), (1, 1));
+
+    let failed = cache
+        .get_or_try_insert_with("vault", || async { Err(anyhow::anyhow!("down")) })
+        .await;
+    assert!(failed.is_err());
+    assert_eq!(cache.get("vault"), None);
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use sui_deepbookv3::utils::{
+    client_order_id::{
+        ClientOrderIdGenerator, MonotonicIdGenerator, NamespacedIdGenerator,
+        TimePrefixedIdGenerator,
+    },
+    types::ClientOrderId,
+};
+
+#[test]
+fn test_client_order_id_serializes_as_u64() {
+    let id = ClientOrderId(42);
+    assert_eq!(bcs::to_bytes(&id).unwrap(), bcs::to_bytes(&42u64).unwrap());
+    assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+    assert_eq!("42".parse::<ClientOrderId>().unwrap(), id);
+}
+
+#[test]
+fn test_monotonic_and_time_prefixed_ids_are_increasing() {
+    let monotonic = MonotonicIdGenerator::new(7);
+    assert_eq!(monotonic.next_id().unwrap(), ClientOrderId(7));
+    assert_eq!(monotonic.next_id().unwrap(), ClientOrderId(8));
+
+    let time_prefixed = TimePrefixedIdGenerator::new();
+    let mut last = time_prefixed.next_id().unwrap();
+    for _ in 0..1_000 {
+        let next = time_prefixed.next_id().unwrap();
+        assert!(next > last);
+        last = next;
+    }
+    assert!(TimePrefixedIdGenerator::timestamp_ms(last) > 0);
+}
+
+#[test]
+fn test_monotonic_ids_do_not_wrap() {
+    let monotonic = MonotonicIdGenerator::new(u64::MAX - 1);
+    assert_eq!(monotonic.next_id().unwrap(), ClientOrderId(u64::MAX - 1));
+    assert!(monotonic.next_id().is_err());
+    assert!(monotonic.next_id().is_err());
+}
+
+#[test]
+fn test_namespaced_ids_survive_restart() {
+    let path = std::env::temp_dir().join(format!("client-order-id-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut seen = HashSet::new();
+    for _ in 0..2 {
+        let generator = NamespacedIdGenerator::open(3, &path, 4).unwrap();
+        for _ in 0..5 {
+            let id = generator.next_id().unwrap();
+            assert_eq!(NamespacedIdGenerator::namespace_of(id), 3);
+            assert!(seen.insert(id));
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,179 @@
+use sui_deepbookv3::{
+    conditional::{
+        ConditionalOrder, ConditionalStatus, OrderAction, PriceQuote, PriceSource,
+        TrailingDistance, Trigger, TriggerEngine,
+    },
+    utils::types::ClientOrderId,
+};
+
+fn order(is_bid: bool, trigger: Trigger, oco_group: Option<u64>) -> ConditionalOrder {
+    ConditionalOrder {
+        id: 0,
+        pool_key: "DEEP_SUI".to_string(),
+        balance_manager_key: "MANAGER_1".to_string(),
+        client_order_id: ClientOrderId(1),
+        is_bid,
+        quantity: 10.0,
+        trigger,
+        source: PriceSource::Mid,
+        action: OrderAction::Market,
+        pay_with_deep: None,
+        oco_group,
+        expires_at_ms: None,
+        status: ConditionalStatus::Pending,
+    }
+}
+
+fn mid(price: f64) -> PriceQuote {
+    PriceQuote {
+        mid: Some(price),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_stop_loss_and_take_profit_directions() {
+    let mut sell_stop = Trigger::StopLoss { trigger_price: 1.0 };
+    assert!(!sell_stop.observe(false, 1.1));
+    assert!(sell_stop.observe(false, 1.0));
+
+    let mut buy_stop = Trigger::StopLoss { trigger_price: 1.0 };
+    assert!(!buy_stop.observe(true, 0.9));
+    assert!(buy_stop.observe(true, 1.05));
+
+    let mut sell_target = Trigger::TakeProfit { trigger_price: 2.0 };
+    assert!(!sell_target.observe(false, 1.5));
+    assert!(sell_target.observe(false, 2.1));
+}
+
+#[test]
+fn test_trailing_stop_follows_best_price() {
+    let mut trailing = Trigger::TrailingStop {
+        distance: TrailingDistance::Percent(0.1),
+        extreme: None,
+    };
+    assert!(!trailing.observe(false, 1.0));
+    assert!(!trailing.observe(false, 2.0));
+    // 1.85 is above 2.0 - 10%
+    assert!(!trailing.observe(false, 1.85));
+    assert!(trailing.observe(false, 1.79));
+    assert_eq!(
+        trailing,
+        Trigger::TrailingStop {
+            distance: TrailingDistance::Percent(0.1),
+            extreme: Some(2.0),
+        }
+    );
+}
+
+#[test]
+fn test_one_cancels_other() {
+    let mut engine = TriggerEngine::new();
+    let stop = engine.add(order(
+        false,
+        Trigger::StopLoss { trigger_price: 0.9 },
+        Some(7),
+    ));
+    let target = engine.add(order(
+        false,
+        Trigger::TakeProfit { trigger_price: 1.2 },
+        Some(7),
+    ));
+
+    assert!(engine.evaluate("DEEP_SUI", &mid(1.0), 0).is_empty());
+    let fired = engine.evaluate("DEEP_SUI", &mid(1.25), 0);
+
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].id, target);
+    assert_eq!(
+        engine.order(target).unwrap().status,
+        ConditionalStatus::Triggered
+    );
+    // The sibling waits for the submission and cannot fire meanwhile
+    assert!(engine.evaluate("DEEP_SUI", &mid(0.8), 0).is_empty());
+    assert_eq!(
+        engine.order(stop).unwrap().status,
+        ConditionalStatus::Pending
+    );
+
+    engine.mark_submitted(target);
+    assert_eq!(
+        engine.order(stop).unwrap().status,
+        ConditionalStatus::Cancelled
+    );
+    assert_eq!(engine.pending().count(), 0);
+}
+
+#[test]
+fn test_failed_submission_keeps_siblings() {
+    let mut engine = TriggerEngine::new();
+    let stop = engine.add(order(
+        false,
+        Trigger::StopLoss { trigger_price: 0.9 },
+        Some(7),
+    ));
+    let target = engine.add(order(
+        false,
+        Trigger::TakeProfit { trigger_price: 1.2 },
+        Some(7),
+    ));
+
+    assert_eq!(engine.evaluate("DEEP_SUI", &mid(1.25), 0).len(), 1);
+    engine.mark_failed(target, "Insufficient balance".to_string());
+    assert_eq!(
+        engine.order(target).unwrap().status,
+        ConditionalStatus::Failed("Insufficient balance".to_string())
+    );
+
+    let fired = engine.evaluate("DEEP_SUI", &mid(0.8), 0);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].id, stop);
+}
+
+#[test]
+fn test_expiry_and_other_pools() {
+    let mut engine = TriggerEngine::new();
+    let mut expiring = order(false, Trigger::StopLoss { trigger_price: 0.9 }, None);
+    expiring.expires_at_ms = Some(100);
+    let expiring = engine.add(expiring);
+    let other = engine.add(order(false, Trigger::StopLoss { trigger_price: 0.9 }, None));
+
+    assert!(engine.evaluate("SUI_USDC", &mid(0.5), 100).is_empty());
+    assert_eq!(
+        engine.order(expiring).unwrap().status,
+        ConditionalStatus::Expired
+    );
+    assert_eq!(
+        engine.order(other).unwrap().status,
+        ConditionalStatus::Pending
+    );
+    assert_eq!(engine.pending_pools(), vec!["DEEP_SUI".to_string()]);
+}
+
+#[test]
+fn test_state_file_round_trip() {
+    let path = std::env::temp_dir().join(format!("conditional_{}.json", std::process::id()));
+    let mut engine = TriggerEngine::load(&path).unwrap();
+    engine.add(order(
+        false,
+        Trigger::TrailingStop {
+            distance: TrailingDistance::Absolute(0.1),
+            extreme: None,
+        },
+        None,
+    ));
+    engine.evaluate("DEEP_SUI", &mid(1.5), 0);
+    engine.save(&path).unwrap();
+
+    let mut restored = TriggerEngine::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        restored.orders().collect::<Vec<_>>(),
+        engine.orders().collect::<Vec<_>>()
+    );
+    assert_eq!(restored.evaluate("DEEP_SUI", &mid(1.35), 0).len(), 1);
+    assert_eq!(
+        restored.add(order(true, Trigger::StopLoss { trigger_price: 1.0 }, None)),
+        1
+    );
+}
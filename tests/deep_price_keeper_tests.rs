@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use sui_deepbookv3::{
+    deep_price_keeper::{is_stale, select_reference_pool},
+    utils::config::PoolMap,
+};
+use utils::pool_config;
+
+mod utils;
+
+fn pools() -> PoolMap {
+    let mut pools = PoolMap::new();
+    pools.insert("DEEP_SUI", pool_config("DEEP", "SUI"));
+    pools.insert("DEEP_USDC", pool_config("DEEP", "USDC"));
+    pools.insert("SUI_USDC", pool_config("SUI", "USDC"));
+    pools.insert("WETH_USDC", pool_config("WETH", "USDC"));
+    pools
+}
+
+#[test]
+fn test_reference_pool_prefers_base_asset() {
+    let pools = pools();
+    let whitelisted = ["DEEP_SUI", "DEEP_USDC"];
+    assert_eq!(
+        select_reference_pool(&pools, "SUI_USDC", &whitelisted),
+        Some("DEEP_SUI")
+    );
+    assert_eq!(
+        select_reference_pool(&pools, "WETH_USDC", &whitelisted),
+        Some("DEEP_USDC")
+    );
+    assert_eq!(
+        select_reference_pool(&pools, "DEEP_SUI", &whitelisted),
+        None
+    );
+    assert_eq!(select_reference_pool(&pools, "MISSING", &whitelisted), None);
+}
+
+#[test]
+fn test_reference_pool_must_be_whitelisted() {
+    let pools = pools();
+    assert_eq!(
+        select_reference_pool(&pools, "SUI_USDC", &["DEEP_USDC"]),
+        Some("DEEP_USDC")
+    );
+    assert_eq!(select_reference_pool(&pools, "SUI_USDC", &[]), None);
+}
+
+#[test]
+fn test_staleness() {
+    let max_age = Duration::from_secs(60);
+    assert!(is_stale(None, 1_000, max_age));
+    assert!(!is_stale(Some(100_000), 159_999, max_age));
+    assert!(is_stale(Some(100_000), 160_000, max_age));
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use sui_deepbookv3::{
+    transactions::{balance_manager::BalanceManagerContract, deepbook::DeepBookContract},
+    utils::{
+        config::{DeepBookConfig, Environment},
+        types::{BalanceManager, ClientOrderId, PlaceLimitOrderParams},
+    },
+};
+use sui_sdk::{
+    types::{
+        base_types::SuiAddress,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{Argument, CallArg, Command, ObjectArg, ProgrammableTransaction},
+    },
+    SuiClientBuilder,
+};
+
+async fn contracts() -> (DeepBookConfig, BalanceManagerContract, DeepBookContract) {
+    let sui_client = SuiClientBuilder::default().build_testnet().await.unwrap();
+    let balance_managers = HashMap::from([(
+        "MANAGER_1",
+        BalanceManager {
+            address: "0x722c39b7b79831d534fbfa522e07101cb881f8807c28b9cf03a58b04c6c5ca9a"
+                .to_string(),
+            trade_cap: None,
+        },
+    )]);
+    let config = DeepBookConfig::new(
+        Environment::Testnet,
+        SuiAddress::ZERO,
+        None,
+        Some(balance_managers),
+        None,
+        None,
+    );
+    let balance_manager = BalanceManagerContract::new(sui_client.clone(), config.clone());
+    let deep_book = DeepBookContract::new(sui_client, config.clone(), balance_manager.clone());
+    (config, balance_manager, deep_book)
+}
+
+fn call(transaction: &ProgrammableTransaction, index: usize) -> (&str, &[Argument]) {
+    match &transaction.commands[index] {
+        Command::MoveCall(call) => (call.function.as_str(), &call.arguments),
+        command => panic!("Expected a Move call, got {:?}", command),
+    }
+}
+
+fn is_mutable_shared(transaction: &ProgrammableTransaction, argument: Argument) -> bool {
+    let Argument::Input(index) = argument else {
+        return false;
+    };
+    matches!(
+        transaction.inputs[index as usize],
+        CallArg::Object(ObjectArg::SharedObject { mutable: true, .. })
+    )
+}
+
+#[tokio::test]
+async fn test_by_ref_generates_proof_before_call() {
+    let (config, _, deep_book) = contracts().await;
+    let pool = config.pool_ref("DEEP_SUI").unwrap();
+    let manager = config.balance_manager_ref("MANAGER_1").unwrap();
+
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    deep_book
+        .withdraw_settled_amounts_by_ref(&mut ptb, &pool, &manager)
+        .await
+        .unwrap();
+    let transaction = ptb.finish();
+
+    assert_eq!(transaction.commands.len(), 2);
+    assert_eq!(call(&transaction, 0).0, "generate_proof_as_owner");
+    let (function, arguments) = call(&transaction, 1);
+    assert_eq!(function, "withdraw_settled_amounts");
+    assert_eq!(arguments[2], Argument::Result(0));
+    assert!(is_mutable_shared(&transaction, arguments[0]));
+    assert!(is_mutable_shared(&transaction, arguments[1]));
+}
+
+#[tokio::test]
+async fn test_with_proof_calls_share_one_proof() {
+    let (config, balance_manager, deep_book) = contracts().await;
+    let pool = config.pool_ref("DEEP_SUI").unwrap();
+    let manager = config.balance_manager_ref("MANAGER_1").unwrap();
+
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    let proof = balance_manager
+        .generate_proof_by_ref(&mut ptb, &manager)
+        .await
+        .unwrap();
+    deep_book
+        .claim_rebates_with_proof(&mut ptb, &pool, &manager, proof)
+        .await
+        .unwrap();
+    deep_book
+        .withdraw_settled_amounts_with_proof(&mut ptb, &pool, &manager, proof)
+        .await
+        .unwrap();
+    let transaction = ptb.finish();
+
+    // One proof, then both calls reusing it
+    assert_eq!(transaction.commands.len(), 3);
+    assert_eq!(call(&transaction, 1).0, "claim_rebates");
+    assert_eq!(call(&transaction, 2).0, "withdraw_settled_amounts");
+    for index in 1..3 {
+        let (_, arguments) = call(&transaction, index);
+        assert_eq!(arguments[2], proof);
+        assert!(is_mutable_shared(&transaction, arguments[0]));
+    }
+}
+
+#[tokio::test]
+async fn test_place_limit_order_shares_pool_and_manager_mutably() {
+    let (config, balance_manager, deep_book) = contracts().await;
+    let pool = config.pool_ref("DEEP_SUI").unwrap();
+    let manager = config.balance_manager_ref("MANAGER_1").unwrap();
+
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    let proof = balance_manager
+        .generate_proof_by_ref(&mut ptb, &manager)
+        .await
+        .unwrap();
+    let params = PlaceLimitOrderParams {
+        pool_key: String::new(),
+        balance_manager_key: String::new(),
+        client_order_id: ClientOrderId(1),
+        price: 0.5,
+        quantity: 10.0,
+        is_bid: true,
+        expiration: None,
+        order_type: None,
+        self_matching_option: None,
+        pay_with_deep: None,
+    };
+    deep_book
+        .place_limit_order_with_proof(&mut ptb, &pool, &manager, proof, params)
+        .await
+        .unwrap();
+    let transaction = ptb.finish();
+
+    let (function, arguments) = call(&transaction, 1);
+    assert_eq!(function, "place_limit_order");
+    assert_eq!(arguments[2], proof);
+    assert!(is_mutable_shared(&transaction, arguments[0]));
+    assert!(is_mutable_shared(&transaction, arguments[1]));
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sui_deepbookv3::{
+    client::PoolBookParams,
+    execution::{
+        slice_schedule, volume_weights, ChildOrder, ChildOrderExecutor, ChildStatus, Clock,
+        ExecutionAlgorithm, ExecutionHandle, ExecutionScheduler, ExecutionState, ParentOrder,
+    },
+    utils::{client_order_id::MonotonicIdGenerator, types::ClientOrderId},
+};
+
+#[derive(Default)]
+struct ManualClock {
+    now: AtomicU64,
+}
+
+#[async_trait]
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    async fn sleep_until(&self, deadline_ms: u64) {
+        self.now.fetch_max(deadline_ms, Ordering::SeqCst);
+    }
+}
+
+/// Fills `fill_ratio` of each child; resting children fill on the first status check
+struct MockExecutor {
+    clock: Arc<ManualClock>,
+    fill_ratio: f64,
+    placed: Vec<(u64, ChildOrder)>,
+    statuses: HashMap<ClientOrderId, ChildStatus>,
+    cancel_after: Option<(usize, ExecutionHandle)>,
+}
+
+impl MockExecutor {
+    fn new(clock: Arc<ManualClock>, fill_ratio: f64) -> Self {
+        Self {
+            clock,
+            fill_ratio,
+            placed: Vec::new(),
+            statuses: HashMap::new(),
+            cancel_after: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ChildOrderExecutor for MockExecutor {
+    async fn place(&mut self, order: &ChildOrder) -> anyhow::Result<()> {
+        self.placed.push((self.clock.now_ms(), order.clone()));
+        self.statuses.insert(
+            order.client_order_id,
+            ChildStatus {
+                filled_quantity: order.quantity * self.fill_ratio,
+                open: false,
+            },
+        );
+        if let Some((count, handle)) = &self.cancel_after {
+            if self.placed.len() == *count {
+                handle.cancel();
+            }
+        }
+        Ok(())
+    }
+
+    async fn status(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<ChildStatus> {
+        Ok(self.statuses[&client_order_id])
+    }
+
+    async fn cancel(&mut self, client_order_id: ClientOrderId) -> anyhow::Result<()> {
+        self.statuses.get_mut(&client_order_id).unwrap().open = false;
+        Ok(())
+    }
+}
+
+fn parent(quantity: f64, limit_price: Option<f64>) -> ParentOrder {
+    ParentOrder {
+        pool_key: "DEEP_SUI".to_string(),
+        is_bid: true,
+        quantity,
+        limit_price,
+        pay_with_deep: None,
+    }
+}
+
+fn book() -> PoolBookParams {
+    PoolBookParams {
+        tick_size: 0.01,
+        lot_size: 1.0,
+        min_size: 1.0,
+    }
+}
+
+fn twap(slices: usize) -> ExecutionAlgorithm {
+    ExecutionAlgorithm::Twap {
+        slices,
+        interval: Duration::from_secs(60),
+    }
+}
+
+#[test]
+fn test_slice_schedules() {
+    let slices = slice_schedule(&twap(4), 100.0, 1_000).unwrap();
+    assert_eq!(
+        slices,
+        vec![
+            (1_000, 25.0),
+            (61_000, 25.0),
+            (121_000, 25.0),
+            (181_000, 25.0)
+        ]
+    );
+
+    let vwap = ExecutionAlgorithm::Vwap {
+        weights: vec![1.0, 3.0],
+        interval: Duration::from_secs(1),
+    };
+    let slices = slice_schedule(&vwap, 100.0, 0).unwrap();
+    assert_eq!(slices, vec![(0, 25.0), (1_000, 75.0)]);
+}
+
+#[test]
+fn test_volume_weights_bucket_by_time_of_day() {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    let samples = [
+        (500, 1.0),
+        (DAY_MS + 1_500, 2.0),
+        (DAY_MS + 1_700, 3.0),
+        (5_000, 9.0),
+    ];
+    let weights = volume_weights(&samples, 2 * DAY_MS, Duration::from_secs(1), 2);
+    assert_eq!(weights, vec![1.0, 5.0]);
+}
+
+#[tokio::test]
+async fn test_twap_sends_slices_on_schedule() {
+    let clock = Arc::new(ManualClock::default());
+    let mut executor = MockExecutor::new(clock.clone(), 1.0);
+    let (mut scheduler, handle) = ExecutionScheduler::new(parent(100.0, None), twap(4), book());
+
+    let progress = scheduler
+        .run(&mut executor, clock.as_ref(), &MonotonicIdGenerator::new(1))
+        .await
+        .unwrap();
+
+    assert_eq!(progress.state, ExecutionState::Completed);
+    assert_eq!(progress.filled_quantity, 100.0);
+    assert_eq!(handle.progress(), progress);
+    let sent: Vec<(u64, f64, Option<f64>)> = executor
+        .placed
+        .iter()
+        .map(|(at, child)| (*at, child.quantity, child.price))
+        .collect();
+    assert_eq!(
+        sent,
+        vec![
+            (0, 25.0, None),
+            (60_000, 25.0, None),
+            (120_000, 25.0, None),
+            (180_000, 25.0, None)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_unfilled_quantity_carries_into_next_slice() {
+    let clock = Arc::new(ManualClock::default());
+    let mut executor = MockExecutor::new(clock.clone(), 0.5);
+    let (mut scheduler, _handle) =
+        ExecutionScheduler::new(parent(100.0, Some(1.0)), twap(2), book());
+
+    let progress = scheduler
+        .run(&mut executor, clock.as_ref(), &MonotonicIdGenerator::new(1))
+        .await
+        .unwrap();
+
+    let quantities: Vec<f64> = executor
+        .placed
+        .iter()
+        .map(|(_, child)| child.quantity)
+        .collect();
+    assert_eq!(quantities, vec![50.0, 75.0]);
+    assert_eq!(progress.filled_quantity, 62.5);
+    assert_eq!(progress.remaining_quantity, 37.5);
+}
+
+#[tokio::test]
+async fn test_iceberg_refills_display_quantity() {
+    let clock = Arc::new(ManualClock::default());
+    let mut executor = MockExecutor::new(clock.clone(), 1.0);
+    let algorithm = ExecutionAlgorithm::Iceberg {
+        display_quantity: 40.0,
+        poll_interval: Duration::from_secs(5),
+    };
+    let (mut scheduler, _handle) =
+        ExecutionScheduler::new(parent(100.0, Some(1.0)), algorithm, book());
+
+    let progress = scheduler
+        .run(&mut executor, clock.as_ref(), &MonotonicIdGenerator::new(1))
+        .await
+        .unwrap();
+
+    assert_eq!(progress.state, ExecutionState::Completed);
+    let sent: Vec<(u64, f64)> = executor
+        .placed
+        .iter()
+        .map(|(at, child)| (*at, child.quantity))
+        .collect();
+    assert_eq!(sent, vec![(0, 40.0), (5_000, 40.0), (10_000, 20.0)]);
+}
+
+#[tokio::test]
+async fn test_cancel_stops_execution() {
+    let clock = Arc::new(ManualClock::default());
+    let mut executor = MockExecutor::new(clock.clone(), 1.0);
+    let (mut scheduler, handle) = ExecutionScheduler::new(parent(100.0, None), twap(4), book());
+    executor.cancel_after = Some((2, handle.clone()));
+
+    let progress = scheduler
+        .run(&mut executor, clock.as_ref(), &MonotonicIdGenerator::new(1))
+        .await
+        .unwrap();
+
+    assert_eq!(progress.state, ExecutionState::Cancelled);
+    assert_eq!(executor.placed.len(), 2);
+    assert_eq!(progress.filled_quantity, 50.0);
+    assert_eq!(progress.children.len(), 2);
+}
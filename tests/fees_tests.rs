@@ -0,0 +1,100 @@
+use sui_deepbookv3::{
+    client::{PoolDeepPrice, PoolTradeParams},
+    fees::{plan_fees, FeeRole, PlannedTrade},
+};
+
+fn trade_params() -> PoolTradeParams {
+    PoolTradeParams {
+        taker_fee: 0.001,
+        maker_fee: 0.0005,
+        stake_required: 0.0,
+    }
+}
+
+fn deep_per_quote(deep: f64) -> PoolDeepPrice {
+    PoolDeepPrice {
+        asset_is_base: false,
+        deep_per_base: None,
+        deep_per_quote: Some(deep),
+    }
+}
+
+fn bid(role: FeeRole) -> PlannedTrade {
+    PlannedTrade {
+        is_bid: true,
+        price: 2.0,
+        quantity: 100.0,
+        role,
+    }
+}
+
+#[test]
+fn test_deep_is_cheaper_at_pool_conversion() {
+    let plan = plan_fees(
+        &bid(FeeRole::Taker),
+        &trade_params(),
+        &deep_per_quote(10.0),
+        5.0,
+        None,
+    )
+    .unwrap();
+
+    // 200 quote notional, 10 DEEP per quote, 0.1% taker fee
+    assert_eq!(plan.deep_fee, 2.0);
+    assert!(!plan.input_is_base);
+    assert!((plan.input_fee - 0.25).abs() < 1e-12);
+    assert!(plan.deep_is_cheaper);
+    assert!(plan.pay_with_deep);
+    assert_eq!(plan.deep_shortfall, 0.0);
+}
+
+#[test]
+fn test_shortfall_reported_when_balance_is_low() {
+    let plan = plan_fees(
+        &bid(FeeRole::Maker),
+        &trade_params(),
+        &deep_per_quote(10.0),
+        0.4,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(plan.deep_fee, 1.0);
+    assert!(plan.deep_is_cheaper);
+    assert!(!plan.pay_with_deep);
+    assert_eq!(plan.deep_shortfall, 0.6);
+}
+
+#[test]
+fn test_input_asset_wins_when_deep_trades_above_conversion() {
+    // The pool values DEEP at 0.1 quote, the market at 0.2
+    let plan = plan_fees(
+        &bid(FeeRole::Taker),
+        &trade_params(),
+        &deep_per_quote(10.0),
+        5.0,
+        Some(0.2),
+    )
+    .unwrap();
+
+    assert_eq!(plan.deep_fee_value, Some(0.4));
+    assert!(!plan.deep_is_cheaper);
+    assert!(!plan.pay_with_deep);
+}
+
+#[test]
+fn test_missing_conversion_is_an_error() {
+    let deep_price = PoolDeepPrice {
+        asset_is_base: true,
+        deep_per_base: None,
+        deep_per_quote: None,
+    };
+    assert!(plan_fees(
+        &bid(FeeRole::Taker),
+        &trade_params(),
+        &deep_price,
+        5.0,
+        None
+    )
+    .is_err());
+}
@@ -0,0 +1,43 @@
+use sui_deepbookv3::utils::{config::Environment, file_config::FileConfig};
+
+#[test]
+fn test_minimal_config_uses_defaults() {
+    let config = FileConfig::parse(
+        r#"{
+            "env": "testnet",
+            "balance_managers": {
+                "MANAGER_1": { "address": "0x1" }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.env, Environment::Testnet);
+    assert_eq!(config.rpc_url(), "https://fullnode.testnet.sui.io:443");
+    assert!(config.address.is_none());
+
+    let managers = config.balance_managers();
+    assert_eq!(managers["MANAGER_1"].address, "0x1");
+    assert!(managers["MANAGER_1"].trade_cap.is_none());
+}
+
+#[test]
+fn test_config_overrides() {
+    let config = FileConfig::parse(
+        r#"{
+            "env": "mainnet",
+            "rpc_url": "http://localhost:9000",
+            "gas_budget": 1000,
+            "wallet_config": "/tmp/client.yaml"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.rpc_url(), "http://localhost:9000");
+    assert_eq!(config.gas_budget(), 1000);
+    assert_eq!(
+        config.wallet_config().unwrap().to_str(),
+        Some("/tmp/client.yaml")
+    );
+    assert!(FileConfig::parse(r#"{ "env": "devnet" }"#).is_err());
+}
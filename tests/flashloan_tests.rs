@@ -0,0 +1,88 @@
+use sui_deepbookv3::{
+    transactions::flashloan::{FlashLoanAsset, FlashLoanContract},
+    utils::config::{DeepBookConfig, Environment},
+};
+use sui_sdk::{
+    types::{
+        base_types::SuiAddress,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{Argument, Command},
+    },
+    SuiClientBuilder,
+};
+
+async fn flash_loans() -> FlashLoanContract {
+    let sui_client = SuiClientBuilder::default().build_testnet().await.unwrap();
+    let config = DeepBookConfig::new(
+        Environment::Testnet,
+        SuiAddress::ZERO,
+        None,
+        None,
+        None,
+        None,
+    );
+    FlashLoanContract::new(sui_client, config)
+}
+
+#[tokio::test]
+async fn test_session_borrows_runs_body_and_repays() {
+    let flash_loans = flash_loans().await;
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    ptb.pure(7u64).unwrap();
+
+    let remainder = flash_loans
+        .session("DEEP_SUI", FlashLoanAsset::Base, 1.0)
+        .unwrap()
+        .run(&mut ptb, async |ptb, borrowed| {
+            ptb.transfer_arg(SuiAddress::ZERO, Argument::GasCoin);
+            Ok(borrowed)
+        })
+        .await
+        .unwrap();
+
+    let transaction = ptb.finish();
+    // Borrow, body, split of the repaid quantity, return
+    assert_eq!(transaction.commands.len(), 4);
+    assert!(matches!(transaction.commands[0], Command::MoveCall(_)));
+    assert!(matches!(
+        transaction.commands[1],
+        Command::TransferObjects(..)
+    ));
+    assert!(matches!(transaction.commands[2], Command::SplitCoins(..)));
+    assert!(matches!(transaction.commands[3], Command::MoveCall(_)));
+    assert_eq!(remainder, Argument::NestedResult(0, 0));
+}
+
+#[tokio::test]
+async fn test_session_keeps_commands_when_body_fails() {
+    let flash_loans = flash_loans().await;
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    ptb.transfer_arg(SuiAddress::ZERO, Argument::GasCoin);
+
+    let result = flash_loans
+        .session("DEEP_SUI", FlashLoanAsset::Quote, 1.0)
+        .unwrap()
+        .run(&mut ptb, async |ptb, _| {
+            ptb.transfer_arg(SuiAddress::ZERO, Argument::GasCoin);
+            Err(anyhow::anyhow!("Body failed"))
+        })
+        .await;
+
+    assert!(result.is_err());
+    // The caller's command, the borrow and the body's command are all still there
+    assert_eq!(ptb.finish().commands.len(), 3);
+}
+
+#[tokio::test]
+async fn test_session_rejects_repaying_with_the_flash_loan() {
+    let flash_loans = flash_loans().await;
+    let mut ptb = ProgrammableTransactionBuilder::new();
+
+    let result = flash_loans
+        .session("DEEP_SUI", FlashLoanAsset::Base, 1.0)
+        .unwrap()
+        .run(&mut ptb, async |_, _| Ok(Argument::NestedResult(0, 1)))
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,160 @@
+use sui_deepbookv3::{
+    client::{AccountStake, GovernanceProposal, GovernanceState, PoolTradeParams},
+    governance_simulator::{stake_to_voting_power, GovernanceAction, GovernanceSimulator},
+    utils::types::ProposalParams,
+};
+use sui_sdk::types::base_types::ObjectID;
+
+fn trade_params(taker_fee: f64, maker_fee: f64) -> PoolTradeParams {
+    PoolTradeParams {
+        taker_fee,
+        maker_fee,
+        stake_required: 100.0,
+    }
+}
+
+fn proposal(id: u8, taker_fee: f64, votes: f64) -> GovernanceProposal {
+    GovernanceProposal {
+        proposal_id: ObjectID::from_single_byte(id),
+        taker_fee,
+        maker_fee: taker_fee / 2.0,
+        stake_required: 100.0,
+        votes,
+        quorum: 1_000.0,
+        quorum_reached: votes >= 1_000.0,
+    }
+}
+
+fn state() -> GovernanceState {
+    GovernanceState {
+        epoch: 10,
+        whitelisted: false,
+        stable: false,
+        voting_power: 2_000.0,
+        quorum: 1_000.0,
+        trade_params: trade_params(0.001, 0.0005),
+        next_trade_params: trade_params(0.001, 0.0005),
+        proposals: vec![proposal(1, 0.0008, 700.0), proposal(2, 0.0006, 200.0)],
+    }
+}
+
+fn account(active_stake: f64) -> AccountStake {
+    AccountStake {
+        epoch: 10,
+        active_stake,
+        inactive_stake: 0.0,
+        created_proposal: false,
+        voted_proposal: None,
+    }
+}
+
+#[test]
+fn test_vote_reaches_quorum() {
+    let manager_id = ObjectID::from_single_byte(9);
+    let mut simulator = GovernanceSimulator::new(manager_id, &state(), Some(&account(300.0)));
+
+    let outcome = simulator.outcome();
+    assert!(!outcome.quorum_reached);
+    assert_eq!(outcome.next_trade_params.taker_fee, 0.001);
+
+    simulator
+        .apply(&GovernanceAction::Vote {
+            proposal_id: ObjectID::from_single_byte(1),
+        })
+        .unwrap();
+    let outcome = simulator.outcome();
+    assert_eq!(
+        outcome.winning_proposal,
+        Some(ObjectID::from_single_byte(1))
+    );
+    assert_eq!(outcome.next_trade_params.taker_fee, 0.0008);
+    assert_eq!(outcome.next_trade_params.maker_fee, 0.0004);
+
+    // Moving the vote drops the first proposal below quorum and restores current fees.
+    simulator
+        .apply(&GovernanceAction::Vote {
+            proposal_id: ObjectID::from_single_byte(2),
+        })
+        .unwrap();
+    let outcome = simulator.outcome();
+    assert_eq!(outcome.winning_proposal, None);
+    assert_eq!(outcome.next_trade_params.taker_fee, 0.001);
+    assert_eq!(outcome.proposals[1].votes, 500.0);
+}
+
+#[test]
+fn test_submit_proposal_requires_stake() {
+    let manager_id = ObjectID::from_single_byte(9);
+    let params = ProposalParams {
+        pool_key: String::new(),
+        balance_manager_key: String::new(),
+        taker_fee: 0.0007,
+        maker_fee: 0.0003,
+        stake_required: 50.0,
+    };
+
+    let mut simulator = GovernanceSimulator::new(manager_id, &state(), Some(&account(50.0)));
+    assert!(simulator
+        .apply(&GovernanceAction::SubmitProposal {
+            params: params.clone()
+        })
+        .is_err());
+
+    let mut simulator = GovernanceSimulator::new(manager_id, &state(), Some(&account(1_000.0)));
+    simulator
+        .apply(&GovernanceAction::SubmitProposal { params })
+        .unwrap();
+    let outcome = simulator.outcome();
+    assert_eq!(outcome.winning_proposal, Some(manager_id));
+    assert_eq!(outcome.next_trade_params.taker_fee, 0.0007);
+    assert_eq!(outcome.voted_proposal, Some(manager_id));
+}
+
+#[test]
+fn test_stake_only_changes_next_quorum() {
+    let mut simulator =
+        GovernanceSimulator::new(ObjectID::from_single_byte(9), &state(), Some(&account(0.0)));
+    simulator
+        .apply(&GovernanceAction::Stake { amount: 2_000.0 })
+        .unwrap();
+
+    let outcome = simulator.outcome();
+    assert_eq!(outcome.quorum, 1_000.0);
+    assert_eq!(outcome.next_quorum, 2_000.0);
+    assert_eq!(outcome.inactive_stake, 2_000.0);
+}
+
+#[test]
+fn test_voting_power_is_sqrt_above_threshold() {
+    let threshold = 100_000_000_000;
+    assert_eq!(stake_to_voting_power(1_000_000), 1_000_000);
+    assert_eq!(stake_to_voting_power(threshold), threshold);
+    // 400k DEEP: 100k linear plus sqrt(400k) - sqrt(100k) DEEP.
+    let power = stake_to_voting_power(4 * threshold);
+    assert_eq!(power, threshold + 632_455_532 - 316_227_766);
+}
+
+#[test]
+fn test_stale_governance_rolls_forward_to_account_epoch() {
+    let manager_id = ObjectID::from_single_byte(9);
+    let account = AccountStake {
+        epoch: 11,
+        ..account(300.0)
+    };
+    let mut stale = state();
+    stale.next_trade_params = trade_params(0.0008, 0.0004);
+    stale.voting_power = 3_000.0;
+    let mut simulator = GovernanceSimulator::new(manager_id, &stale, Some(&account));
+
+    // The chain cleared last epoch's proposals, so voting on one aborts
+    assert!(simulator
+        .apply(&GovernanceAction::Vote {
+            proposal_id: ObjectID::from_single_byte(1),
+        })
+        .is_err());
+
+    let outcome = simulator.outcome();
+    assert!(outcome.proposals.is_empty());
+    assert_eq!(outcome.quorum, 1_500.0);
+    assert_eq!(outcome.next_trade_params.taker_fee, 0.0008);
+}
@@ -0,0 +1,125 @@
+use serde_json::{json, Value};
+use sui_deepbookv3::client::GovernanceState;
+use sui_sdk::types::base_types::ObjectID;
+
+fn trade_params(taker_fee: &str, maker_fee: &str, stake_required: &str) -> Value {
+    json!({
+        "type": "0x2::trade_params::TradeParams",
+        "fields": {
+            "taker_fee": taker_fee,
+            "maker_fee": maker_fee,
+            "stake_required": stake_required,
+        }
+    })
+}
+
+fn proposal(key: &str, votes: &str) -> Value {
+    json!({
+        "type": "0x2::vec_map::Entry",
+        "fields": {
+            "key": key,
+            "value": {
+                "type": "0x2::governance::Proposal",
+                "fields": {
+                    "taker_fee": "500000",
+                    "maker_fee": "200000",
+                    "stake_required": "10000000",
+                    "votes": votes,
+                }
+            }
+        }
+    })
+}
+
+// `PoolInner` as rendered by the JSON-RPC API, with the `fields` wrappers it adds
+fn pool_inner() -> Value {
+    json!({
+        "state": {
+            "type": "0x2::state::State",
+            "fields": {
+                "governance": {
+                    "type": "0x2::governance::Governance",
+                    "fields": {
+                        "epoch": "512",
+                        "whitelisted": false,
+                        "stable": true,
+                        "voting_power": "12500000",
+                        "quorum": "5000000",
+                        "trade_params": trade_params("1000000", "500000", "100000000"),
+                        "next_trade_params": trade_params("800000", "400000", "100000000"),
+                        "proposals": {
+                            "type": "0x2::vec_map::VecMap",
+                            "fields": {
+                                "contents": [
+                                    proposal("0x1", "6000000"),
+                                    proposal("0x2", "4999999"),
+                                ]
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[test]
+fn test_governance_state_decodes_pool_inner() {
+    let state = GovernanceState::from_pool_inner(&pool_inner()).unwrap();
+
+    assert_eq!(state.epoch, 512);
+    assert!(!state.whitelisted);
+    assert!(state.stable);
+    assert_eq!(state.voting_power, 12.5);
+    assert_eq!(state.quorum, 5.0);
+    assert_eq!(state.trade_params.taker_fee, 0.001);
+    assert_eq!(state.trade_params.maker_fee, 0.0005);
+    assert_eq!(state.trade_params.stake_required, 100.0);
+    assert_eq!(state.next_trade_params.taker_fee, 0.0008);
+
+    assert_eq!(state.proposals.len(), 2);
+    let first = &state.proposals[0];
+    assert_eq!(
+        first.proposal_id,
+        ObjectID::from_hex_literal("0x1").unwrap()
+    );
+    assert_eq!(first.taker_fee, 0.0005);
+    assert_eq!(first.stake_required, 10.0);
+    assert_eq!(first.votes, 6.0);
+    assert_eq!(first.quorum, 5.0);
+    assert!(first.quorum_reached);
+    assert!(!state.proposals[1].quorum_reached);
+}
+
+#[test]
+fn test_governance_state_rejects_missing_fields() {
+    let mut inner = pool_inner();
+    inner["state"]["fields"]["governance"]["fields"]
+        .as_object_mut()
+        .unwrap()
+        .remove("quorum");
+    assert!(GovernanceState::from_pool_inner(&inner).is_err());
+}
+
+#[test]
+fn test_stale_governance_rolls_forward() {
+    let mut state = GovernanceState::from_pool_inner(&pool_inner()).unwrap();
+    state.roll_forward(513);
+
+    assert_eq!(state.epoch, 513);
+    assert!(state.proposals.is_empty());
+    assert_eq!(state.trade_params.taker_fee, 0.0008);
+    assert_eq!(state.trade_params.maker_fee, 0.0004);
+    assert_eq!(state.quorum, 6.25);
+}
+
+#[test]
+fn test_current_governance_is_unchanged() {
+    let mut state = GovernanceState::from_pool_inner(&pool_inner()).unwrap();
+    state.roll_forward(512);
+
+    assert_eq!(state.epoch, 512);
+    assert_eq!(state.proposals.len(), 2);
+    assert_eq!(state.trade_params.taker_fee, 0.001);
+    assert_eq!(state.quorum, 5.0);
+}
@@ -0,0 +1,141 @@
+use serde_json::json;
+use sui_deepbookv3::{
+    history::{compute_pnl, fills_from_event, CostBasisMethod, FeeAsset, Fill, TradeRole},
+    utils::{
+        config::{DeepBookConfig, Environment},
+        types::{ClientOrderId, OrderId},
+    },
+};
+use sui_sdk::{
+    rpc_types::SuiEvent,
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        digests::TransactionDigest,
+    },
+};
+
+fn fill(is_bid: bool, price: f64, base_quantity: f64) -> Fill {
+    Fill {
+        pool_key: "DEEP_SUI".to_string(),
+        pool_id: ObjectID::ZERO,
+        order_id: OrderId(0),
+        client_order_id: ClientOrderId(0),
+        is_bid,
+        role: TradeRole::Taker,
+        price,
+        base_quantity,
+        quote_quantity: price * base_quantity,
+        fee: 0.1,
+        fee_is_deep: true,
+        fee_asset: FeeAsset::Deep,
+        timestamp_ms: 0,
+        tx_digest: String::new(),
+    }
+}
+
+fn fills() -> Vec<Fill> {
+    vec![
+        fill(true, 1.0, 10.0),
+        fill(true, 2.0, 10.0),
+        fill(false, 3.0, 15.0),
+    ]
+}
+
+#[test]
+fn test_fifo_pnl() {
+    let pnl = compute_pnl(&fills(), CostBasisMethod::Fifo, Some(4.0));
+
+    // 10 @ 1.0 and 5 @ 2.0 closed at 3.0
+    assert_eq!(pnl.realized, 25.0);
+    assert_eq!(pnl.position, 5.0);
+    assert_eq!(pnl.average_entry_price, 2.0);
+    assert_eq!(pnl.unrealized, Some(10.0));
+    assert!((pnl.fees_deep - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn test_average_cost_pnl() {
+    let pnl = compute_pnl(&fills(), CostBasisMethod::AverageCost, Some(4.0));
+
+    // 15 closed at 3.0 against an average entry of 1.5
+    assert_eq!(pnl.realized, 22.5);
+    assert_eq!(pnl.position, 5.0);
+    assert_eq!(pnl.average_entry_price, 1.5);
+    assert_eq!(pnl.unrealized, Some(12.5));
+}
+
+#[test]
+fn test_position_flips_short() {
+    let fills = vec![fill(true, 2.0, 5.0), fill(false, 3.0, 8.0)];
+
+    for method in [CostBasisMethod::Fifo, CostBasisMethod::AverageCost] {
+        let pnl = compute_pnl(&fills, method, Some(2.5));
+        assert_eq!(pnl.realized, 5.0);
+        assert_eq!(pnl.position, -3.0);
+        assert_eq!(pnl.average_entry_price, 3.0);
+        assert_eq!(pnl.unrealized, Some(1.5));
+    }
+}
+
+#[test]
+fn test_self_trade_records_both_sides() {
+    let config = DeepBookConfig::new(
+        Environment::Testnet,
+        SuiAddress::ZERO,
+        None,
+        None,
+        None,
+        None,
+    );
+    let manager_id = ObjectID::from_hex_literal("0x1").unwrap();
+    let pool_id = config.get_pool("DEEP_SUI").unwrap().address.clone();
+    let package = config.deepbook_package_id();
+    let event: SuiEvent = serde_json::from_value(json!({
+        "id": { "txDigest": TransactionDigest::new([1; 32]).to_string(), "eventSeq": "0" },
+        "packageId": package,
+        "transactionModule": "pool",
+        "sender": SuiAddress::ZERO.to_string(),
+        "type": format!("{}::order_info::OrderFilled", package),
+        "parsedJson": {
+            "pool_id": pool_id,
+            "maker_order_id": "1",
+            "taker_order_id": "2",
+            "maker_client_order_id": "3",
+            "taker_client_order_id": "4",
+            "price": "1000000",
+            "taker_is_bid": true,
+            "taker_fee": "2000",
+            "taker_fee_is_deep": true,
+            "maker_fee": "1000",
+            "maker_fee_is_deep": false,
+            "base_quantity": "5000000",
+            "quote_quantity": "5000000",
+            "maker_balance_manager_id": "0x1",
+            "taker_balance_manager_id": "0x1",
+            "timestamp": "10",
+        },
+        "bcsEncoding": "base64",
+        "bcs": "",
+        "timestampMs": "10",
+    }))
+    .unwrap();
+
+    let fills = fills_from_event(&config, manager_id, &event).unwrap();
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0].role, TradeRole::Maker);
+    assert!(!fills[0].is_bid);
+    assert_eq!(fills[0].client_order_id, ClientOrderId(3));
+    assert_eq!(fills[0].fee_asset, FeeAsset::Base);
+    assert_eq!(fills[1].role, TradeRole::Taker);
+    assert!(fills[1].is_bid);
+    assert_eq!(fills[1].order_id, OrderId(2));
+    assert_eq!(fills[1].fee_asset, FeeAsset::Deep);
+
+    // A self-trade opens no position
+    let pnl = compute_pnl(&fills, CostBasisMethod::Fifo, None);
+    assert_eq!(pnl.position, 0.0);
+    assert_eq!(pnl.realized, 0.0);
+
+    let other = ObjectID::from_hex_literal("0x2").unwrap();
+    assert!(fills_from_event(&config, other, &event).unwrap().is_empty());
+}
@@ -0,0 +1,88 @@
+use sui_deepbookv3::{
+    client::{Level2TicksFromMid, NormalizedOrder, Order, OrderDeepPrice, ID},
+    ladder::{build_ladder, spread},
+    utils::types::{ClientOrderId, OrderId, OrderStatus},
+};
+use sui_sdk::types::base_types::SuiAddress;
+use utils::pool;
+
+mod utils;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![1.0, 0.9],
+        bid_quantities: vec![5.0, 7.0],
+        ask_prices: vec![1.2, 1.3],
+        ask_quantities: vec![3.0, 4.0],
+    }
+}
+
+fn own_order(is_bid: bool, price: f64, quantity: u64) -> NormalizedOrder {
+    let pool = pool();
+    let order = Order {
+        balance_manager_id: ID {
+            bytes: SuiAddress::ZERO,
+        },
+        order_id: OrderId::encode(is_bid, pool.price_to_input(price), 1),
+        client_order_id: ClientOrderId(1),
+        quantity,
+        filled_quantity: 0,
+        fee_is_deep: true,
+        order_deep_price: OrderDeepPrice {
+            asset_is_base: true,
+            deep_per_asset: 0,
+        },
+        epoch: 1,
+        status: OrderStatus::Live,
+        expire_timestamp: 0,
+    };
+    NormalizedOrder::from_raw(order, &pool)
+}
+
+#[test]
+fn test_ladder_orders_prices_highest_first() {
+    let ladder = build_ladder(&book(), &[], 0.1);
+
+    let prices: Vec<f64> = ladder.iter().map(|row| row.price).collect();
+    assert_eq!(prices, vec![1.3, 1.2, 1.0, 0.9]);
+    assert_eq!(ladder[1].ask_quantity, 3.0);
+    assert_eq!(ladder[2].bid_quantity, 5.0);
+    assert!(ladder.iter().all(|row| !row.has_own_order()));
+}
+
+#[test]
+fn test_ladder_highlights_own_orders() {
+    let orders = vec![
+        own_order(true, 1.0, 2_000_000),
+        own_order(false, 1.1, 1_000_000),
+    ];
+    let ladder = build_ladder(&book(), &orders, 0.1);
+
+    assert_eq!(ladder.len(), 5);
+    let bid = ladder.iter().find(|row| row.price == 1.0).unwrap();
+    assert_eq!(bid.bid_quantity, 5.0);
+    assert_eq!(bid.own_bid_quantity, 2.0);
+    assert!(bid.has_own_order());
+
+    // An own ask inside the spread gets a row of its own
+    let ask = ladder
+        .iter()
+        .find(|row| row.own_ask_quantity > 0.0)
+        .unwrap();
+    assert_eq!(ask.own_ask_quantity, 1.0);
+    assert_eq!(ask.ask_quantity, 0.0);
+}
+
+#[test]
+fn test_spread() {
+    assert_eq!(spread(&book()), Some((1.0, 1.2, 0.2)));
+    assert_eq!(
+        spread(&Level2TicksFromMid {
+            bid_prices: vec![1.0],
+            bid_quantities: vec![1.0],
+            ask_prices: vec![],
+            ask_quantities: vec![],
+        }),
+        None
+    );
+}
@@ -0,0 +1,107 @@
+use sui_deepbookv3::{
+    client::{PoolBookParams, PoolDeepPrice, PoolTradeParams},
+    market_maker::{MarketMakerConfig, MarketSnapshot, QuoteEngine},
+    requote::TargetOrder,
+    utils::types::OrderType,
+};
+
+fn config(deep_market_price: Option<f64>) -> MarketMakerConfig {
+    MarketMakerConfig {
+        pool_key: "DEEP_SUI".to_string(),
+        manager_key: "MANAGER_1".to_string(),
+        spread: 0.01,
+        order_size: 10.0,
+        target_base_ratio: 0.5,
+        max_skew: 0.01,
+        deep_market_price,
+    }
+}
+
+fn snapshot(base_balance: f64, quote_balance: f64) -> MarketSnapshot {
+    MarketSnapshot {
+        mid_price: 1.0,
+        book: PoolBookParams {
+            tick_size: 0.001,
+            lot_size: 0.1,
+            min_size: 1.0,
+        },
+        trade_params: PoolTradeParams {
+            taker_fee: 0.002,
+            maker_fee: 0.001,
+            stake_required: 0.0,
+        },
+        deep_price: PoolDeepPrice {
+            asset_is_base: true,
+            deep_per_base: Some(2.0),
+            deep_per_quote: None,
+        },
+        base_balance,
+        quote_balance,
+        deep_balance: 1.0,
+    }
+}
+
+fn order(is_bid: bool, price: f64, quantity: f64) -> Option<TargetOrder> {
+    Some(TargetOrder {
+        is_bid,
+        price,
+        quantity,
+    })
+}
+
+#[test]
+fn test_balanced_inventory_quotes_symmetric() {
+    let quotes = QuoteEngine::new(config(None))
+        .quote(&snapshot(100.0, 100.0))
+        .unwrap();
+
+    assert_eq!(quotes.imbalance, 0.0);
+    assert_eq!(quotes.bid, order(true, 0.995, 10.0));
+    assert_eq!(quotes.ask, order(false, 1.005, 10.0));
+    assert!(quotes.pay_with_deep);
+    assert_eq!(quotes.options().order_type, Some(OrderType::PostOnly));
+    assert_eq!(quotes.targets().len(), 2);
+}
+
+#[test]
+fn test_excess_base_skews_prices_down() {
+    let quotes = QuoteEngine::new(config(None))
+        .quote(&snapshot(300.0, 100.0))
+        .unwrap();
+
+    assert_eq!(quotes.imbalance, 0.5);
+    assert_eq!(quotes.bid, order(true, 0.99, 10.0));
+    assert_eq!(quotes.ask, order(false, 1.0, 10.0));
+}
+
+#[test]
+fn test_side_dropped_without_balance() {
+    let quotes = QuoteEngine::new(config(None))
+        .quote(&snapshot(100.0, 0.5))
+        .unwrap();
+
+    assert_eq!(quotes.bid, None);
+    assert!(quotes.imbalance > 0.9);
+    assert!(quotes.ask.is_some());
+}
+
+#[test]
+fn test_fee_asset_follows_deep_market_price() {
+    // 0.04 DEEP of fees against 0.025 quote of input fees
+    let expensive = QuoteEngine::new(config(Some(1.0)))
+        .quote(&snapshot(100.0, 100.0))
+        .unwrap();
+    assert!(!expensive.pay_with_deep);
+
+    let cheap = QuoteEngine::new(config(Some(0.5)))
+        .quote(&snapshot(100.0, 100.0))
+        .unwrap();
+    assert!(cheap.pay_with_deep);
+
+    let mut short_of_deep = snapshot(100.0, 100.0);
+    short_of_deep.deep_balance = 0.01;
+    let quotes = QuoteEngine::new(config(None))
+        .quote(&short_of_deep)
+        .unwrap();
+    assert!(!quotes.pay_with_deep);
+}
@@ -0,0 +1,90 @@
+use sui_deepbookv3::{
+    client::{PoolTradeParams, VaultBalances},
+    metrics::{render, ManagerSample, PoolSample, Sample},
+};
+
+fn pool(best_bid: Option<(f64, f64)>, best_ask: Option<(f64, f64)>) -> PoolSample {
+    PoolSample {
+        pool_key: "SUI_USDC".to_string(),
+        mid_price: Some(2.0),
+        best_bid,
+        best_ask,
+        vault: VaultBalances {
+            base: 100.0,
+            quote: 250.0,
+            deep: 3.0,
+        },
+        trade_params: PoolTradeParams {
+            taker_fee: 0.001,
+            maker_fee: 0.0005,
+            stake_required: 100.0,
+        },
+    }
+}
+
+#[test]
+fn test_spread() {
+    assert_eq!(pool(Some((1.9, 5.0)), Some((2.1, 4.0))).spread(), Some(0.2));
+    assert_eq!(pool(Some((1.9, 5.0)), None).spread(), None);
+}
+
+#[test]
+fn test_render_pool_and_manager_series() {
+    let sample = Sample {
+        timestamp_ms: 1_500,
+        pools: vec![pool(Some((1.9, 5.0)), Some((2.1, 4.0)))],
+        managers: vec![ManagerSample {
+            manager_key: "MANAGER_1".to_string(),
+            balances: vec![("SUI".to_string(), 12.5)],
+            open_orders: vec![("SUI_USDC".to_string(), 3)],
+        }],
+        failed_pools: vec![("DEEP_SUI".to_string(), "timeout".to_string())],
+        failed_managers: Vec::new(),
+    };
+    let text = render(&sample);
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert!(lines.contains(&"# TYPE deepbook_pool_spread gauge"));
+    assert!(lines.contains(&"deepbook_last_sample_timestamp_seconds 1.5"));
+    assert!(lines.contains(&"deepbook_pool_sample_ok{pool=\"SUI_USDC\"} 1"));
+    assert!(lines.contains(&"deepbook_pool_sample_ok{pool=\"DEEP_SUI\"} 0"));
+    assert!(lines.contains(&"deepbook_pool_spread{pool=\"SUI_USDC\"} 0.2"));
+    assert!(lines.contains(&"deepbook_pool_spread_ratio{pool=\"SUI_USDC\"} 0.1"));
+    assert!(lines.contains(&"deepbook_pool_top_depth{pool=\"SUI_USDC\",side=\"ask\"} 4"));
+    assert!(lines.contains(&"deepbook_pool_vault_balance{pool=\"SUI_USDC\",asset=\"quote\"} 250"));
+    assert!(lines.contains(&"deepbook_manager_balance{manager=\"MANAGER_1\",coin=\"SUI\"} 12.5"));
+    assert!(
+        lines.contains(&"deepbook_manager_open_orders{manager=\"MANAGER_1\",pool=\"SUI_USDC\"} 3")
+    );
+}
+
+#[test]
+fn test_render_skips_spread_of_one_sided_book() {
+    let sample = Sample {
+        pools: vec![pool(Some((1.9, 5.0)), None)],
+        ..Default::default()
+    };
+    let text = render(&sample);
+
+    assert!(!text.contains("deepbook_pool_spread{"));
+    assert!(!text.contains("deepbook_pool_best_ask{"));
+    assert!(text.contains("deepbook_pool_top_depth{pool=\"SUI_USDC\",side=\"ask\"} 0"));
+}
+
+#[test]
+fn test_render_pool_without_mid_price() {
+    let sample = Sample {
+        pools: vec![PoolSample {
+            mid_price: None,
+            ..pool(None, Some((2.1, 4.0)))
+        }],
+        ..Default::default()
+    };
+    let text = render(&sample);
+
+    assert!(!text.contains("deepbook_pool_mid_price{"));
+    assert!(!text.contains("deepbook_pool_spread_ratio{"));
+    assert!(text.contains("deepbook_pool_best_ask{pool=\"SUI_USDC\"} 2.1"));
+    assert!(text.contains("deepbook_pool_vault_balance{pool=\"SUI_USDC\",asset=\"quote\"} 250"));
+    assert!(text.contains("deepbook_pool_sample_ok{pool=\"SUI_USDC\"} 1"));
+}
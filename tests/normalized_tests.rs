@@ -0,0 +1,70 @@
+use sui_deepbookv3::{
+    client::{Account, NormalizedOrder, Order, OrderDeepPrice, RawAccount, RawBalances, ID},
+    utils::types::{ClientOrderId, OrderId, OrderStatus},
+};
+use sui_sdk::types::base_types::SuiAddress;
+use utils::pool;
+
+mod utils;
+
+#[test]
+fn test_normalized_order_scales_and_keeps_raw() {
+    let pool = pool();
+    let order = Order {
+        balance_manager_id: ID {
+            bytes: SuiAddress::ZERO,
+        },
+        order_id: OrderId::encode(false, pool.price_to_input(2.5), 1),
+        client_order_id: ClientOrderId(7),
+        quantity: 10_000_000,
+        filled_quantity: 4_000_000,
+        fee_is_deep: true,
+        order_deep_price: OrderDeepPrice {
+            asset_is_base: true,
+            deep_per_asset: 3_000_000,
+        },
+        epoch: 1,
+        status: OrderStatus::PartiallyFilled,
+        expire_timestamp: 0,
+    };
+    let normalized = NormalizedOrder::from_raw(order, &pool);
+
+    assert!(!normalized.is_bid);
+    assert_eq!(normalized.quantity, 10.0);
+    assert_eq!(normalized.filled_quantity, 4.0);
+    assert_eq!(normalized.remaining_quantity, 6.0);
+    assert_eq!(normalized.normalized_price, 2.5);
+    assert_eq!(normalized.notional, 15.0);
+    assert_eq!(normalized.order_deep_price.deep_per_asset, 3.0);
+    assert_eq!(normalized.raw.quantity, 10_000_000);
+}
+
+#[test]
+fn test_account_scales_volumes_stake_and_balances() {
+    let raw = RawAccount {
+        epoch: 3,
+        open_orders: Vec::new(),
+        taker_volume: 5_000_000,
+        maker_volume: 2_500_000,
+        active_stake: 100_000_000,
+        inactive_stake: 0,
+        created_proposal: false,
+        voted_proposal: None,
+        unclaimed_rebates: RawBalances {
+            base: 1_000_000,
+            quote: 2_000_000_000,
+            deep: 500_000,
+        },
+        settled_balances: RawBalances::default(),
+        owed_balances: RawBalances::default(),
+    };
+    let account = Account::from_raw(raw, &pool());
+
+    assert_eq!(account.taker_volume, 5.0);
+    assert_eq!(account.maker_volume, 2.5);
+    assert_eq!(account.active_stake, 100.0);
+    assert_eq!(account.unclaimed_rebates.base, 1.0);
+    assert_eq!(account.unclaimed_rebates.quote, 2.0);
+    assert_eq!(account.unclaimed_rebates.deep, 0.5);
+    assert_eq!(account.raw.unclaimed_rebates.deep, 500_000);
+}
@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use sui_deepbookv3::{
+    client::{Order, OrderDeepPrice, ID},
+    oms::{ManagedOrder, OmsOrderStatus, OrderKey, OrderTable},
+    utils::{
+        config::{DeepBookConfig, Environment},
+        types::{BalanceManager, ClientOrderId, OrderId, OrderStatus},
+    },
+};
+use sui_sdk::{
+    rpc_types::SuiEvent,
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        digests::TransactionDigest,
+    },
+};
+
+const MANAGER: &str = "0x1";
+
+fn config() -> DeepBookConfig {
+    let mut managers = HashMap::new();
+    managers.insert(
+        "MANAGER_1",
+        BalanceManager {
+            address: MANAGER.to_string(),
+            trade_cap: None,
+        },
+    );
+    DeepBookConfig::new(
+        Environment::Testnet,
+        SuiAddress::ZERO,
+        None,
+        Some(managers),
+        None,
+        None,
+    )
+}
+
+fn manager_id() -> ObjectID {
+    ObjectID::from_hex_literal(MANAGER).unwrap()
+}
+
+fn pool_id(config: &DeepBookConfig, pool_key: &str) -> String {
+    config.get_pool(pool_key).unwrap().address.clone()
+}
+
+fn event(package: &str, module: &str, name: &str, tx: u8, parsed_json: Value) -> SuiEvent {
+    serde_json::from_value(json!({
+        "id": { "txDigest": TransactionDigest::new([tx; 32]).to_string(), "eventSeq": "0" },
+        "packageId": package,
+        "transactionModule": "pool",
+        "sender": SuiAddress::ZERO.to_string(),
+        "type": format!("{}::{}::{}", package, module, name),
+        "parsedJson": parsed_json,
+        "bcsEncoding": "base64",
+        "bcs": "",
+        "timestampMs": "0",
+    }))
+    .unwrap()
+}
+
+fn placed(
+    config: &DeepBookConfig,
+    pool_key: &str,
+    client_order_id: u64,
+    order_id: u128,
+) -> SuiEvent {
+    event(
+        config.deepbook_package_id(),
+        "order_info",
+        "OrderPlaced",
+        1,
+        json!({
+            "balance_manager_id": MANAGER,
+            "pool_id": pool_id(config, pool_key),
+            "order_id": order_id.to_string(),
+            "client_order_id": client_order_id.to_string(),
+        }),
+    )
+}
+
+fn filled(config: &DeepBookConfig, tx: u8, client_order_id: u64, order_id: u128) -> SuiEvent {
+    partial_fill(config, tx, client_order_id, order_id, 4_000_000)
+}
+
+fn partial_fill(
+    config: &DeepBookConfig,
+    tx: u8,
+    client_order_id: u64,
+    order_id: u128,
+    base_quantity: u64,
+) -> SuiEvent {
+    event(
+        config.deepbook_package_id(),
+        "order_info",
+        "OrderFilled",
+        tx,
+        json!({
+            "pool_id": pool_id(config, "DEEP_SUI"),
+            "taker_is_bid": true,
+            "base_quantity": base_quantity.to_string(),
+            "maker_balance_manager_id": MANAGER,
+            "maker_client_order_id": client_order_id.to_string(),
+            "maker_order_id": order_id.to_string(),
+            "taker_balance_manager_id": "0x2",
+            "taker_client_order_id": "0",
+            "taker_order_id": "1",
+        }),
+    )
+}
+
+fn canceled(config: &DeepBookConfig, tx: u8, client_order_id: u64, order_id: u128) -> SuiEvent {
+    event(
+        config.deepbook_package_id(),
+        "order",
+        "OrderCanceled",
+        tx,
+        json!({
+            "balance_manager_id": MANAGER,
+            "pool_id": pool_id(config, "DEEP_SUI"),
+            "order_id": order_id.to_string(),
+            "client_order_id": client_order_id.to_string(),
+            "base_asset_quantity_canceled": "10000000",
+        }),
+    )
+}
+
+fn modified(
+    config: &DeepBookConfig,
+    tx: u8,
+    client_order_id: u64,
+    order_id: u128,
+    new_quantity: u64,
+) -> SuiEvent {
+    event(
+        config.deepbook_package_id(),
+        "order",
+        "OrderModified",
+        tx,
+        json!({
+            "balance_manager_id": MANAGER,
+            "pool_id": pool_id(config, "DEEP_SUI"),
+            "order_id": order_id.to_string(),
+            "client_order_id": client_order_id.to_string(),
+            "new_quantity": new_quantity.to_string(),
+        }),
+    )
+}
+
+fn pending(pool_key: &str, client_order_id: u64) -> ManagedOrder {
+    ManagedOrder {
+        client_order_id: ClientOrderId(client_order_id),
+        pool_key: pool_key.to_string(),
+        order_id: None,
+        is_bid: false,
+        price: 1.0,
+        quantity: 10.0,
+        filled_quantity: 0.0,
+        status: OmsOrderStatus::Pending,
+    }
+}
+
+fn chain_order(client_order_id: u64, order_id: OrderId) -> Order {
+    Order {
+        balance_manager_id: ID {
+            bytes: SuiAddress::from(manager_id()),
+        },
+        order_id,
+        client_order_id: ClientOrderId(client_order_id),
+        quantity: 10_000_000,
+        filled_quantity: 0,
+        fee_is_deep: true,
+        order_deep_price: OrderDeepPrice {
+            asset_is_base: true,
+            deep_per_asset: 0,
+        },
+        epoch: 1,
+        status: OrderStatus::Live,
+        expire_timestamp: 0,
+    }
+}
+
+#[test]
+fn test_events_update_tracked_order() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 7)).unwrap();
+    table.track(pending("SUI_DBUSDC", 7)).unwrap();
+
+    let order_id = OrderId::encode(false, 1_000_000, 1);
+    let events = vec![
+        placed(&config, "DEEP_SUI", 7, order_id.value()),
+        filled(&config, 2, 7, order_id.value()),
+    ];
+    table.apply_events(&config, manager_id(), &events).unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.order_id, Some(order_id));
+    assert_eq!(order.filled_quantity, 4.0);
+    assert_eq!(order.status, OmsOrderStatus::PartiallyFilled);
+
+    // The same client order ID in another pool is a different order
+    let other = table
+        .get(&OrderKey::new("SUI_DBUSDC", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(other.status, OmsOrderStatus::Pending);
+    assert_eq!(other.order_id, None);
+}
+
+#[test]
+fn test_events_of_other_packages_are_ignored() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 7)).unwrap();
+
+    // Same event name from another package, without DeepBook's fields
+    let foreign = event(
+        "0x2",
+        "order_info",
+        "OrderFilled",
+        1,
+        json!({ "amount": "1" }),
+    );
+    table
+        .apply_events(&config, manager_id(), &[foreign])
+        .unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.status, OmsOrderStatus::Pending);
+}
+
+#[test]
+fn test_reconcile_does_not_overwrite_tracked_orders() {
+    let config = config();
+    let pool = config.pool_ref("DEEP_SUI").unwrap();
+    let tracked_id = OrderId::encode(true, 1_000_000, u64::MAX - 1);
+    let mut table = OrderTable::from_orders(vec![
+        ManagedOrder {
+            order_id: Some(tracked_id),
+            status: OmsOrderStatus::Live,
+            ..pending("SUI_DBUSDC", 0)
+        },
+        ManagedOrder {
+            order_id: Some(OrderId::encode(false, 2_000_000, 9)),
+            status: OmsOrderStatus::Live,
+            ..pending("DEEP_SUI", 5)
+        },
+    ]);
+
+    // Two untracked orders sharing the default client order ID
+    let first = OrderId::encode(false, 1_000_000, 1);
+    let second = OrderId::encode(false, 1_000_000, 2);
+    let report = table.reconcile_pool(
+        "DEEP_SUI",
+        &pool,
+        &[chain_order(0, first), chain_order(0, second)],
+    );
+
+    assert_eq!(report.unknown_on_chain.len(), 2);
+    assert_eq!(
+        table
+            .get(&OrderKey::new("DEEP_SUI", ClientOrderId(0)))
+            .unwrap()
+            .order_id,
+        Some(first)
+    );
+    assert_eq!(
+        table
+            .get(&OrderKey::new("SUI_DBUSDC", ClientOrderId(0)))
+            .unwrap()
+            .order_id,
+        Some(tracked_id)
+    );
+
+    assert_eq!(report.missing_on_chain.len(), 1);
+    assert_eq!(report.missing_on_chain[0].client_order_id, ClientOrderId(5));
+    assert_eq!(
+        table
+            .get(&OrderKey::new("DEEP_SUI", ClientOrderId(5)))
+            .unwrap()
+            .status,
+        OmsOrderStatus::Closed
+    );
+}
+
+#[test]
+fn test_reconcile_updates_matching_order() {
+    let config = config();
+    let pool = config.pool_ref("DEEP_SUI").unwrap();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 3)).unwrap();
+
+    let order_id = OrderId::encode(false, 1_000_000, 1);
+    let report = table.reconcile_pool("DEEP_SUI", &pool, &[chain_order(3, order_id)]);
+
+    assert_eq!(
+        report.updated,
+        vec![OrderKey::new("DEEP_SUI", ClientOrderId(3))]
+    );
+    assert!(report.unknown_on_chain.is_empty());
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(3)))
+        .unwrap();
+    assert_eq!(order.order_id, Some(order_id));
+    assert_eq!(order.status, OmsOrderStatus::Live);
+}
+
+#[test]
+fn test_fills_are_applied_once() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 7)).unwrap();
+
+    let order_id = OrderId::encode(false, 1_000_000, 1).value();
+    let events = vec![
+        placed(&config, "DEEP_SUI", 7, order_id),
+        filled(&config, 2, 7, order_id),
+    ];
+    table.apply_events(&config, manager_id(), &events).unwrap();
+    table.apply_events(&config, manager_id(), &events).unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.filled_quantity, 4.0);
+    assert_eq!(order.status, OmsOrderStatus::PartiallyFilled);
+
+    // A later fill of the same order still counts
+    table
+        .apply_events(&config, manager_id(), &[filled(&config, 3, 7, order_id)])
+        .unwrap();
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.filled_quantity, 8.0);
+}
+
+#[test]
+fn test_small_fills_complete_order() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table
+        .track(ManagedOrder {
+            quantity: 1.0,
+            ..pending("DEEP_SUI", 7)
+        })
+        .unwrap();
+
+    // Ten fills of 0.1 sum to 0.9999999999999999 in f64
+    let order_id = OrderId::encode(false, 1_000_000, 1).value();
+    let mut events = vec![placed(&config, "DEEP_SUI", 7, order_id)];
+    events.extend((2..12).map(|tx| partial_fill(&config, tx, 7, order_id, 100_000)));
+    table.apply_events(&config, manager_id(), &events).unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.filled_quantity, 1.0);
+    assert_eq!(order.status, OmsOrderStatus::Filled);
+}
+
+#[test]
+fn test_cancel_event_from_order_module() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 7)).unwrap();
+
+    let order_id = OrderId::encode(false, 1_000_000, 1).value();
+    let events = vec![
+        placed(&config, "DEEP_SUI", 7, order_id),
+        canceled(&config, 2, 7, order_id),
+    ];
+    table.apply_events(&config, manager_id(), &events).unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.status, OmsOrderStatus::Canceled);
+}
+
+#[test]
+fn test_modify_event_from_order_module() {
+    let config = config();
+    let mut table = OrderTable::new();
+    table.track(pending("DEEP_SUI", 7)).unwrap();
+
+    let order_id = OrderId::encode(false, 1_000_000, 1).value();
+    let events = vec![
+        placed(&config, "DEEP_SUI", 7, order_id),
+        modified(&config, 2, 7, order_id, 6_000_000),
+    ];
+    table.apply_events(&config, manager_id(), &events).unwrap();
+
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.quantity, 6.0);
+    assert_eq!(order.status, OmsOrderStatus::Live);
+
+    // The same names under the wrong module are not DeepBook's events
+    let misrouted = event(
+        config.deepbook_package_id(),
+        "order_info",
+        "OrderCanceled",
+        3,
+        json!({ "pool_id": pool_id(&config, "DEEP_SUI") }),
+    );
+    table
+        .apply_events(&config, manager_id(), &[misrouted])
+        .unwrap();
+    let order = table
+        .get(&OrderKey::new("DEEP_SUI", ClientOrderId(7)))
+        .unwrap();
+    assert_eq!(order.status, OmsOrderStatus::Live);
+}
@@ -0,0 +1,45 @@
+use sui_deepbookv3::utils::types::OrderId;
+use utils::pool;
+
+mod utils;
+
+#[test]
+fn test_encode_round_trips() {
+    let bid = OrderId::encode(true, 1_500_000, u64::MAX - 7);
+    assert!(bid.is_bid());
+    assert_eq!(bid.price(), 1_500_000);
+    assert_eq!(bid.sequence(), u64::MAX - 7);
+
+    let ask = OrderId::encode(false, 2_000_000, 3);
+    assert!(!ask.is_bid());
+    assert_eq!(ask.price(), 2_000_000);
+    assert_eq!(ask.sequence(), 3);
+    assert_eq!(ask.value() >> 127, 1);
+    assert_eq!(ask.to_string().parse::<OrderId>().unwrap(), ask);
+}
+
+#[test]
+fn test_orders_sort_in_book_priority() {
+    let older_bid = OrderId::encode(true, 1_000_000, u64::MAX - 1);
+    let newer_bid = OrderId::encode(true, 1_000_000, u64::MAX - 2);
+    let better_bid = OrderId::encode(true, 1_100_000, u64::MAX - 3);
+    let older_ask = OrderId::encode(false, 1_200_000, 1);
+    let newer_ask = OrderId::encode(false, 1_200_000, 2);
+    let better_ask = OrderId::encode(false, 1_150_000, 3);
+
+    let mut ids = vec![
+        newer_ask, older_bid, better_ask, newer_bid, older_ask, better_bid,
+    ];
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![better_bid, older_bid, newer_bid, better_ask, older_ask, newer_ask]
+    );
+}
+
+#[test]
+fn test_normalized_price_uses_pool_scalars() {
+    let pool = pool();
+    let id = OrderId::encode(true, pool.price_to_input(2.5), 0);
+    assert_eq!(id.normalized_price(&pool), 2.5);
+}
@@ -0,0 +1,41 @@
+use sui_deepbookv3::utils::types::{OrderStatus, OrderType, SelfMatchingOptions};
+
+#[test]
+fn test_enums_round_trip_through_u8() {
+    for value in 0..4u8 {
+        assert_eq!(u8::from(OrderType::try_from(value).unwrap()), value);
+    }
+    for value in 0..3u8 {
+        assert_eq!(
+            u8::from(SelfMatchingOptions::try_from(value).unwrap()),
+            value
+        );
+    }
+    for value in 0..5u8 {
+        assert_eq!(u8::from(OrderStatus::try_from(value).unwrap()), value);
+    }
+
+    assert!(OrderType::try_from(4).is_err());
+    assert!(SelfMatchingOptions::try_from(3).is_err());
+    assert!(OrderStatus::try_from(5).is_err());
+}
+
+#[test]
+fn test_order_status_decodes_from_bcs_u8() {
+    let status: OrderStatus = bcs::from_bytes(&[1]).unwrap();
+    assert_eq!(status, OrderStatus::PartiallyFilled);
+    assert!(status.is_open());
+    assert_eq!(bcs::to_bytes(&OrderStatus::Expired).unwrap(), vec![4]);
+    assert!(bcs::from_bytes::<OrderStatus>(&[9]).is_err());
+}
+
+#[test]
+fn test_order_options_serialize_as_u8() {
+    assert_eq!(bcs::to_bytes(&OrderType::PostOnly).unwrap(), vec![3]);
+    assert_eq!(
+        bcs::from_bytes::<SelfMatchingOptions>(&[2]).unwrap(),
+        SelfMatchingOptions::CancelMaker
+    );
+    assert_eq!(serde_json::to_string(&OrderType::FillOrKill).unwrap(), "2");
+    assert!(serde_json::from_str::<SelfMatchingOptions>("3").is_err());
+}
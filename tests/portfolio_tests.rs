@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use sui_deepbookv3::portfolio::{CoinHoldings, Portfolio};
+
+fn portfolio() -> Portfolio {
+    Portfolio {
+        quote_coin: "SUI".to_string(),
+        holdings: BTreeMap::from([
+            (
+                "DEEP".to_string(),
+                CoinHoldings {
+                    wallet: 10.0,
+                    manager: 5.0,
+                    locked: 2.0,
+                    settled: 1.0,
+                    owed: 3.0,
+                    unclaimed_rebates: 0.5,
+                    price: Some(0.5),
+                },
+            ),
+            (
+                "SUI".to_string(),
+                CoinHoldings {
+                    wallet: 4.0,
+                    price: Some(1.0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "WETH".to_string(),
+                CoinHoldings {
+                    manager: 1.0,
+                    ..Default::default()
+                },
+            ),
+        ]),
+    }
+}
+
+#[test]
+fn test_holdings_net_owed_amounts() {
+    let portfolio = portfolio();
+    let deep = &portfolio.holdings["DEEP"];
+    assert_eq!(deep.total(), 15.5);
+    assert_eq!(deep.value(), Some(7.75));
+    assert_eq!(portfolio.holdings["WETH"].value(), None);
+}
+
+#[test]
+fn test_total_value_skips_unpriced_coins() {
+    let portfolio = portfolio();
+    assert_eq!(portfolio.total_value(), 11.75);
+    assert_eq!(portfolio.unpriced(), vec!["WETH"]);
+}
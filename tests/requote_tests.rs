@@ -0,0 +1,167 @@
+use sui_deepbookv3::{
+    client::PoolBookParams,
+    requote::{diff_orders, LiveOrder, OrderModification, TargetOrder},
+    utils::types::OrderId,
+};
+use utils::pool;
+
+mod utils;
+
+// Lower `age` is older. Bid sequences count down from u64::MAX and ask sequences count up.
+fn id(age: u64, is_bid: bool, price: f64) -> OrderId {
+    let sequence = if is_bid { u64::MAX - age } else { age };
+    OrderId::encode(is_bid, pool().price_to_input(price), sequence)
+}
+
+fn live(age: u64, is_bid: bool, price: f64, quantity: f64, filled: f64) -> LiveOrder {
+    LiveOrder {
+        order_id: id(age, is_bid, price),
+        is_bid,
+        price,
+        quantity,
+        filled_quantity: filled,
+        expire_timestamp: u64::MAX,
+    }
+}
+
+fn book() -> PoolBookParams {
+    PoolBookParams {
+        tick_size: 0.1,
+        lot_size: 0.5,
+        min_size: 1.0,
+    }
+}
+
+fn target(is_bid: bool, price: f64, quantity: f64) -> TargetOrder {
+    TargetOrder {
+        is_bid,
+        price,
+        quantity,
+    }
+}
+
+#[test]
+fn test_matching_book_is_empty_diff() {
+    let current = [
+        live(1, true, 0.9, 10.0, 0.0),
+        live(2, false, 1.1, 10.0, 4.0),
+    ];
+    let diff = diff_orders(
+        &pool(),
+        &book(),
+        &current,
+        &[target(true, 0.9, 10.0), target(false, 1.1, 6.0)],
+        0,
+    );
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.unchanged, vec![id(2, false, 1.1), id(1, true, 0.9)]);
+}
+
+#[test]
+fn test_diff_cancels_reduces_and_places() {
+    let current = [
+        live(1, true, 0.9, 10.0, 0.0),
+        live(2, true, 0.9, 10.0, 2.0),
+        live(3, true, 0.8, 5.0, 0.0),
+        live(4, false, 1.1, 5.0, 0.0),
+    ];
+    let diff = diff_orders(
+        &pool(),
+        &book(),
+        &current,
+        &[
+            target(true, 0.9, 14.0),
+            target(false, 1.1, 7.0),
+            target(false, 1.2, 3.0),
+        ],
+        0,
+    );
+
+    assert_eq!(diff.unchanged, vec![id(4, false, 1.1), id(1, true, 0.9)]);
+    assert_eq!(diff.cancels, vec![id(3, true, 0.8)]);
+    // Keeps 2 filled, leaves 4 resting
+    assert_eq!(
+        diff.modifies,
+        vec![OrderModification {
+            order_id: id(2, true, 0.9),
+            new_quantity: 6.0,
+        }]
+    );
+    assert_eq!(
+        diff.placements,
+        vec![target(false, 1.1, 2.0), target(false, 1.2, 3.0)]
+    );
+    assert_eq!(diff.call_count(), 4);
+}
+
+#[test]
+fn test_diff_skips_top_up_below_min_size() {
+    let current = [live(1, true, 0.9, 10.0, 0.0)];
+    let diff = diff_orders(&pool(), &book(), &current, &[target(true, 0.9, 10.5)], 0);
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.unchanged, vec![id(1, true, 0.9)]);
+}
+
+#[test]
+fn test_diff_replaces_expired_orders() {
+    let expired = LiveOrder {
+        expire_timestamp: 100,
+        ..live(1, true, 0.9, 10.0, 0.0)
+    };
+    let current = [expired, live(2, true, 0.9, 4.0, 0.0)];
+    let diff = diff_orders(&pool(), &book(), &current, &[target(true, 0.9, 10.0)], 101);
+
+    assert_eq!(diff.cancels, vec![id(1, true, 0.9)]);
+    assert_eq!(diff.unchanged, vec![id(2, true, 0.9)]);
+    assert_eq!(diff.placements, vec![target(true, 0.9, 6.0)]);
+
+    // Not yet expired at its expiry time
+    let diff = diff_orders(&pool(), &book(), &current, &[target(true, 0.9, 14.0)], 100);
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_diff_rounds_to_lots() {
+    let current = [
+        live(1, true, 0.9, 10.0, 0.0),
+        live(2, false, 1.1, 10.0, 1.5),
+    ];
+    let diff = diff_orders(
+        &pool(),
+        &book(),
+        &current,
+        &[
+            target(true, 0.9, 13.7),
+            target(false, 1.1, 4.3),
+            target(false, 1.2, 0.9),
+        ],
+        0,
+    );
+
+    assert_eq!(diff.unchanged, vec![id(1, true, 0.9)]);
+    assert!(diff.cancels.is_empty());
+    // Keeps 1.5 filled, leaves 4.0 resting
+    assert_eq!(
+        diff.modifies,
+        vec![OrderModification {
+            order_id: id(2, false, 1.1),
+            new_quantity: 5.5,
+        }]
+    );
+    // 3.7 short rounds down to 3.5; 0.9 rounds down to 0.5, below the minimum size
+    assert_eq!(diff.placements, vec![target(true, 0.9, 3.5)]);
+}
+
+#[test]
+fn test_diff_cancels_reduction_below_min_size() {
+    let current = [live(1, true, 0.9, 10.0, 2.0), live(2, true, 0.9, 5.0, 0.0)];
+    let diff = diff_orders(&pool(), &book(), &current, &[target(true, 0.9, 8.7)], 0);
+
+    assert_eq!(diff.unchanged, vec![id(1, true, 0.9)]);
+    // 0.7 wanted beyond the first order rounds down to 0.5, which cannot rest alone
+    assert_eq!(diff.cancels, vec![id(2, true, 0.9)]);
+    assert!(diff.modifies.is_empty());
+    assert!(diff.placements.is_empty());
+}
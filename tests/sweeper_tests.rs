@@ -0,0 +1,110 @@
+use sui_deepbookv3::{
+    client::Balances,
+    sweeper::{PoolSweep, SweepPlan},
+    utils::config::{DeepBookConfig, Environment},
+};
+use sui_sdk::types::base_types::SuiAddress;
+
+fn config() -> DeepBookConfig {
+    DeepBookConfig::new(
+        Environment::Testnet,
+        SuiAddress::ZERO,
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+fn balances(base: f64, quote: f64, deep: f64) -> Balances {
+    Balances { base, quote, deep }
+}
+
+fn sweep(pool_key: &str, unclaimed_rebates: Balances, settled_balances: Balances) -> PoolSweep {
+    PoolSweep {
+        pool_key: pool_key.to_string(),
+        unclaimed_rebates,
+        settled_balances,
+        owed_balances: balances(0.0, 0.0, 0.0),
+    }
+}
+
+#[test]
+fn test_plan_sums_proceeds_per_coin() {
+    let plan = SweepPlan::new(
+        &config(),
+        "MANAGER_1",
+        vec![
+            sweep("DEEP_SUI", balances(0.0, 0.0, 1.5), balances(2.0, 3.0, 0.0)),
+            sweep(
+                "SUI_DBUSDC",
+                balances(0.0, 1.0, 0.5),
+                balances(4.0, 0.0, 0.0),
+            ),
+            sweep(
+                "DEEP_DBUSDC",
+                balances(0.0, 0.0, 0.0),
+                balances(0.0, 0.0, 0.0),
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(plan.manager_key, "MANAGER_1");
+    // Pools with nothing to claim or settle are dropped
+    let pool_keys: Vec<&str> = plan
+        .pools
+        .iter()
+        .map(|sweep| sweep.pool_key.as_str())
+        .collect();
+    assert_eq!(pool_keys, vec!["DEEP_SUI", "SUI_DBUSDC"]);
+    assert_eq!(
+        plan.proceeds.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("DBUSDC".to_string(), 1.0),
+            ("DEEP".to_string(), 4.0),
+            ("SUI".to_string(), 7.0),
+        ]
+    );
+}
+
+#[test]
+fn test_empty_plan() {
+    let plan = SweepPlan::new(&config(), "MANAGER_1", Vec::new()).unwrap();
+    assert!(plan.is_empty());
+    assert!(plan.proceeds.is_empty());
+
+    let unknown = vec![sweep(
+        "MISSING",
+        balances(1.0, 0.0, 0.0),
+        balances(0.0, 0.0, 0.0),
+    )];
+    assert!(SweepPlan::new(&config(), "MANAGER_1", unknown).is_err());
+}
+
+#[test]
+fn test_plan_nets_owed_balances() {
+    let plan = SweepPlan::new(
+        &config(),
+        "MANAGER_1",
+        vec![
+            PoolSweep {
+                owed_balances: balances(0.5, 4.0, 0.25),
+                ..sweep("DEEP_SUI", balances(0.0, 0.0, 1.0), balances(2.0, 3.0, 0.0))
+            },
+            sweep(
+                "SUI_DBUSDC",
+                balances(0.0, 0.0, 0.0),
+                balances(1.0, 0.0, 0.0),
+            ),
+        ],
+    )
+    .unwrap();
+
+    // DEEP nets 1.0 + 2.0 - 0.5 - 0.25. DEEP_SUI owes 1.0 SUI net, which cancels the
+    // SUI settled in SUI_DBUSDC, so no SUI is withdrawn
+    assert_eq!(
+        plan.proceeds.into_iter().collect::<Vec<_>>(),
+        vec![("DEEP".to_string(), 2.25)]
+    );
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use sui_deepbookv3::utils::{
+    config::MAX_TIMESTAMP,
+    types::{ClientOrderId, PlaceLimitOrderParams, TimeInForce},
+};
+
+#[test]
+fn test_good_til_duration() {
+    let time_in_force = TimeInForce::good_til_duration(1_000, Duration::from_secs(60));
+    assert_eq!(time_in_force, TimeInForce::GoodTilTime(61_000));
+
+    let forever = TimeInForce::good_til_duration(1_000, Duration::MAX);
+    assert_eq!(forever.expiration(), MAX_TIMESTAMP);
+    assert_eq!(TimeInForce::GoodTilCancelled.expiration(), MAX_TIMESTAMP);
+}
+
+#[test]
+fn test_good_til_datetime() {
+    let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        TimeInForce::good_til_datetime(&time).unwrap(),
+        TimeInForce::GoodTilTime(1_735_689_600_000)
+    );
+
+    let before_epoch = Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap();
+    assert!(TimeInForce::good_til_datetime(&before_epoch).is_err());
+}
+
+#[test]
+fn test_with_time_in_force_sets_expiration() {
+    let params = PlaceLimitOrderParams {
+        pool_key: "DEEP_SUI".to_string(),
+        balance_manager_key: "MANAGER_1".to_string(),
+        client_order_id: ClientOrderId(1),
+        price: 1.0,
+        quantity: 10.0,
+        is_bid: true,
+        expiration: None,
+        order_type: None,
+        self_matching_option: None,
+        pay_with_deep: None,
+    }
+    .with_time_in_force(TimeInForce::GoodTilTime(5_000));
+
+    assert_eq!(params.expiration, Some(5_000));
+}
@@ -1,7 +1,11 @@
+// Helpers shared by the integration tests, each of which uses only some of them
+#![allow(dead_code)]
+
 use shared_crypto::intent::Intent;
 use sui_config::{
     sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG, SUI_KEYSTORE_FILENAME,
 };
+use sui_deepbookv3::utils::types::{Pool, PoolRef};
 use sui_deepbookv3::DataReader;
 use sui_sdk::rpc_types::SuiTypeTag;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
@@ -9,9 +13,11 @@ use sui_sdk::{
     rpc_types::SuiTransactionBlockResponseOptions,
     sui_client_config::{SuiClientConfig, SuiEnv},
     types::{
+        base_types::ObjectID,
         programmable_transaction_builder::ProgrammableTransactionBuilder,
         quorum_driver_types::ExecuteTransactionRequestType,
         transaction::{Transaction, TransactionData},
+        TypeTag,
     },
     wallet_context::WalletContext,
     SuiClient, SuiClientBuilder,
@@ -57,7 +63,6 @@ pub fn retrieve_wallet() -> anyhow::Result<WalletContext> {
     Ok(wallet)
 }
 
-#[allow(dead_code)]
 pub async fn execute_transaction(ptb: ProgrammableTransactionBuilder) {
     let sui_client = SuiClientBuilder::default().build_testnet().await.unwrap();
     println!("Sui testnet version: {}", sui_client.api_version());
@@ -123,3 +128,23 @@ pub async fn dry_run_transaction(
 
     sui_client.dev_inspect_transaction(sender, ptb).await
 }
+
+/// A pool scaled like DEEP/SUI, with a 6 decimal base and a 9 decimal quote coin
+pub fn pool() -> PoolRef {
+    PoolRef::new(
+        ObjectID::ZERO,
+        TypeTag::U64,
+        TypeTag::U64,
+        1_000_000,
+        1_000_000_000,
+    )
+}
+
+/// A config entry for a pool trading `base_coin` against `quote_coin`
+pub fn pool_config(base_coin: &str, quote_coin: &str) -> Pool {
+    Pool {
+        address: String::new(),
+        base_coin: base_coin.to_string(),
+        quote_coin: quote_coin.to_string(),
+    }
+}